@@ -19,4 +19,10 @@ pub(crate) enum AttributeKey {
 pub(crate) enum InternalAttributeKey {
     /// The attribute key for panic handling.
     Panic,
+    /// The attribute key for the number of path segments consumed by mount prefixes.
+    MountDepth,
+    /// The attribute key for the normalized path the route was matched against.
+    RoutePath,
+    /// The attribute key for the request target exactly as sent by the client.
+    RequestTarget,
 }
@@ -0,0 +1,11 @@
+/// The WebSocket protocol version sent in the handshake.
+pub(crate) const WS_CLIENT_VERSION: &str = "13";
+
+/// The status code accepting a WebSocket upgrade.
+pub(crate) const WS_SWITCHING_PROTOCOLS: usize = 101;
+
+/// The length of the random nonce encoded in `Sec-WebSocket-Key`.
+pub(crate) const WS_CLIENT_KEY_LEN: usize = 16;
+
+/// The largest handshake response the client accepts.
+pub(crate) const WS_CLIENT_MAX_HANDSHAKE_LEN: usize = 16 * 1024;
@@ -0,0 +1,16 @@
+use crate::*;
+
+/// A complete message received by `WebSocketClient`.
+#[derive(Clone, CustomDebug, DisplayDebug, PartialEq, Eq)]
+pub enum WebSocketMessage {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+    /// A ping frame, which the client has already answered.
+    Ping(Vec<u8>),
+    /// A pong frame.
+    Pong(Vec<u8>),
+    /// The server's close frame.
+    Close(WebSocketClose),
+}
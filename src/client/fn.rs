@@ -0,0 +1,51 @@
+use crate::*;
+
+/// Produces random bytes for handshake nonces and masking keys.
+///
+/// The bytes come from the randomly keyed hasher of the standard library, which is
+/// unpredictable enough for a test client without pulling in a random number crate.
+///
+/// # Arguments
+///
+/// - `usize` - The number of bytes to produce.
+///
+/// # Returns
+///
+/// - `Vec<u8>` - The random bytes.
+pub(crate) fn ws_client_random_bytes(len: usize) -> Vec<u8> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut bytes: Vec<u8> = Vec::with_capacity(len + 8);
+    while bytes.len() < len {
+        let value: u64 = RandomState::new().hash_one(COUNTER.fetch_add(1, Ordering::Relaxed));
+        bytes.extend_from_slice(&value.to_be_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+/// Encodes a single masked WebSocket frame as sent by a client.
+///
+/// # Arguments
+///
+/// - `bool` - Whether the frame is the final fragment of a message.
+/// - `WebSocketOpcode` - The opcode of the frame.
+/// - `&[u8]` - The payload of the frame.
+///
+/// # Returns
+///
+/// - `Vec<u8>` - The encoded frame.
+pub(crate) fn encode_ws_client_frame(
+    fin: bool,
+    opcode: WebSocketOpcode,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut frame: Vec<u8> = encode_ws_frame(fin, 0, opcode, payload);
+    let header_len: usize = frame.len() - payload.len();
+    let mask_key: Vec<u8> = ws_client_random_bytes(WS_MASK_KEY_LEN);
+    frame[1] |= WS_MASK_BIT;
+    for (position, byte) in frame[header_len..].iter_mut().enumerate() {
+        *byte ^= mask_key[position % WS_MASK_KEY_LEN];
+    }
+    frame.splice(header_len..header_len, mask_key);
+    frame
+}
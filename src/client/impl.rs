@@ -0,0 +1,381 @@
+use crate::*;
+
+impl WebSocketClient {
+    /// Connects to a WebSocket route and performs the opening handshake.
+    ///
+    /// # Arguments
+    ///
+    /// - `A` - The server address, such as `127.0.0.1:60000`.
+    /// - `P` - The request path, such as `/ws`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientWebSocketClientError` - The connected client or an error.
+    pub async fn connect<A, P>(addr: A, path: P) -> ResultWebSocketClientWebSocketClientError
+    where
+        A: ToString,
+        P: ToString,
+    {
+        Self::connect_with_headers(addr, path, Vec::<(String, String)>::new()).await
+    }
+
+    /// Connects to a WebSocket route, sending extra headers with the handshake request.
+    ///
+    /// # Arguments
+    ///
+    /// - `A` - The server address, such as `127.0.0.1:60000`.
+    /// - `P` - The request path, such as `/ws`.
+    /// - `I` - The extra headers, such as `Origin` or `Sec-WebSocket-Protocol`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientWebSocketClientError` - The connected client or an error.
+    pub async fn connect_with_headers<A, P, I, K, V>(
+        addr: A,
+        path: P,
+        headers: I,
+    ) -> ResultWebSocketClientWebSocketClientError
+    where
+        A: ToString,
+        P: ToString,
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: ToString,
+    {
+        let addr: String = addr.to_string();
+        let mut stream: TcpStream = TcpStream::connect(&addr)
+            .await
+            .map_err(|err| WebSocketClientError::Connect(err.to_string()))?;
+        let key: String = WebSocketFrame::base64_encode(&ws_client_random_bytes(WS_CLIENT_KEY_LEN));
+        let mut request: String = format!(
+            "{}{}{}{}{}{}",
+            GET,
+            SPACE,
+            path.to_string(),
+            SPACE,
+            HTTP_VERSION_1_1,
+            HTTP_BR
+        );
+        let mut lines: Vec<(String, String)> = vec![
+            (HOST.to_owned(), addr),
+            (UPGRADE.to_owned(), WEBSOCKET.to_owned()),
+            (CONNECTION.to_owned(), UPGRADE.to_owned()),
+            (SEC_WEBSOCKET_KEY.to_owned(), key.clone()),
+            (
+                SEC_WEBSOCKET_VERSION.to_owned(),
+                WS_CLIENT_VERSION.to_owned(),
+            ),
+        ];
+        lines.extend(
+            headers
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        for (name, value) in lines {
+            request.push_str(&format!("{}{}{}{}", name, COLON_SPACE, value, HTTP_BR));
+        }
+        request.push_str(HTTP_BR);
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|err| WebSocketClientError::Io(err.to_string()))?;
+        let mut client: WebSocketClient = WebSocketClient {
+            stream,
+            headers: HashMap::new(),
+            reader: WebSocketReader::default(),
+            close_sent: false,
+        };
+        client.read_handshake(&key).await?;
+        Ok(client)
+    }
+
+    /// Reads and checks the handshake response.
+    ///
+    /// Bytes received after the response are kept as the start of the first frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The `Sec-WebSocket-Key` sent with the request.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - An error if the server did not accept the upgrade.
+    async fn read_handshake(&mut self, key: &str) -> ResultWebSocketClientError {
+        let mut buffer: Vec<u8> = Vec::new();
+        let end: usize = loop {
+            if let Some(index) = buffer
+                .windows(HTTP_DOUBLE_BR_BYTES.len())
+                .position(|window: &[u8]| window == HTTP_DOUBLE_BR_BYTES)
+            {
+                break index;
+            }
+            if buffer.len() > WS_CLIENT_MAX_HANDSHAKE_LEN {
+                return Err(WebSocketClientError::Handshake(
+                    "handshake response too large".to_owned(),
+                ));
+            }
+            self.read_more(&mut buffer).await?;
+        };
+        let head: String = String::from_utf8_lossy(&buffer[..end]).into_owned();
+        self.reader
+            .get_mut_buffer()
+            .extend_from_slice(&buffer[end + HTTP_DOUBLE_BR_BYTES.len()..]);
+        let mut lines = head.split(HTTP_BR);
+        let status_line: &str = lines.next().unwrap_or_default();
+        let status: usize = status_line
+            .split(SPACE)
+            .nth(1)
+            .and_then(|code: &str| code.parse().ok())
+            .unwrap_or_default();
+        for line in lines {
+            if let Some((name, value)) = line.split_once(COLON) {
+                self.headers
+                    .insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+            }
+        }
+        if status != WS_SWITCHING_PROTOCOLS {
+            return Err(WebSocketClientError::Handshake(status_line.to_owned()));
+        }
+        if self.get_header(SEC_WEBSOCKET_ACCEPT) != Some(&WebSocketFrame::generate_accept_key(key))
+        {
+            return Err(WebSocketClientError::Handshake(format!(
+                "invalid {} header",
+                SEC_WEBSOCKET_ACCEPT
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reads more bytes from the connection into a buffer.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut Vec<u8>` - The buffer to append to.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - An error if the connection failed or was closed.
+    async fn read_more(&mut self, buffer: &mut Vec<u8>) -> ResultWebSocketClientError {
+        let mut temp_buffer: Vec<u8> = vec![0; DEFAULT_BUFFER_SIZE];
+        match self.stream.read(&mut temp_buffer).await {
+            Ok(0) => Err(WebSocketClientError::Closed),
+            Ok(len) => {
+                buffer.extend_from_slice(&temp_buffer[..len]);
+                Ok(())
+            }
+            Err(err) => Err(WebSocketClientError::Io(err.to_string())),
+        }
+    }
+
+    /// Retrieves the headers of the handshake response.
+    ///
+    /// # Returns
+    ///
+    /// - `&HashMapStringString` - The headers, keyed by lowercase name.
+    pub fn get_headers(&self) -> &HashMapStringString {
+        &self.headers
+    }
+
+    /// Retrieves a header of the handshake response.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The header name, compared case-insensitively.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&String>` - The header value, if present.
+    pub fn get_header(&self, name: &str) -> Option<&String> {
+        self.headers.get(&name.to_ascii_lowercase())
+    }
+
+    /// Retrieves the subprotocol selected by the server.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&String>` - The value of `Sec-WebSocket-Protocol`, if the server sent one.
+    pub fn get_protocol(&self) -> Option<&String> {
+        self.get_header(SEC_WEBSOCKET_PROTOCOL)
+    }
+
+    /// Sends a single masked frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether the frame is the final fragment of a message.
+    /// - `WebSocketOpcode` - The opcode of the frame.
+    /// - `&[u8]` - The payload of the frame.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - The outcome of the send operation.
+    pub async fn send_frame(
+        &mut self,
+        fin: bool,
+        opcode: WebSocketOpcode,
+        payload: &[u8],
+    ) -> ResultWebSocketClientError {
+        if self.close_sent {
+            return Err(WebSocketClientError::Closed);
+        }
+        self.stream
+            .write_all(&encode_ws_client_frame(fin, opcode, payload))
+            .await
+            .map_err(|err| WebSocketClientError::Io(err.to_string()))
+    }
+
+    /// Sends a text message.
+    ///
+    /// # Arguments
+    ///
+    /// - `T` - The text implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - The outcome of the send operation.
+    pub async fn send_text<T: ToString>(&mut self, text: T) -> ResultWebSocketClientError {
+        self.send_frame(true, WebSocketOpcode::Text, text.to_string().as_bytes())
+            .await
+    }
+
+    /// Sends a binary message.
+    ///
+    /// # Arguments
+    ///
+    /// - `B` - The binary payload.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - The outcome of the send operation.
+    pub async fn send_binary<B: AsRef<[u8]>>(&mut self, data: B) -> ResultWebSocketClientError {
+        self.send_frame(true, WebSocketOpcode::Binary, data.as_ref())
+            .await
+    }
+
+    /// Sends a ping frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `B` - The ping payload of at most 125 bytes.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - The outcome of the send operation.
+    pub async fn send_ping<B: AsRef<[u8]>>(&mut self, payload: B) -> ResultWebSocketClientError {
+        self.send_frame(true, WebSocketOpcode::Ping, payload.as_ref())
+            .await
+    }
+
+    /// Sends a close frame.
+    ///
+    /// Keep calling `read_message` until it returns the server's `WebSocketMessage::Close`
+    /// to complete the close handshake.
+    ///
+    /// # Arguments
+    ///
+    /// - `u16` - The close status code.
+    /// - `R` - The close reason implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketClientError` - The outcome of the send operation.
+    pub async fn send_close<R: ToString>(
+        &mut self,
+        code: u16,
+        reason: R,
+    ) -> ResultWebSocketClientError {
+        let payload: Vec<u8> = WebSocketClose::new(code, reason).to_payload();
+        self.send_frame(true, WebSocketOpcode::Close, &payload)
+            .await?;
+        self.close_sent = true;
+        Ok(())
+    }
+
+    /// Reads the next frame sent by the server.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<WebSocketFrameData, WebSocketClientError>` - The frame or an error.
+    async fn read_frame(&mut self) -> Result<WebSocketFrameData, WebSocketClientError> {
+        loop {
+            if let Some((frame, consumed)) = decode_ws_frame(self.reader.get_buffer()) {
+                self.reader.get_mut_buffer().drain(..consumed);
+                if *frame.get_masked() {
+                    return Err(WebSocketClientError::Protocol(
+                        "masked server frame".to_owned(),
+                    ));
+                }
+                return Ok(frame);
+            }
+            let mut buffer: Vec<u8> = std::mem::take(self.reader.get_mut_buffer());
+            let result: ResultWebSocketClientError = self.read_more(&mut buffer).await;
+            *self.reader.get_mut_buffer() = buffer;
+            result?;
+        }
+    }
+
+    /// Reads the next message sent by the server.
+    ///
+    /// Fragmented messages are reassembled, and pings are answered before they are returned.
+    /// A message violating the protocol, such as a text message that is not valid UTF-8,
+    /// is answered with a close frame carrying the matching code, such as 1007.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketMessageWebSocketClientError` - The message or an error.
+    pub async fn read_message(&mut self) -> ResultWebSocketMessageWebSocketClientError {
+        loop {
+            let frame: WebSocketFrameData = self.read_frame().await?;
+            let opcode: WebSocketOpcode = *frame.get_opcode();
+            match opcode {
+                WebSocketOpcode::Ping => {
+                    if !self.close_sent {
+                        self.send_frame(true, WebSocketOpcode::Pong, frame.get_payload())
+                            .await?;
+                    }
+                    return Ok(WebSocketMessage::Ping(frame.get_payload().clone()));
+                }
+                WebSocketOpcode::Pong => {
+                    return Ok(WebSocketMessage::Pong(frame.get_payload().clone()));
+                }
+                WebSocketOpcode::Close => {
+                    let close: WebSocketClose = WebSocketClose::from_payload(frame.get_payload());
+                    if !self.close_sent {
+                        let code: Vec<u8> = frame
+                            .get_payload()
+                            .get(..WS_CLOSE_CODE_LEN)
+                            .unwrap_or_default()
+                            .to_vec();
+                        self.send_frame(true, WebSocketOpcode::Close, &code).await?;
+                        self.close_sent = true;
+                    }
+                    return Ok(WebSocketMessage::Close(close));
+                }
+                WebSocketOpcode::Reserved(_) => {
+                    return Err(WebSocketClientError::Protocol("reserved opcode".to_owned()));
+                }
+                _ => {}
+            }
+            let message_opcode: WebSocketOpcode = match self.reader.get_message_opcode() {
+                Some(message_opcode) if opcode.is_continuation() => *message_opcode,
+                _ => opcode,
+            };
+            let message: RequestBody = match self.reader.assemble(frame) {
+                Ok(Some(message)) => message,
+                Ok(None) => continue,
+                Err(err) => {
+                    if let Some(close) = self.reader.take_violation() {
+                        let _ = self.send_close(*close.get_code(), close.get_reason()).await;
+                    }
+                    return Err(WebSocketClientError::Protocol(err.to_string()));
+                }
+            };
+            return match message_opcode {
+                WebSocketOpcode::Text => String::from_utf8(message)
+                    .map(WebSocketMessage::Text)
+                    .map_err(|err| WebSocketClientError::Protocol(err.to_string())),
+                _ => Ok(WebSocketMessage::Binary(message)),
+            };
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub(crate) mod r#const;
+pub(crate) mod r#enum;
+pub(crate) mod r#fn;
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#type;
+
+pub use r#enum::*;
+pub use r#struct::*;
+pub use r#type::*;
+
+pub(crate) use r#const::*;
+pub(crate) use r#fn::*;
@@ -0,0 +1,18 @@
+use crate::*;
+
+/// A minimal WebSocket client for exercising WebSocket routes from tests.
+///
+/// It performs the opening handshake, masks every frame it sends, answers pings,
+/// and reassembles fragmented messages. Extensions such as compression are not offered.
+#[derive(CustomDebug, DisplayDebug)]
+pub struct WebSocketClient {
+    /// The connection to the server.
+    #[debug(skip)]
+    pub(super) stream: TcpStream,
+    /// The headers of the handshake response, keyed by lowercase name.
+    pub(super) headers: HashMapStringString,
+    /// The frame buffer and message reassembly.
+    pub(super) reader: WebSocketReader,
+    /// Whether a close frame has been sent to the server.
+    pub(super) close_sent: bool,
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+/// The result of connecting a WebSocket client.
+pub type ResultWebSocketClientWebSocketClientError = Result<WebSocketClient, WebSocketClientError>;
+
+/// The result of reading a message with a WebSocket client.
+pub type ResultWebSocketMessageWebSocketClientError =
+    Result<WebSocketMessage, WebSocketClientError>;
+
+/// The result of sending a frame with a WebSocket client.
+pub type ResultWebSocketClientError = Result<(), WebSocketClientError>;
+
+/// The headers of the handshake response, keyed by lowercase name.
+pub(crate) type HashMapStringString = HashMap<String, String>;
@@ -13,9 +13,40 @@ impl Default for ServerConfig {
             port: DEFAULT_WEB_PORT,
             ws_buffer: DEFAULT_BUFFER_SIZE,
             http_buffer: DEFAULT_BUFFER_SIZE,
+            http_max_body_size: DEFAULT_HTTP_MAX_BODY_SIZE,
             nodelay: DEFAULT_NODELAY,
             linger: DEFAULT_LINGER,
             ttl: DEFAULT_TTI,
+            trailing_slash: None,
+            merge_slashes: false,
+            remove_dot_segments: false,
+            decode_route_params: false,
+            case_sensitive: true,
+            ws_close_timeout: DEFAULT_WS_CLOSE_TIMEOUT,
+            ws_heartbeat: WebSocketHeartbeat::default(),
+            ws_deflate: None,
+            ws_policy: WebSocketPolicy::default(),
+            ws_queue_capacity: DEFAULT_WS_QUEUE_CAPACITY,
+            ws_max_message_size: DEFAULT_WS_MAX_MESSAGE_SIZE,
+            sse_keep_alive: DEFAULT_SSE_KEEP_ALIVE,
+            proxy_protocol: ProxyProtocol::default(),
+            proxy_protocol_sources: Vec::new(),
+            trusted_proxies: Vec::new(),
+            listen: Vec::new(),
+            #[cfg(feature = "tls")]
+            tls_handshake_timeout: DEFAULT_TLS_HANDSHAKE_TIMEOUT,
+            #[cfg(feature = "http2")]
+            http2: None,
+            #[cfg(unix)]
+            unix_socket: None,
+            #[cfg(unix)]
+            unix_socket_mode: None,
+            #[cfg(unix)]
+            unix_socket_remove_stale: DEFAULT_UNIX_SOCKET_REMOVE_STALE,
+            #[cfg(unix)]
+            upgrade_signal: false,
+            #[cfg(unix)]
+            upgrade_drain_timeout: DEFAULT_UPGRADE_DRAIN_TIMEOUT,
         }
     }
 }
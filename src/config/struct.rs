@@ -26,6 +26,11 @@ pub(crate) struct ServerConfig {
     #[get_mut(pub(crate))]
     #[set(pub(crate))]
     pub(super) http_buffer: usize,
+    /// The size limit of a request body.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) http_max_body_size: usize,
     /// The TCP_NODELAY option for sockets.
     #[get(pub(crate))]
     #[get_mut(pub(crate))]
@@ -41,4 +46,128 @@ pub(crate) struct ServerConfig {
     #[get_mut(pub(crate))]
     #[set(pub(crate))]
     pub(super) ttl: OptionU32,
+    /// The policy applied to a trailing slash in the request path.
+    ///
+    /// While unset, empty path segments are skipped when matching dynamic and regex routes.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) trailing_slash: OptionTrailingSlash,
+    /// Whether consecutive slashes in the request path are collapsed before matching.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) merge_slashes: bool,
+    /// Whether `.` and `..` segments are removed from the request path before matching.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) remove_dot_segments: bool,
+    /// Whether captured route parameters are percent-decoded.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) decode_route_params: bool,
+    /// Whether static route segments are matched case-sensitively.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) case_sensitive: bool,
+    /// The time to wait for the peer's close frame after sending one.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) ws_close_timeout: Duration,
+    /// The heartbeat settings applied to WebSocket connections without a route-specific setting.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) ws_heartbeat: WebSocketHeartbeat,
+    /// The `permessage-deflate` settings, or None if compression is not offered.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) ws_deflate: OptionWebSocketDeflate,
+    /// The upgrade policy applied to WebSocket handshakes without a route-specific policy.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) ws_policy: WebSocketPolicy,
+    /// The number of broadcast messages queued per WebSocket connection.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) ws_queue_capacity: usize,
+    /// The size limit of a reassembled WebSocket message.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) ws_max_message_size: usize,
+    /// The interval between keep-alive comments on an idle event stream, zero to disable them.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) sse_keep_alive: Duration,
+    /// Whether connections start with a PROXY protocol header.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) proxy_protocol: ProxyProtocol,
+    /// The sources an optional PROXY protocol header is read from.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) proxy_protocol_sources: VecIpCidr,
+    /// The proxies whose forwarding headers are trusted.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) trusted_proxies: VecTrustedProxy,
+    /// The addresses to listen on, or empty to listen on the host and port.
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) listen: VecListenAddr,
+    /// The longest time a client may take to complete the TLS handshake.
+    #[cfg(feature = "tls")]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) tls_handshake_timeout: Duration,
+    /// The HTTP/2 settings, or None if only HTTP/1.1 is served.
+    #[cfg(feature = "http2")]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) http2: OptionHttp2Settings,
+    /// The path of the Unix domain socket to listen on instead of the TCP address.
+    #[cfg(unix)]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) unix_socket: OptionPathBuf,
+    /// The file mode applied to the Unix domain socket file, or None to keep the umask default.
+    #[cfg(unix)]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) unix_socket_mode: OptionU32,
+    /// Whether a socket file left behind by a stopped server is removed before binding.
+    #[cfg(unix)]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) unix_socket_remove_stale: bool,
+    /// Whether `SIGUSR2` hands the listeners over to a new process of the server.
+    #[cfg(unix)]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) upgrade_signal: bool,
+    /// The time an upgraded server waits for in-flight connections before exiting.
+    #[cfg(unix)]
+    #[get(pub(crate))]
+    #[get_mut(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) upgrade_drain_timeout: Duration,
 }
@@ -19,18 +19,27 @@ impl Context {
     ///
     /// # Arguments
     ///
-    /// - `&ArcRwLockStream` - The network stream.
+    /// - `&ConnectionStream` - The network stream.
     /// - `&Request` - The HTTP request.
+    /// - `&Server` - The server that accepted the connection.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
     ///
     /// # Returns
     ///
     /// - `Context` - The newly created context.
-    pub(crate) fn create_context(stream: &ArcRwLockStream, request: &Request) -> Context {
+    pub(crate) fn create_context(
+        stream: &ConnectionStream,
+        request: &Request,
+        server: &Server,
+        connection: &ConnectionInfo,
+    ) -> Context {
         Context::from_internal_context({
             let mut internal_ctx: ContextInner = ContextInner::default();
             internal_ctx
                 .set_stream(Some(stream.clone()))
-                .set_request(request.clone());
+                .set_request(request.clone())
+                .set_server(Some(server.clone()))
+                .set_connection(connection.clone());
             internal_ctx
         })
     }
@@ -141,10 +150,17 @@ impl Context {
 
     /// Retrieves the underlying network stream, if available.
     ///
+    /// The stream is the same type for TCP, TLS and Unix domain socket connections.
+    ///
+    /// This replaces the `OptionArcRwLockStream` returned by earlier versions, which
+    /// handed out the raw `TcpStream`. A connection may be encrypted or not use TCP at
+    /// all, so no `TcpStream` is available any more; write through `send`, `send_body`
+    /// and `flush` of the returned stream instead.
+    ///
     /// # Returns
     ///
-    /// - `OptionArcRwLockStream` - The thread-safe, shareable network stream if it exists.
-    pub async fn get_stream(&self) -> OptionArcRwLockStream {
+    /// - `OptionConnectionStream` - The thread-safe, shareable network stream if it exists.
+    pub async fn get_stream(&self) -> OptionConnectionStream {
         self.read().await.get_stream().clone()
     }
 
@@ -152,13 +168,10 @@ impl Context {
     ///
     /// # Returns
     ///
-    /// - `OptionSocketAddr` - The socket address of the remote peer if available.
+    /// - `OptionSocketAddr` - The socket address of the remote peer if available, None for
+    ///   connections on a Unix domain socket.
     pub async fn get_socket_addr(&self) -> OptionSocketAddr {
-        let stream_result: OptionArcRwLockStream = self.get_stream().await;
-        if stream_result.is_none() {
-            return None;
-        }
-        stream_result.unwrap().read().await.peer_addr().ok()
+        *self.read().await.get_connection().get_peer_addr()
     }
 
     /// Retrieves the remote socket address or a default value if unavailable.
@@ -167,24 +180,74 @@ impl Context {
     ///
     /// - `SocketAddr` - The socket address of the remote peer, or default if unavailable.
     pub async fn get_socket_addr_or_default(&self) -> SocketAddr {
-        let stream_result: OptionArcRwLockStream = self.get_stream().await;
-        if stream_result.is_none() {
-            return DEFAULT_SOCKET_ADDR;
-        }
-        stream_result
-            .unwrap()
-            .read()
+        self.get_socket_addr().await.unwrap_or(DEFAULT_SOCKET_ADDR)
+    }
+
+    /// Retrieves the tag of the listener that accepted the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionString` - The tag given to `Server::listen_tagged`, or None for untagged listeners.
+    pub async fn get_listener_tag(&self) -> OptionString {
+        self.read()
             .await
-            .peer_addr()
-            .unwrap_or(DEFAULT_SOCKET_ADDR)
+            .get_connection()
+            .get_listener_tag()
+            .clone()
+    }
+
+    /// Retrieves the parameters negotiated during the TLS handshake.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionTlsInfo` - The TLS parameters, or None if the connection is not encrypted.
+    pub async fn get_tls_info(&self) -> OptionTlsInfo {
+        self.read().await.get_connection().get_tls().clone()
+    }
+
+    /// Checks whether the request arrived on an HTTP/2 stream.
+    ///
+    /// HTTP/2 requests run through the same middleware and routes as HTTP/1.1 requests
+    /// and report `HTTP/2` as their version. Their responses are sent as HTTP/2 frames.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the request is served over HTTP/2, false otherwise.
+    pub async fn is_http2(&self) -> bool {
+        *self.read().await.get_connection().get_http2()
+    }
+
+    /// Retrieves the PROXY protocol header the connection started with.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionProxyHeader` - The header, or None if the PROXY protocol is disabled or the
+    ///   connection did not start with a header.
+    pub async fn get_proxy_header(&self) -> OptionProxyHeader {
+        self.read().await.get_connection().get_proxy().clone()
+    }
+
+    /// Retrieves the peer of a connection accepted on a Unix domain socket.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionUnixPeer` - The socket path and the peer credentials, or None for TCP connections.
+    #[cfg(unix)]
+    pub async fn get_unix_peer(&self) -> OptionUnixPeer {
+        self.read().await.get_connection().get_unix().clone()
     }
 
     /// Retrieves the remote socket address as a string.
     ///
     /// # Returns
     ///
-    /// - `OptionString` - The string representation of the socket address if available.
+    /// - `OptionString` - The string representation of the socket address if available,
+    ///   such as `unix:/run/app.sock` for connections on a Unix domain socket.
     pub async fn get_socket_addr_string(&self) -> OptionString {
+        #[cfg(unix)]
+        if let Some(peer) = self.get_unix_peer().await {
+            return Some(peer.to_address_string());
+        }
         self.get_socket_addr().await.map(|data| data.to_string())
     }
 
@@ -194,6 +257,10 @@ impl Context {
     ///
     /// - `String` - The string representation of the socket address, or default if unavailable.
     pub async fn get_socket_addr_or_default_string(&self) -> String {
+        #[cfg(unix)]
+        if let Some(peer) = self.get_unix_peer().await {
+            return peer.to_address_string();
+        }
         self.get_socket_addr_or_default().await.to_string()
     }
 
@@ -219,6 +286,119 @@ impl Context {
             .map(|socket_addr: SocketAddr| socket_addr.port())
     }
 
+    /// Retrieves the address of the client, as reported by trusted proxies.
+    ///
+    /// The forwarding header the peer was trusted with, `Forwarded` or `X-Forwarded-For`,
+    /// is walked from the right for as long as the hops are trusted proxies of the server.
+    /// The first untrusted hop is the client. Without trusted proxies, or if the peer is not one,
+    /// this is the address returned by `get_socket_host`.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionSocketHost` - The IP address of the client if available.
+    pub async fn get_client_ip(&self) -> OptionSocketHost {
+        *self.resolve_client().await.get_ip()
+    }
+
+    /// Retrieves the protocol the client sent the request over, as reported by trusted proxies.
+    ///
+    /// The forwarded protocol is only read from proxies trusted with `ForwardedHeaders::with_proto`.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The lowercase scheme, such as `https`, falling back to the scheme of
+    ///   the connection.
+    pub async fn get_client_scheme(&self) -> String {
+        self.resolve_client()
+            .await
+            .get_proto()
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Retrieves the host the client sent the request to, as reported by trusted proxies.
+    ///
+    /// The forwarded host is only read from proxies trusted with `ForwardedHeaders::with_host`.
+    ///
+    /// # Returns
+    ///
+    /// - `RequestHost` - The host, falling back to the host of the request.
+    pub async fn get_client_host(&self) -> RequestHost {
+        self.resolve_client()
+            .await
+            .get_host()
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Resolves the client hop from the forwarding headers and the trusted proxies.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHop` - The address, protocol and host of the client.
+    async fn resolve_client(&self) -> ForwardedHop {
+        let scheme: &str = if self.get_tls_info().await.is_some() {
+            HTTPS_LOWERCASE
+        } else {
+            HTTP_LOWERCASE
+        };
+        let peer: ForwardedHop = ForwardedHop::new(
+            self.get_socket_host().await,
+            Some(scheme.to_string()),
+            Some(self.get_request_host().await),
+        );
+        let trusted_proxies: VecTrustedProxy = match self.get_server().await {
+            Some(server) => server.get_config().await.get_trusted_proxies().clone(),
+            None => Vec::new(),
+        };
+        let Some(headers) = peer.get_ip().and_then(|ip: IpAddr| {
+            trusted_proxies
+                .iter()
+                .find(|trusted_proxy: &&TrustedProxy| trusted_proxy.get_cidr().contains(&ip))
+                .map(|trusted_proxy: &TrustedProxy| *trusted_proxy.get_headers())
+        }) else {
+            return peer;
+        };
+        let hops: VecForwardedHop = match headers.get_header() {
+            ForwardedHeader::Forwarded => {
+                parse_forwarded(&self.get_request_header_joined(FORWARDED).await)
+            }
+            ForwardedHeader::XForwarded => parse_x_forwarded(
+                &self.get_request_header_joined(X_FORWARDED_FOR).await,
+                &self.get_request_header_joined(X_FORWARDED_PROTO).await,
+                &self.get_request_header_joined(X_FORWARDED_HOST).await,
+            ),
+        };
+        let hops: VecForwardedHop = hops
+            .into_iter()
+            .map(|hop: ForwardedHop| headers.restrict(hop))
+            .collect();
+        let cidrs: VecIpCidr = trusted_proxies
+            .iter()
+            .filter(|trusted_proxy: &&TrustedProxy| {
+                trusted_proxy.get_headers().get_header() == headers.get_header()
+            })
+            .map(|trusted_proxy: &TrustedProxy| *trusted_proxy.get_cidr())
+            .collect();
+        resolve_forwarded(peer, &hops, &cidrs)
+    }
+
+    /// Retrieves every value of a request header, joined by commas.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The key of the header.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The joined values, or an empty string if the header is absent.
+    async fn get_request_header_joined(&self, key: &str) -> String {
+        self.get_request_header(key)
+            .await
+            .map(|values: RequestHeadersValue| Vec::from(values).join(","))
+            .unwrap_or_default()
+    }
+
     /// Retrieves the current HTTP request.
     ///
     /// # Returns
@@ -333,11 +513,17 @@ impl Context {
 
     /// Retrieves the body of the request.
     ///
+    /// On a WebSocket connection this is the payload of the last complete message.
+    ///
     /// # Returns
     ///
     /// - `RequestBody` - A clone of the request's body.
     pub async fn get_request_body(&self) -> RequestBody {
-        self.read().await.get_request().get_body().clone()
+        let inner: RwLockReadContextInner = self.read().await;
+        match inner.get_ws_message() {
+            Some(message) => message.clone(),
+            None => inner.get_request().get_body().clone(),
+        }
     }
 
     /// Retrieves the request body as a string.
@@ -346,7 +532,7 @@ impl Context {
     ///
     /// - `String` - The request body converted to a string.
     pub async fn get_request_body_string(&self) -> String {
-        self.read().await.get_request().get_body_string()
+        String::from_utf8_lossy(&self.get_request_body().await).into_owned()
     }
 
     /// Deserializes the request body from JSON into a specified type.
@@ -358,7 +544,7 @@ impl Context {
     where
         J: DeserializeOwned,
     {
-        self.read().await.get_request().get_body_json()
+        json_from_slice(&self.get_request_body().await)
     }
 
     /// Retrieves a specific request header by its key.
@@ -981,6 +1167,37 @@ impl Context {
         self.read().await.get_route_params().get(name).cloned()
     }
 
+    /// Retrieves the server that accepted the connection, if available.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionServer` - The server handling this context.
+    pub async fn get_server(&self) -> OptionServer {
+        self.read().await.get_server().clone()
+    }
+
+    /// Generates the URL path of a named route registered on the server.
+    ///
+    /// # Arguments
+    ///
+    /// - `N` - The route name implementing `ToString`.
+    /// - `&RouteParams` - The values to substitute into the route pattern.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultStringRouteError` - The generated path, or a `RouteError` on failure.
+    pub async fn url_for<N: ToString>(
+        &self,
+        name: N,
+        params: &RouteParams,
+    ) -> ResultStringRouteError {
+        let name: String = name.to_string();
+        match self.get_server().await {
+            Some(server) => server.url_for(&name, params).await,
+            None => Err(RouteError::UnknownRouteName(name)),
+        }
+    }
+
     /// Retrieves all attributes stored in the context.
     ///
     /// # Returns
@@ -1125,6 +1342,128 @@ impl Context {
             .await
     }
 
+    /// Retrieves the number of path segments consumed by mount prefixes.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The mount depth, 0 outside of nested servers.
+    pub(crate) async fn get_mount_depth(&self) -> usize {
+        self.get_internal_attribute(InternalAttributeKey::MountDepth)
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Sets the number of path segments consumed by mount prefixes.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The mount depth to store.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - A reference to the modified context.
+    pub(crate) async fn set_mount_depth(&self, depth: usize) -> &Self {
+        self.set_internal_attribute(InternalAttributeKey::MountDepth, depth)
+            .await
+    }
+
+    /// Retrieves the normalized path the route was matched against.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The matched path, or the request path if no route was resolved.
+    pub(crate) async fn get_route_path(&self) -> String {
+        match self
+            .get_internal_attribute(InternalAttributeKey::RoutePath)
+            .await
+        {
+            Some(path) => path,
+            None => self.get_request_path().await,
+        }
+    }
+
+    /// Sets the normalized path the route was matched against.
+    ///
+    /// # Arguments
+    ///
+    /// - `String` - The matched path to store.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - A reference to the modified context.
+    pub(crate) async fn set_route_path(&self, path: String) -> &Self {
+        self.set_internal_attribute(InternalAttributeKey::RoutePath, path)
+            .await
+    }
+
+    /// Retrieves the request target exactly as sent by the client.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The raw target including the query string, or the request path if none was stored.
+    pub(crate) async fn get_request_target(&self) -> String {
+        match self
+            .get_internal_attribute(InternalAttributeKey::RequestTarget)
+            .await
+        {
+            Some(target) => target,
+            None => self.get_request_path().await,
+        }
+    }
+
+    /// Sets the request target exactly as sent by the client.
+    ///
+    /// # Arguments
+    ///
+    /// - `String` - The raw target to store.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - A reference to the modified context.
+    pub(crate) async fn set_request_target(&self, target: String) -> &Self {
+        self.set_internal_attribute(InternalAttributeKey::RequestTarget, target)
+            .await
+    }
+
+    /// Splits the normalized request path at the current mount point.
+    ///
+    /// # Returns
+    ///
+    /// - `(String, String)` - The mount prefix and the path relative to it.
+    async fn split_mount_path(&self) -> (String, String) {
+        let depth: usize = self.get_mount_depth().await;
+        let path: String = self.get_route_path().await;
+        if depth == 0 {
+            return (String::new(), path);
+        }
+        let trimmed: &str = path.strip_prefix(DEFAULT_HTTP_PATH).unwrap_or(&path);
+        let mut parts = trimmed.splitn(depth + 1, DEFAULT_HTTP_PATH);
+        let prefix: Vec<&str> = parts.by_ref().take(depth).collect();
+        let rest: &str = parts.next().unwrap_or_default();
+        (
+            format!("{}{}", DEFAULT_HTTP_PATH, prefix.join(DEFAULT_HTTP_PATH)),
+            format!("{}{}", DEFAULT_HTTP_PATH, rest),
+        )
+    }
+
+    /// Retrieves the part of the request path matched by the mount prefixes of nested servers.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The mount prefix, empty outside of nested servers.
+    pub async fn get_mount_prefix(&self) -> String {
+        self.split_mount_path().await.0
+    }
+
+    /// Retrieves the request path relative to the mount point of the nested server.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The relative path, equal to the full path outside of nested servers.
+    pub async fn get_request_relative_path(&self) -> String {
+        self.split_mount_path().await.1
+    }
+
     /// Checks if the connection has been terminated (aborted and closed).
     ///
     /// # Returns
@@ -1154,6 +1493,15 @@ impl Context {
     pub async fn upgrade_to_ws(&self) -> ResponseResult {
         if let Some(key) = &self.get_request_header_back(SEC_WEBSOCKET_KEY).await {
             let accept_key: String = WebSocketFrame::generate_accept_key(key);
+            let deflate: OptionWebSocketDeflateAgreement = self.negotiate_ws_deflate().await;
+            if let Some(agreement) = &deflate {
+                self.replace_response_header(SEC_WEBSOCKET_EXTENSIONS, agreement.to_header())
+                    .await;
+            }
+            if let Some(protocol) = self.get_ws_protocol().await {
+                self.replace_response_header(SEC_WEBSOCKET_PROTOCOL, protocol)
+                    .await;
+            }
             let result: ResponseResult = self
                 .set_response_version(HttpVersion::HTTP1_1)
                 .await
@@ -1167,6 +1515,20 @@ impl Context {
                 .await
                 .send()
                 .await;
+            if result.is_ok() {
+                let max_message_size: usize = match self.get_server().await {
+                    Some(server) => *server.get_config().await.get_ws_max_message_size(),
+                    None => DEFAULT_WS_MAX_MESSAGE_SIZE,
+                };
+                let websocket: WebSocketConnection = self.read().await.get_websocket().clone();
+                let mut reader: MutexGuard<WebSocketReader> = websocket.get_reader().lock().await;
+                *reader.get_mut_max_message_size() = max_message_size;
+                if let Some(agreement) = deflate {
+                    *reader.get_mut_inflater() = Some(agreement.inflater());
+                    *websocket.get_writer().lock().await.get_mut_deflater() =
+                        Some(agreement.deflater());
+                }
+            }
             return result;
         }
         Err(ResponseError::WebSocketHandShake(format!(
@@ -1175,142 +1537,1056 @@ impl Context {
         )))
     }
 
-    /// Reads an HTTP request from the underlying stream.
+    /// Applies an upgrade policy to the WebSocket handshake request.
+    ///
+    /// Selects the subprotocol of the connection and checks the `Origin` header.
     ///
     /// # Arguments
     ///
-    /// - `usize` - The read buffer size.
+    /// - `&WebSocketPolicy` - The upgrade policy of the route.
     ///
     /// # Returns
     ///
-    /// - `RequestReaderHandleResult` - The parsed request or error.
-    pub async fn http_from_stream(&self, buffer: usize) -> RequestReaderHandleResult {
-        if self.get_aborted().await {
-            return Err(RequestError::RequestAborted);
+    /// - `bool` - true if the handshake may proceed, false if it must be rejected.
+    pub(crate) async fn apply_ws_policy(&self, policy: &WebSocketPolicy) -> bool {
+        let origin: OptionString = self.get_request_header_back(ORIGIN).await;
+        if !policy.is_origin_allowed(origin.as_deref()) {
+            return false;
         }
-        if let Some(stream) = self.get_stream().await.as_ref() {
-            let request_res: RequestReaderHandleResult =
-                Request::http_from_stream(stream, buffer).await;
-            if let Ok(request) = request_res.as_ref() {
-                self.set_request(request).await;
-            }
-            return request_res;
-        };
-        Err(RequestError::GetTcpStream)
+        let offered: String = self
+            .get_request_header(SEC_WEBSOCKET_PROTOCOL)
+            .await
+            .map(|values| values.into_iter().collect::<Vec<String>>().join(COMMA))
+            .unwrap_or_default();
+        let protocol: OptionString = policy.select_protocol(&offered);
+        if protocol.is_none() && *policy.get_protocol_required() {
+            return false;
+        }
+        self.write()
+            .await
+            .get_mut_websocket()
+            .set_protocol(protocol);
+        true
     }
 
-    /// Reads a WebSocket frame from the underlying stream.
-    ///
-    /// # Arguments
-    ///
-    /// - `usize` - The read buffer size.
+    /// Retrieves the subprotocol selected during the WebSocket handshake.
     ///
     /// # Returns
     ///
-    /// - `RequestReaderHandleResult` - The parsed frame or error.
-    pub async fn ws_from_stream(&self, buffer: usize) -> RequestReaderHandleResult {
-        if self.get_aborted().await {
-            return Err(RequestError::RequestAborted);
-        }
-        if let Some(stream) = self.get_stream().await.as_ref() {
-            let mut last_request: Request = self.get_request().await;
-            let request_res: RequestReaderHandleResult =
-                Request::ws_from_stream(stream, buffer, &mut last_request).await;
-            match request_res.as_ref() {
-                Ok(request) => {
-                    self.set_request(&request).await;
-                }
-                Err(_) => {
-                    self.set_request(&last_request).await;
-                }
-            }
-            return request_res;
-        };
-        Err(RequestError::GetTcpStream)
+    /// - `OptionString` - The selected subprotocol, if any.
+    pub async fn get_ws_protocol(&self) -> OptionString {
+        self.read().await.get_websocket().get_protocol().clone()
     }
 
-    /// Updates the lifecycle status based on the current context state.
+    /// Retrieves the broadcast hub of the server that accepted the connection.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// - `&mut Lifecycle` - The lifecycle to update.
-    pub(crate) async fn update_lifecycle_status(&self, lifecycle: &mut Lifecycle) {
-        let keep_alive: bool = !self.get_closed().await && lifecycle.is_keep_alive();
-        let aborted: bool = self.get_aborted().await;
-        lifecycle.update_status(aborted, keep_alive);
+    /// - `WebSocketHub` - The server's hub, or an empty hub when the context has no server.
+    pub async fn get_ws_hub(&self) -> WebSocketHub {
+        match self.get_server().await {
+            Some(server) => server.get_ws_hub().await,
+            None => WebSocketHub::new(),
+        }
     }
 
-    /// Sends the response headers and body to the client.
+    /// Retrieves the broadcast member id of the connection.
     ///
     /// # Returns
     ///
-    /// - `ResponseResult` - The outcome of the send operation.
-    pub async fn send(&self) -> ResponseResult {
-        if self.is_terminated().await {
-            return Err(ResponseError::Terminated);
-        }
-        if let Some(stream) = self.get_stream().await {
-            let response_res: ResponseData = self.write().await.get_mut_response().build();
-            return stream.send(&response_res).await;
-        }
-        Err(ResponseError::NotFoundStream)
+    /// - `Option<u64>` - The id, or None if the connection never joined a room.
+    pub(crate) async fn get_ws_member_id(&self) -> Option<u64> {
+        self.read()
+            .await
+            .get_websocket()
+            .get_member()
+            .as_ref()
+            .map(|member: &WebSocketMember| *member.get_id())
     }
 
-    /// Sends the response and then closes the connection.
+    /// Retrieves the broadcast membership of the connection, creating it on first use.
     ///
-    /// After sending, the connection will be marked as closed.
+    /// Creating the membership spawns the task that drains the connection's bounded
+    /// broadcast queue into the socket.
     ///
     /// # Returns
     ///
-    /// - `ResponseResult` - The outcome of the send operation.
-    pub async fn send_once(&self) -> ResponseResult {
-        let res: ResponseResult = self.send().await;
-        self.closed().await;
-        res
+    /// - `WebSocketMember` - The membership of the connection.
+    async fn ws_member(&self) -> WebSocketMember {
+        let capacity: usize = match self.get_server().await {
+            Some(server) => *server.get_config().await.get_ws_queue_capacity(),
+            None => DEFAULT_WS_QUEUE_CAPACITY,
+        };
+        let mut inner: RwLockWriteContextInner = self.write().await;
+        if let Some(member) = inner.get_websocket().get_member() {
+            return member.clone();
+        }
+        let (member, mut receiver): (WebSocketMember, ReceiverWebSocketQueuedMessage) =
+            WebSocketMember::new(capacity);
+        inner.get_mut_websocket().set_member(Some(member.clone()));
+        drop(inner);
+        let ctx: Context = self.clone();
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                if ctx
+                    .send_ws_message(*message.get_opcode(), message.get_payload())
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        member
     }
 
-    /// Sends only the response body to the client.
+    /// Adds the connection to a broadcast room.
     ///
-    /// This is useful for streaming data or for responses where headers have already been sent.
+    /// # Arguments
+    ///
+    /// - `R` - The room name implementing `ToString`.
     ///
     /// # Returns
     ///
-    /// - `ResponseResult` - The outcome of the send operation.
-    pub async fn send_body(&self) -> ResponseResult {
+    /// - `ResponseResult` - Ok once the connection joined the room, or an error if the
+    ///   context is terminated or is not a WebSocket connection.
+    pub async fn ws_join<R: ToString>(&self, room: R) -> ResponseResult {
         if self.is_terminated().await {
             return Err(ResponseError::Terminated);
         }
-        if let Some(stream) = self.get_stream().await {
-            let is_ws: bool = self.get_request_upgrade_type().await.is_ws();
-            let response_body: ResponseBody = self.get_response_body().await;
-            return stream.send_body_conditional(&response_body, is_ws).await;
+        if !self.get_request_upgrade_type().await.is_ws() {
+            return Err(ResponseError::Response(
+                "only WebSocket connections can join a room".to_owned(),
+            ));
         }
-        Err(ResponseError::NotFoundStream)
+        let room: String = room.to_string();
+        let member: WebSocketMember = self.ws_member().await;
+        self.get_ws_hub().await.join(&room, &member).await;
+        self.write()
+            .await
+            .get_mut_websocket()
+            .get_mut_rooms()
+            .insert(room);
+        Ok(())
     }
 
-    /// Sends only the response body and then closes the connection.
+    /// Removes the connection from a broadcast room.
     ///
-    /// After sending the body, the connection will be marked as closed.
+    /// # Arguments
+    ///
+    /// - `R` - The room name implementing `ToString`.
     ///
     /// # Returns
     ///
-    /// - `ResponseResult` - The outcome of the send operation.
-    pub async fn send_once_body(&self) -> ResponseResult {
-        let res: ResponseResult = self.send_body().await;
-        self.closed().await;
-        res
+    /// - `&Self` - Reference to the context.
+    pub async fn ws_leave<R: ToString>(&self, room: R) -> &Self {
+        let room: String = room.to_string();
+        let Some(id) = self.get_ws_member_id().await else {
+            return self;
+        };
+        self.write()
+            .await
+            .get_mut_websocket()
+            .get_mut_rooms()
+            .remove(&room);
+        self.get_ws_hub().await.leave(&room, id).await;
+        self
     }
 
-    /// Flushes the underlying network stream, ensuring all buffered data is sent.
+    /// Removes the connection from every broadcast room and stops its queue.
+    pub(crate) async fn ws_leave_all(&self) {
+        let (member, rooms): (OptionWebSocketMember, HashSetString) = {
+            let mut inner: RwLockWriteContextInner = self.write().await;
+            let websocket: &mut WebSocketConnection = inner.get_mut_websocket();
+            (
+                websocket.get_member().clone(),
+                std::mem::take(websocket.get_mut_rooms()),
+            )
+        };
+        let Some(member) = member else {
+            return;
+        };
+        let hub: WebSocketHub = self.get_ws_hub().await;
+        for room in rooms.iter() {
+            hub.leave(room, *member.get_id()).await;
+        }
+        self.write().await.get_mut_websocket().set_member(None);
+    }
+
+    /// Negotiates `permessage-deflate` with the offers of the upgrade request.
     ///
     /// # Returns
     ///
-    /// - `ResponseResult` - The outcome of the flush operation.
-    pub async fn flush(&self) -> ResponseResult {
-        if let Some(stream) = self.get_stream().await {
-            stream.flush().await;
-            return Ok(());
-        }
-        Err(ResponseError::NotFoundStream)
+    /// - `OptionWebSocketDeflateAgreement` - The agreed parameters, or None if compression
+    ///   is disabled on the server or no offer is acceptable.
+    async fn negotiate_ws_deflate(&self) -> OptionWebSocketDeflateAgreement {
+        let config: WebSocketDeflate =
+            (*self.get_server().await?.get_config().await.get_ws_deflate())?;
+        let offers: String = self
+            .get_request_header(SEC_WEBSOCKET_EXTENSIONS)
+            .await?
+            .into_iter()
+            .collect::<Vec<String>>()
+            .join(COMMA);
+        negotiate_ws_deflate(&offers, &config)
+    }
+
+    /// Reads an HTTP request from the underlying stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The read buffer size.
+    ///
+    /// # Returns
+    ///
+    /// - `RequestReaderHandleResult` - The parsed request or error.
+    pub async fn http_from_stream(&self, buffer: usize) -> RequestReaderHandleResult {
+        if self.get_aborted().await {
+            return Err(RequestError::RequestAborted);
+        }
+        let stream: ConnectionStream = self.get_stream().await.ok_or(RequestError::GetTcpStream)?;
+        let max_body_size: usize = match self.get_server().await {
+            Some(server) => *server.get_config().await.get_http_max_body_size(),
+            None => DEFAULT_HTTP_MAX_BODY_SIZE,
+        };
+        let request: Request = stream
+            .read_request(buffer, max_body_size)
+            .await
+            .map_err(RequestReadError::into_request_error)?
+            .into_parts()
+            .0;
+        self.set_request(&request).await;
+        Ok(request)
+    }
+
+    /// Reads the next complete WebSocket message from the underlying stream.
+    ///
+    /// Fragmented messages are reassembled and pings are answered automatically.
+    /// The payload is available through `get_request_body` afterwards, while the
+    /// returned request is the one that opened the connection. A close frame is
+    /// answered and ends the connection with `RequestError::ClientClosedConnection`.
+    ///
+    /// # Breaking change
+    ///
+    /// Earlier versions returned a copy of the request whose body was the payload of a
+    /// single unfragmented frame, rejected continuation frames, ignored pings and did not
+    /// answer close frames. Code reading `get_body` from the returned request must read
+    /// `Context::get_request_body` instead, or use `ws_read_message` to get the payload
+    /// directly.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The read buffer size.
+    ///
+    /// # Returns
+    ///
+    /// - `RequestReaderHandleResult` - The request or error.
+    pub async fn ws_from_stream(&self, buffer: usize) -> RequestReaderHandleResult {
+        self.ws_read_message(buffer).await?;
+        Ok(self.get_request().await)
+    }
+
+    /// Reads the next complete WebSocket message and stores its payload.
+    ///
+    /// Fragmented messages are reassembled and control frames are handled like in
+    /// `ws_from_stream`.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The read buffer size.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultRequestBodyRequestError` - The message payload or error.
+    pub async fn ws_read_message(&self, buffer: usize) -> ResultRequestBodyRequestError {
+        self.ws_read_message_until(buffer, None)
+            .await?
+            .ok_or(RequestError::ClientClosedConnection)
+    }
+
+    /// Reads the next complete WebSocket message, waiting for frames until a deadline.
+    ///
+    /// Only the wait for frame bytes is bounded by the deadline, so answering pings and
+    /// close frames is never interrupted. Bytes and fragments received before the deadline
+    /// are kept for the next call.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The read buffer size.
+    /// - `OptionInstant` - The deadline, or None to wait without limit.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultOptionRequestBodyRequestError` - The message payload, None if the deadline
+    ///   elapsed first, or an error.
+    pub(crate) async fn ws_read_message_until(
+        &self,
+        buffer: usize,
+        deadline: OptionInstant,
+    ) -> ResultOptionRequestBodyRequestError {
+        if self.get_aborted().await {
+            return Err(RequestError::RequestAborted);
+        }
+        if self.get_closed().await {
+            return Err(RequestError::ClientClosedConnection);
+        }
+        let stream: ConnectionStream = self.get_stream().await.ok_or(RequestError::GetTcpStream)?;
+        let reader: ArcMutexWebSocketReader =
+            self.read().await.get_websocket().get_reader().clone();
+        let mut reader: MutexGuard<WebSocketReader> = reader.lock().await;
+        let result: ResultOptionRequestBodyRequestError = self
+            .ws_read_frames(&mut reader, &stream, buffer, deadline)
+            .await;
+        if let Some(close) = reader.take_violation() {
+            drop(reader);
+            self.ws_fail(close).await;
+        }
+        result
+    }
+
+    /// Reads frames until a complete WebSocket message arrives.
+    ///
+    /// Control frames received in between are answered or recorded.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut WebSocketReader` - The locked read side of the connection.
+    /// - `&ConnectionStream` - The stream to read from.
+    /// - `usize` - The read buffer size.
+    /// - `OptionInstant` - The deadline for waiting on the next frame, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultOptionRequestBodyRequestError` - The message payload, None if the deadline
+    ///   elapsed while waiting for a frame, or an error.
+    async fn ws_read_frames(
+        &self,
+        reader: &mut WebSocketReader,
+        stream: &ConnectionStream,
+        buffer: usize,
+        deadline: OptionInstant,
+    ) -> ResultOptionRequestBodyRequestError {
+        loop {
+            let read = reader.read_frame(stream, buffer);
+            let frame: WebSocketFrameData = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, read).await {
+                    Ok(frame) => frame?,
+                    Err(_) => return Ok(None),
+                },
+                None => read.await?,
+            };
+            self.ws_touch().await;
+            match frame.get_opcode() {
+                WebSocketOpcode::Ping => {
+                    let _ = self
+                        .send_ws_frame(true, WebSocketOpcode::Pong, frame.get_payload())
+                        .await;
+                }
+                WebSocketOpcode::Pong => {
+                    self.write().await.get_mut_websocket().set_ping_sent(None);
+                }
+                WebSocketOpcode::Close => {
+                    self.ws_close_received(frame.get_payload()).await;
+                    return Err(RequestError::ClientClosedConnection);
+                }
+                _ => {
+                    if let Some(message) = reader.assemble(frame)? {
+                        self.write().await.set_ws_message(Some(message.clone()));
+                        return Ok(Some(message));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records a close frame received from the peer and answers it if needed.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The payload of the received close frame.
+    async fn ws_close_received(&self, payload: &[u8]) {
+        let received: WebSocketClose = WebSocketClose::from_payload(payload);
+        let reply: bool = {
+            let mut inner: RwLockWriteContextInner = self.write().await;
+            let websocket: &mut WebSocketConnection = inner.get_mut_websocket();
+            *websocket.get_mut_close_received() = true;
+            if websocket.get_close().is_none() {
+                *websocket.get_mut_close() = Some(received.clone());
+            }
+            !std::mem::replace(websocket.get_mut_close_sent(), true)
+        };
+        if reply {
+            let code: &[u8] = payload.get(..WS_CLOSE_CODE_LEN).unwrap_or_default();
+            let _ = self.send_ws_frame(true, WebSocketOpcode::Close, code).await;
+        }
+        self.closed().await;
+    }
+
+    /// Sends a close frame without waiting for the peer's reply.
+    ///
+    /// The connection keeps reading until the peer answers with its own close frame,
+    /// after which the WebSocket loop ends. Use `ws_close` to wait for the reply instead.
+    ///
+    /// # Arguments
+    ///
+    /// - `u16` - The close status code.
+    /// - `R` - The close reason implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation, an error if the code may not
+    ///   be sent on the wire, or `ConnectionClosed` if a close frame was already sent.
+    pub async fn ws_send_close<R: ToString>(&self, code: u16, reason: R) -> ResponseResult {
+        if !is_valid_ws_close_code(code) {
+            return Err(ResponseError::Response(format!(
+                "close code {} may not be sent on the wire",
+                code
+            )));
+        }
+        let close: WebSocketClose = WebSocketClose::new(code, reason);
+        {
+            let mut inner: RwLockWriteContextInner = self.write().await;
+            let websocket: &mut WebSocketConnection = inner.get_mut_websocket();
+            if std::mem::replace(websocket.get_mut_close_sent(), true) {
+                return Err(ResponseError::ConnectionClosed);
+            }
+            if websocket.get_close().is_none() {
+                *websocket.get_mut_close() = Some(close.clone());
+            }
+        }
+        self.send_ws_frame(true, WebSocketOpcode::Close, &close.to_payload())
+            .await
+    }
+
+    /// Closes the WebSocket connection with a status code and reason.
+    ///
+    /// Sends a close frame and waits for the peer's close frame until the timeout
+    /// configured with `Server::ws_close_timeout` elapses. The connection is marked
+    /// as closed afterwards, which ends the WebSocket loop.
+    ///
+    /// # Arguments
+    ///
+    /// - `u16` - The close status code, such as `WS_CLOSE_NORMAL`.
+    /// - `R` - The close reason implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of sending the close frame, or an error if the code
+    ///   may not be sent on the wire.
+    pub async fn ws_close<R: ToString>(&self, code: u16, reason: R) -> ResponseResult {
+        let result: ResponseResult = self.ws_send_close(code, reason).await;
+        if result.is_ok() {
+            let (timeout, buffer): (Duration, usize) = match self.get_server().await {
+                Some(server) => {
+                    let config: ServerConfig = server.get_config().await;
+                    (*config.get_ws_close_timeout(), *config.get_ws_buffer())
+                }
+                None => (DEFAULT_WS_CLOSE_TIMEOUT, DEFAULT_BUFFER_SIZE),
+            };
+            let _ = tokio::time::timeout(timeout, self.ws_await_close(buffer)).await;
+        }
+        self.closed().await;
+        result
+    }
+
+    /// Reads and discards frames until the peer's close frame arrives.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The read buffer size.
+    async fn ws_await_close(&self, buffer: usize) {
+        let Some(stream) = self.get_stream().await else {
+            return;
+        };
+        let reader: ArcMutexWebSocketReader =
+            self.read().await.get_websocket().get_reader().clone();
+        let mut reader: MutexGuard<WebSocketReader> = reader.lock().await;
+        while !*self.read().await.get_websocket().get_close_received() {
+            match reader.read_frame(&stream, buffer).await {
+                Ok(frame) if frame.get_opcode().is_close() => {
+                    self.ws_close_received(frame.get_payload()).await;
+                }
+                Ok(_) => {}
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Records that a frame was just received from the peer.
+    pub(crate) async fn ws_touch(&self) {
+        self.write()
+            .await
+            .get_mut_websocket()
+            .set_last_received(Some(Instant::now()));
+    }
+
+    /// Computes the next moment a heartbeat check is due.
+    ///
+    /// # Arguments
+    ///
+    /// - `&WebSocketHeartbeat` - The heartbeat settings of the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionInstant` - The next deadline, or None if no check is enabled.
+    pub(crate) async fn ws_next_heartbeat(&self, heartbeat: &WebSocketHeartbeat) -> OptionInstant {
+        let inner: RwLockReadContextInner = self.read().await;
+        let websocket: &WebSocketConnection = inner.get_websocket();
+        let last_received: Instant = websocket.get_last_received().unwrap_or_else(Instant::now);
+        heartbeat.next_deadline(last_received, *websocket.get_ping_sent())
+    }
+
+    /// Runs the heartbeat checks that are due.
+    ///
+    /// Closes the connection with `WS_CLOSE_GOING_AWAY` when the idle timeout elapsed
+    /// or the pong deadline was missed, and sends a ping when the ping interval elapsed.
+    /// A missed pong is not followed by waiting for the peer's close frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `&WebSocketHeartbeat` - The heartbeat settings of the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - false if the connection was closed, true otherwise.
+    pub(crate) async fn ws_heartbeat_tick(&self, heartbeat: &WebSocketHeartbeat) -> bool {
+        let now: Instant = Instant::now();
+        let (last_received, ping_sent): (Instant, OptionInstant) = {
+            let inner: RwLockReadContextInner = self.read().await;
+            let websocket: &WebSocketConnection = inner.get_websocket();
+            (
+                websocket.get_last_received().unwrap_or(now),
+                *websocket.get_ping_sent(),
+            )
+        };
+        if let Some(idle_timeout) = heartbeat.get_idle_timeout() {
+            if now >= last_received + *idle_timeout {
+                let _ = self.ws_close(WS_CLOSE_GOING_AWAY, "idle timeout").await;
+                return false;
+            }
+        }
+        if heartbeat
+            .pong_deadline(ping_sent)
+            .is_some_and(|deadline: Instant| now >= deadline)
+        {
+            self.ws_fail(WebSocketClose::new(WS_CLOSE_GOING_AWAY, "pong timeout"))
+                .await;
+            return false;
+        }
+        if heartbeat
+            .ping_due(last_received, ping_sent)
+            .is_some_and(|due: Instant| now >= due)
+        {
+            if self
+                .send_ws_frame(true, WebSocketOpcode::Ping, EMPTY_STR.as_bytes())
+                .await
+                .is_err()
+            {
+                return false;
+            }
+            self.write()
+                .await
+                .get_mut_websocket()
+                .set_ping_sent(Some(now));
+        }
+        true
+    }
+
+    /// Fails the connection after a protocol violation.
+    ///
+    /// The close frame is sent without waiting for the peer's reply.
+    ///
+    /// # Arguments
+    ///
+    /// - `WebSocketClose` - The close code and reason describing the violation.
+    async fn ws_fail(&self, close: WebSocketClose) {
+        let _ = self
+            .ws_send_close(*close.get_code(), close.get_reason())
+            .await;
+        self.closed().await;
+    }
+
+    /// Records an abnormal closure if the connection ended without a close handshake.
+    pub(crate) async fn ws_finish_close(&self) {
+        let mut inner: RwLockWriteContextInner = self.write().await;
+        let websocket: &mut WebSocketConnection = inner.get_mut_websocket();
+        if websocket.get_close().is_none() {
+            *websocket.get_mut_close() = Some(WebSocketClose::new(WS_CLOSE_ABNORMAL, EMPTY_STR));
+        }
+    }
+
+    /// Retrieves the close code and reason of the WebSocket connection.
+    ///
+    /// The value describes the close frame of the side that initiated the closure, or
+    /// `WS_CLOSE_ABNORMAL` if the connection ended without a close handshake.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionWebSocketClose` - The close frame, or None while the connection is open.
+    pub async fn get_ws_close(&self) -> OptionWebSocketClose {
+        self.read().await.get_websocket().get_close().clone()
+    }
+
+    /// Writes a single WebSocket frame to the underlying stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether the frame is the final fragment of a message.
+    /// - `WebSocketOpcode` - The opcode of the frame.
+    /// - `&[u8]` - The payload of the frame.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub(crate) async fn send_ws_frame(
+        &self,
+        fin: bool,
+        opcode: WebSocketOpcode,
+        payload: &[u8],
+    ) -> ResponseResult {
+        if self.is_terminated().await {
+            return Err(ResponseError::Terminated);
+        }
+        let stream: ConnectionStream = self
+            .get_stream()
+            .await
+            .ok_or(ResponseError::NotFoundStream)?;
+        let writer: ArcMutexWebSocketWriter =
+            self.read().await.get_websocket().get_writer().clone();
+        let _writer: MutexGuard<WebSocketWriter> = writer.lock().await;
+        stream
+            .write_all(&encode_ws_frame(fin, 0, opcode, payload))
+            .await
+    }
+
+    /// Sends a WebSocket text message.
+    ///
+    /// # Arguments
+    ///
+    /// - `T` - The text implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn ws_send_text<T: ToString>(&self, text: T) -> ResponseResult {
+        self.send_ws_message(WebSocketOpcode::Text, text.to_string().as_bytes())
+            .await
+    }
+
+    /// Sends a WebSocket binary message.
+    ///
+    /// # Arguments
+    ///
+    /// - `B` - The binary payload.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn ws_send_binary<B: AsRef<[u8]>>(&self, data: B) -> ResponseResult {
+        self.send_ws_message(WebSocketOpcode::Binary, data.as_ref())
+            .await
+    }
+
+    /// Sends a WebSocket ping frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `B` - The ping payload of at most 125 bytes.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn ws_send_ping<B: AsRef<[u8]>>(&self, payload: B) -> ResponseResult {
+        let payload: &[u8] = payload.as_ref();
+        if payload.len() > WS_MAX_CONTROL_PAYLOAD_LEN {
+            return Err(ResponseError::Response(format!(
+                "ping payload of {} bytes exceeds {} bytes",
+                payload.len(),
+                WS_MAX_CONTROL_PAYLOAD_LEN
+            )));
+        }
+        self.send_ws_frame(true, WebSocketOpcode::Ping, payload)
+            .await
+    }
+
+    /// Starts a WebSocket message that is sent in several fragments.
+    ///
+    /// # Arguments
+    ///
+    /// - `WebSocketOpcode` - The opcode of the message.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketMessageWriterResponseError` - The writer of the message.
+    async fn ws_start_message(
+        &self,
+        opcode: WebSocketOpcode,
+    ) -> ResultWebSocketMessageWriterResponseError {
+        if self.is_terminated().await {
+            return Err(ResponseError::Terminated);
+        }
+        let stream: ConnectionStream = self
+            .get_stream()
+            .await
+            .ok_or(ResponseError::NotFoundStream)?;
+        let writer: ArcMutexWebSocketWriter =
+            self.read().await.get_websocket().get_writer().clone();
+        Ok(WebSocketMessageWriter::new(
+            stream,
+            writer.lock_owned().await,
+            opcode,
+        ))
+    }
+
+    /// Starts a text message that is sent as several continuation frames.
+    ///
+    /// The fragments are written with `WebSocketMessageWriter::send` and the message
+    /// ends with `WebSocketMessageWriter::finish`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketMessageWriterResponseError` - The writer of the message.
+    pub async fn ws_start_text(&self) -> ResultWebSocketMessageWriterResponseError {
+        self.ws_start_message(WebSocketOpcode::Text).await
+    }
+
+    /// Starts a binary message that is sent as several continuation frames.
+    ///
+    /// The fragments are written with `WebSocketMessageWriter::send` and the message
+    /// ends with `WebSocketMessageWriter::finish`.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketMessageWriterResponseError` - The writer of the message.
+    pub async fn ws_start_binary(&self) -> ResultWebSocketMessageWriterResponseError {
+        self.ws_start_message(WebSocketOpcode::Binary).await
+    }
+
+    /// Writes a complete WebSocket data message in a single frame.
+    ///
+    /// The payload is compressed when `permessage-deflate` was negotiated.
+    ///
+    /// # Arguments
+    ///
+    /// - `WebSocketOpcode` - The opcode of the message.
+    /// - `&[u8]` - The payload of the message.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub(crate) async fn send_ws_message(
+        &self,
+        opcode: WebSocketOpcode,
+        payload: &[u8],
+    ) -> ResponseResult {
+        if self.is_terminated().await {
+            return Err(ResponseError::Terminated);
+        }
+        let stream: ConnectionStream = self
+            .get_stream()
+            .await
+            .ok_or(ResponseError::NotFoundStream)?;
+        let writer: ArcMutexWebSocketWriter =
+            self.read().await.get_websocket().get_writer().clone();
+        let mut writer: MutexGuard<WebSocketWriter> = writer.lock().await;
+        let frame: Vec<u8> = match writer.get_mut_deflater() {
+            Some(deflater) => {
+                let compressed: Vec<u8> =
+                    deflater.deflate(payload).map_err(ResponseError::Response)?;
+                encode_ws_frame(true, WS_RSV1_BIT, opcode, &compressed)
+            }
+            None => encode_ws_frame(true, 0, opcode, payload),
+        };
+        stream.write_all(&frame).await
+    }
+
+    /// Updates the lifecycle status based on the current context state.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut Lifecycle` - The lifecycle to update.
+    pub(crate) async fn update_lifecycle_status(&self, lifecycle: &mut Lifecycle) {
+        let keep_alive: bool = !self.get_closed().await && lifecycle.is_keep_alive();
+        let aborted: bool = self.get_aborted().await;
+        lifecycle.update_status(aborted, keep_alive);
+    }
+
+    /// Sends the response headers and body to the client.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn send(&self) -> ResponseResult {
+        if self.is_terminated().await {
+            return Err(ResponseError::Terminated);
+        }
+        if let Some(stream) = self.get_stream().await {
+            #[cfg(feature = "http2")]
+            if stream.is_http2().await {
+                let message: ResultHttp2ResponseMessageResponseError =
+                    Http2ResponseMessage::from_response(self.write().await.get_mut_response());
+                return stream.send_http2_response(message?).await;
+            }
+            let response_res: ResponseData = self.write().await.get_mut_response().build();
+            return stream.send(&response_res).await;
+        }
+        Err(ResponseError::NotFoundStream)
+    }
+
+    /// Sends the response and then closes the connection.
+    ///
+    /// After sending, the connection will be marked as closed.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn send_once(&self) -> ResponseResult {
+        let res: ResponseResult = self.send().await;
+        self.closed().await;
+        res
+    }
+
+    /// Sends only the response body to the client.
+    ///
+    /// This is useful for streaming data or for responses where headers have already been sent.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn send_body(&self) -> ResponseResult {
+        if self.is_terminated().await {
+            return Err(ResponseError::Terminated);
+        }
+        if let Some(stream) = self.get_stream().await {
+            let response_body: ResponseBody = self.get_response_body().await;
+            if self.get_request_upgrade_type().await.is_ws() {
+                return self
+                    .send_ws_message(ws_message_opcode(&response_body), &response_body)
+                    .await;
+            }
+            return stream.send_body(&response_body).await;
+        }
+        Err(ResponseError::NotFoundStream)
+    }
+
+    /// Starts a Server-Sent Events stream.
+    ///
+    /// Sends a `200 OK` response with the headers of an event stream and starts
+    /// sending keep-alive comments while the stream is idle. The stream ends and the
+    /// connection is closed when the route handler returns.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn sse_start(&self) -> ResponseResult {
+        if self.get_read_sse_started().await {
+            return Ok(());
+        }
+        self.set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(200)
+            .await
+            .replace_response_header(CONTENT_TYPE, TEXT_EVENT_STREAM)
+            .await
+            .replace_response_header(CACHE_CONTROL, NO_CACHE)
+            .await
+            .replace_response_header(X_ACCEL_BUFFERING, X_ACCEL_BUFFERING_NO)
+            .await
+            .set_response_body(Vec::new())
+            .await
+            .send()
+            .await?;
+        let interval: Duration = match self.get_server().await {
+            Some(server) => *server.get_config().await.get_sse_keep_alive(),
+            None => DEFAULT_SSE_KEEP_ALIVE,
+        };
+        let keep_alive: OptionAbortHandle = if interval.is_zero() {
+            None
+        } else {
+            let ctx: Context = self.clone();
+            Some(tokio::spawn(async move { ctx.sse_keep_alive(interval).await }).abort_handle())
+        };
+        let mut inner: RwLockWriteContextInner = self.write().await;
+        let sse: &mut SseState = inner.get_mut_sse();
+        sse.set_started(true);
+        sse.set_last_sent(Some(Instant::now()));
+        sse.set_keep_alive(keep_alive);
+        Ok(())
+    }
+
+    /// Checks whether a Server-Sent Events stream has been started.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if `sse_start` was called, false otherwise.
+    async fn get_read_sse_started(&self) -> bool {
+        *self.read().await.get_sse().get_started()
+    }
+
+    /// Sends keep-alive comments whenever the stream was idle for a whole interval.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The keep-alive interval.
+    async fn sse_keep_alive(&self, interval: Duration) {
+        let comment: String = SseEvent::new()
+            .with_comment(SSE_KEEP_ALIVE_COMMENT)
+            .encode();
+        loop {
+            let last_sent: Instant = self
+                .read()
+                .await
+                .get_sse()
+                .get_last_sent()
+                .unwrap_or_else(Instant::now);
+            tokio::time::sleep_until(last_sent + interval).await;
+            let idle: bool = self
+                .read()
+                .await
+                .get_sse()
+                .get_last_sent()
+                .is_none_or(|sent: Instant| sent.elapsed() >= interval);
+            if idle && self.sse_write(comment.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Writes encoded event stream data and records the time.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The encoded events or comments.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    async fn sse_write(&self, data: &[u8]) -> ResponseResult {
+        if self.is_terminated().await {
+            return Err(ResponseError::Terminated);
+        }
+        let stream: ConnectionStream = self
+            .get_stream()
+            .await
+            .ok_or(ResponseError::NotFoundStream)?;
+        stream.send_body(&data.to_vec()).await?;
+        self.write()
+            .await
+            .get_mut_sse()
+            .set_last_sent(Some(Instant::now()));
+        Ok(())
+    }
+
+    /// Sends one Server-Sent Events message, starting the stream first if needed.
+    ///
+    /// # Arguments
+    ///
+    /// - `&SseEvent` - The event to send.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn sse_send(&self, event: &SseEvent) -> ResponseResult {
+        if !self.get_read_sse_started().await {
+            self.sse_start().await?;
+        }
+        self.sse_write(event.encode().as_bytes()).await
+    }
+
+    /// Retrieves the id of the last event received by a reconnecting client.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionString` - The value of the `Last-Event-ID` request header, if present.
+    pub async fn get_last_event_id(&self) -> OptionString {
+        self.get_request_header_back(LAST_EVENT_ID).await
+    }
+
+    /// Ends the Server-Sent Events stream once its route handler has returned.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if a stream was running and the connection was closed, false otherwise.
+    pub(crate) async fn sse_finish(&self) -> bool {
+        if !self.get_read_sse_started().await {
+            return false;
+        }
+        let keep_alive: OptionAbortHandle = {
+            let mut inner: RwLockWriteContextInner = self.write().await;
+            let sse: &mut SseState = inner.get_mut_sse();
+            if !std::mem::take(sse.get_mut_started()) {
+                return false;
+            }
+            sse.get_mut_keep_alive().take()
+        };
+        if let Some(keep_alive) = keep_alive {
+            keep_alive.abort();
+        }
+        self.closed().await;
+        true
+    }
+
+    /// Sends only the response body and then closes the connection.
+    ///
+    /// After sending the body, the connection will be marked as closed.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn send_once_body(&self) -> ResponseResult {
+        let res: ResponseResult = self.send_body().await;
+        self.closed().await;
+        res
+    }
+
+    /// Flushes the underlying network stream, ensuring all buffered data is sent.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the flush operation.
+    pub async fn flush(&self) -> ResponseResult {
+        if let Some(stream) = self.get_stream().await {
+            stream.flush().await;
+            return Ok(());
+        }
+        Err(ResponseError::NotFoundStream)
+    }
+}
+
+/// Implementation of methods for `ConnectionInfo` structure.
+impl ConnectionInfo {
+    /// Creates the connection details for a peer address.
+    ///
+    /// # Arguments
+    ///
+    /// - `SocketAddr` - The address of the remote peer.
+    /// - `OptionString` - The tag of the listener that accepted the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `ConnectionInfo` - The connection details without TLS parameters.
+    pub(crate) fn new(peer_addr: SocketAddr, listener_tag: OptionString) -> Self {
+        Self {
+            peer_addr: Some(peer_addr),
+            listener_tag,
+            ..Default::default()
+        }
+    }
+
+    /// Creates the connection details for a peer on a Unix domain socket.
+    ///
+    /// # Arguments
+    ///
+    /// - `UnixPeer` - The peer and its credentials.
+    ///
+    /// # Returns
+    ///
+    /// - `ConnectionInfo` - The connection details without a socket address.
+    #[cfg(unix)]
+    pub(crate) fn from_unix(peer: UnixPeer) -> Self {
+        Self {
+            unix: Some(peer),
+            ..Default::default()
+        }
     }
 }
@@ -20,7 +20,7 @@ pub struct ContextInner {
     #[get(pub(super))]
     #[get_mut(pub(super))]
     #[set(pub(super))]
-    stream: OptionArcRwLockStream,
+    stream: OptionConnectionStream,
     /// The incoming HTTP request.
     #[get(pub(super))]
     #[get_mut(pub(super))]
@@ -41,6 +41,51 @@ pub struct ContextInner {
     #[get_mut(pub(super))]
     #[set(pub(super))]
     attributes: HashMapArcAnySendSync,
+    /// The server that accepted the connection, if any.
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    server: OptionServer,
+    /// The read and write state of the connection after a WebSocket upgrade.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    websocket: WebSocketConnection,
+    /// The payload of the last complete WebSocket message.
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    ws_message: OptionVecU8,
+    /// Details about the underlying connection gathered when it was accepted.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    connection: ConnectionInfo,
+    /// The Server-Sent Events state of the connection.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    sse: SseState,
+}
+
+/// Details about an accepted connection that cannot be read from the stream itself.
+#[derive(Clone, Default, CustomDebug, Data, DisplayDebug)]
+pub(crate) struct ConnectionInfo {
+    /// The address of the remote peer as seen by the listener.
+    pub(super) peer_addr: OptionSocketAddr,
+    /// The tag of the listener that accepted the connection, if any.
+    pub(super) listener_tag: OptionString,
+    /// The negotiated TLS parameters, if the connection is encrypted.
+    pub(super) tls: OptionTlsInfo,
+    /// Whether the request arrived on an HTTP/2 stream.
+    pub(super) http2: bool,
+    /// The PROXY protocol header the connection started with, if any.
+    pub(super) proxy: OptionProxyHeader,
+    /// The peer of a connection accepted on a Unix domain socket.
+    #[cfg(unix)]
+    pub(super) unix: OptionUnixPeer,
 }
 
 /// The main application context, providing thread-safe access to request and response data.
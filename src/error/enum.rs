@@ -5,6 +5,14 @@ use crate::*;
 pub enum ServerError {
     /// An error occurred while trying to bind to a TCP socket.
     TcpBind(String),
+    /// An error occurred while trying to bind to a Unix domain socket.
+    UnixBind(String),
+    /// A pre-opened listener could not be used.
+    AdoptListener(String),
+    /// The listeners could not be handed over to a new process.
+    Upgrade(String),
+    /// A trusted proxy is not a valid IP address or CIDR block.
+    InvalidTrustedProxy(String),
     /// An unknown or unexpected error occurred.
     Unknown(String),
     /// An error occurred while reading an HTTP request.
@@ -22,4 +30,48 @@ pub enum RouteError {
     DuplicatePattern(String),
     /// The provided route pattern is not a valid regular expression.
     InvalidRegexPattern(String),
+    /// A route with the same name has already been defined.
+    DuplicateRouteName(String),
+    /// No route has been registered under the given name.
+    UnknownRouteName(String),
+    /// A parameter required by the route pattern was not supplied.
+    MissingRouteParam(String),
+    /// A supplied parameter does not satisfy the regular expression of its segment.
+    InvalidRouteParam(String),
+    /// The provided host pattern is empty or malformed.
+    InvalidHostPattern(String),
+    /// The provided mount prefix is empty or contains a regex segment.
+    InvalidMountPrefix(String),
+}
+
+/// Represents errors raised by `WebSocketClient`.
+#[cfg(feature = "websocket-client")]
+#[derive(CustomDebug, DisplayDebug, PartialEq, Eq)]
+pub enum WebSocketClientError {
+    /// The TCP connection to the server could not be established.
+    Connect(String),
+    /// The server did not accept the WebSocket upgrade.
+    Handshake(String),
+    /// Reading from or writing to the connection failed.
+    Io(String),
+    /// The server sent a frame that violates the WebSocket protocol.
+    Protocol(String),
+    /// The connection was closed.
+    Closed,
+}
+
+/// Represents errors raised while configuring TLS.
+#[cfg(feature = "tls")]
+#[derive(CustomDebug, DisplayDebug, PartialEq, Eq)]
+pub enum TlsError {
+    /// No certificate was supplied.
+    EmptyCertificateChain,
+    /// The certificate chain or the private key was rejected.
+    InvalidCertificate(String),
+    /// The client CA bundle was rejected.
+    InvalidClientCa(String),
+    /// A certificate or private key file could not be read.
+    Io(String),
+    /// TLS has not been enabled on the server.
+    NotEnabled,
 }
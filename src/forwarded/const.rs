@@ -0,0 +1,23 @@
+/// The separator of the elements of a forwarding header.
+pub(crate) const FORWARDED_ELEMENT_SEPARATOR: char = ',';
+
+/// The separator of the parameters of a `Forwarded` element.
+pub(crate) const FORWARDED_PAIR_SEPARATOR: char = ';';
+
+/// The separator of a `Forwarded` parameter name and its value.
+pub(crate) const FORWARDED_VALUE_SEPARATOR: char = '=';
+
+/// The quote around a `Forwarded` parameter value.
+pub(crate) const FORWARDED_QUOTE: char = '"';
+
+/// The `Forwarded` parameter naming the node that made the request.
+pub(crate) const FORWARDED_FOR: &str = "for";
+
+/// The `Forwarded` parameter naming the protocol of the request.
+pub(crate) const FORWARDED_PROTO: &str = "proto";
+
+/// The `Forwarded` parameter naming the host the request was made to.
+pub(crate) const FORWARDED_HOST: &str = "host";
+
+/// The separator of an address and its prefix length in a CIDR block.
+pub(crate) const CIDR_PREFIX_SEPARATOR: char = '/';
@@ -0,0 +1,11 @@
+use crate::*;
+
+/// Defines the forwarding header a trusted proxy sets.
+#[derive(CustomDebug, Clone, Copy, Default, PartialEq, Eq, DisplayDebug)]
+pub enum ForwardedHeader {
+    /// The `Forwarded` header described in RFC 7239.
+    #[default]
+    Forwarded,
+    /// The `X-Forwarded-For`, `X-Forwarded-Proto` and `X-Forwarded-Host` headers.
+    XForwarded,
+}
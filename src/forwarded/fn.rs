@@ -0,0 +1,168 @@
+use crate::*;
+
+/// Splits a header value at a separator that is not inside a quoted string.
+///
+/// # Arguments
+///
+/// - `&str` - The header value.
+/// - `char` - The separator.
+///
+/// # Returns
+///
+/// - `Vec<&str>` - The trimmed parts, in order.
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts: Vec<&str> = Vec::new();
+    let mut quoted: bool = false;
+    let mut start: usize = 0;
+    for (index, character) in value.char_indices() {
+        if character == FORWARDED_QUOTE {
+            quoted = !quoted;
+        } else if character == separator && !quoted {
+            parts.push(value[start..index].trim());
+            start = index + character.len_utf8();
+        }
+    }
+    parts.push(value[start..].trim());
+    parts
+}
+
+/// Parses the address of a forwarding node, such as `192.0.2.1`, `"[2001:db8::1]:4711"`
+/// or `192.0.2.1:47011`.
+///
+/// # Arguments
+///
+/// - `&str` - The node, possibly quoted and with a port.
+///
+/// # Returns
+///
+/// - `OptionSocketHost` - The address, or None for obfuscated identifiers such as `unknown`.
+pub(crate) fn parse_forwarded_node(node: &str) -> OptionSocketHost {
+    let node: &str = node.trim().trim_matches(FORWARDED_QUOTE);
+    if let Ok(ip) = node.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(socket_addr) = node.parse::<SocketAddr>() {
+        return Some(socket_addr.ip());
+    }
+    node.strip_prefix('[')
+        .and_then(|node: &str| node.strip_suffix(']'))
+        .and_then(|node: &str| node.parse::<IpAddr>().ok())
+}
+
+/// Parses the hops of the `Forwarded` header.
+///
+/// # Arguments
+///
+/// - `&str` - The header values joined by commas.
+///
+/// # Returns
+///
+/// - `VecForwardedHop` - The hops, from the client towards the server.
+pub(crate) fn parse_forwarded(value: &str) -> VecForwardedHop {
+    split_unquoted(value, FORWARDED_ELEMENT_SEPARATOR)
+        .into_iter()
+        .filter(|element: &&str| !element.is_empty())
+        .map(|element: &str| {
+            let mut hop: ForwardedHop = ForwardedHop::default();
+            for pair in split_unquoted(element, FORWARDED_PAIR_SEPARATOR) {
+                let Some((name, value)) = pair.split_once(FORWARDED_VALUE_SEPARATOR) else {
+                    continue;
+                };
+                let value: &str = value.trim().trim_matches(FORWARDED_QUOTE);
+                match name.trim().to_ascii_lowercase().as_str() {
+                    FORWARDED_FOR => hop.ip = parse_forwarded_node(value),
+                    FORWARDED_PROTO => hop.proto = Some(value.to_ascii_lowercase()),
+                    FORWARDED_HOST => hop.host = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+            hop
+        })
+        .collect()
+}
+
+/// Parses the hops of the `X-Forwarded-For`, `X-Forwarded-Proto` and `X-Forwarded-Host` headers.
+///
+/// Proxies that append to `X-Forwarded-Proto` and `X-Forwarded-Host` line their values up
+/// with `X-Forwarded-For` from the right. A single value set by the closest proxy only
+/// applies to the last hop, so these headers must only be read from proxies that
+/// overwrite the values a client sent.
+///
+/// # Arguments
+///
+/// - `&str` - The `X-Forwarded-For` values joined by commas.
+/// - `&str` - The `X-Forwarded-Proto` values joined by commas.
+/// - `&str` - The `X-Forwarded-Host` values joined by commas.
+///
+/// # Returns
+///
+/// - `VecForwardedHop` - The hops, from the client towards the server.
+pub(crate) fn parse_x_forwarded(for_value: &str, proto: &str, host: &str) -> VecForwardedHop {
+    let split = |value: &str| -> Vec<String> {
+        value
+            .split(FORWARDED_ELEMENT_SEPARATOR)
+            .map(str::trim)
+            .filter(|item: &&str| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let nodes: Vec<String> = split(for_value);
+    let protos: Vec<String> = split(proto);
+    let hosts: Vec<String> = split(host);
+    let aligned = |values: &Vec<String>, index: usize| -> OptionString {
+        (index + values.len())
+            .checked_sub(nodes.len())
+            .and_then(|index: usize| values.get(index))
+            .cloned()
+    };
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| ForwardedHop {
+            ip: parse_forwarded_node(node),
+            proto: aligned(&protos, index).map(|proto: String| proto.to_ascii_lowercase()),
+            host: aligned(&hosts, index),
+        })
+        .collect()
+}
+
+/// Walks the hops from the server towards the client until the first untrusted address.
+///
+/// # Arguments
+///
+/// - `ForwardedHop` - The peer of the connection, with the protocol and host it was sent.
+/// - `&[ForwardedHop]` - The hops of the forwarding headers, from the client towards the server.
+/// - `&[IpCidr]` - The trusted proxies.
+///
+/// # Returns
+///
+/// - `ForwardedHop` - The hop of the client, or the peer if it is not a trusted proxy.
+pub(crate) fn resolve_forwarded(
+    peer: ForwardedHop,
+    hops: &[ForwardedHop],
+    trusted_proxies: &[IpCidr],
+) -> ForwardedHop {
+    let is_trusted = |ip: &IpAddr| -> bool {
+        trusted_proxies
+            .iter()
+            .any(|trusted_proxy: &IpCidr| trusted_proxy.contains(ip))
+    };
+    if !peer.ip.is_some_and(|ip: IpAddr| is_trusted(&ip)) {
+        return peer;
+    }
+    let mut client: ForwardedHop = peer;
+    for hop in hops.iter().rev() {
+        let Some(ip) = hop.ip else {
+            break;
+        };
+        client = ForwardedHop {
+            ip: Some(ip),
+            proto: hop.proto.clone().or(client.proto),
+            host: hop.host.clone().or(client.host),
+        };
+        if !is_trusted(&ip) {
+            break;
+        }
+    }
+    client
+}
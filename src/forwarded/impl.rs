@@ -0,0 +1,178 @@
+use crate::*;
+
+/// Implementation of methods for `IpCidr` structure.
+impl IpCidr {
+    /// Creates a block from an address and a prefix length.
+    ///
+    /// The host bits of the address are cleared, so `10.1.2.3/8` becomes `10.0.0.0/8`.
+    ///
+    /// # Arguments
+    ///
+    /// - `IpAddr` - An address of the block.
+    /// - `u8` - The prefix length, at most 32 for IPv4 and 128 for IPv6.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<IpCidr>` - The block, or None if the prefix length is too large.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Option<Self> {
+        let network: IpAddr = match addr {
+            IpAddr::V4(v4) if prefix_len <= 32 => {
+                let mask: u32 = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0);
+                IpAddr::from((u32::from(v4) & mask).to_be_bytes())
+            }
+            IpAddr::V6(v6) if prefix_len <= 128 => {
+                let mask: u128 = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0);
+                IpAddr::from((u128::from(v6) & mask).to_be_bytes())
+            }
+            _ => return None,
+        };
+        Some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// Checks whether an address belongs to the block.
+    ///
+    /// IPv4 addresses mapped into IPv6, such as `::ffff:10.0.0.1`, are matched as IPv4.
+    ///
+    /// # Arguments
+    ///
+    /// - `&IpAddr` - The address to check.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the address belongs to the block, false otherwise.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        Self::new(ip.to_canonical(), self.prefix_len)
+            .is_some_and(|block: IpCidr| block.network == self.network)
+    }
+}
+
+/// Implementation of `FromStr` trait for `IpCidr`.
+impl FromStr for IpCidr {
+    type Err = ServerError;
+
+    /// Parses a block such as `10.0.0.0/8` or a single address such as `::1`.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The block in CIDR notation.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<IpCidr, ServerError>` - The block, or `ServerError::InvalidTrustedProxy`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || ServerError::InvalidTrustedProxy(value.to_string());
+        let (addr, prefix_len) = match value.trim().split_once(CIDR_PREFIX_SEPARATOR) {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (value.trim(), None),
+        };
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let prefix_len: u8 = match prefix_len {
+            Some(prefix_len) => prefix_len.parse().map_err(|_| invalid())?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+        Self::new(addr, prefix_len).ok_or_else(invalid)
+    }
+}
+
+/// Implementation of methods for `ForwardedHop` structure.
+impl ForwardedHop {
+    /// Creates a hop.
+    ///
+    /// # Arguments
+    ///
+    /// - `OptionSocketHost` - The address the hop received the request from.
+    /// - `OptionString` - The protocol the hop received the request over.
+    /// - `OptionString` - The host the request was made to.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHop` - The hop.
+    pub(crate) fn new(ip: OptionSocketHost, proto: OptionString, host: OptionString) -> Self {
+        Self { ip, proto, host }
+    }
+}
+
+/// Implementation of methods for `TrustedProxy` structure.
+impl TrustedProxy {
+    /// Creates a trusted proxy.
+    ///
+    /// # Arguments
+    ///
+    /// - `IpCidr` - The addresses of the proxy.
+    /// - `ForwardedHeaders` - The forwarding headers read from the proxy.
+    ///
+    /// # Returns
+    ///
+    /// - `TrustedProxy` - The trusted proxy.
+    pub(crate) fn new(cidr: IpCidr, headers: ForwardedHeaders) -> Self {
+        Self { cidr, headers }
+    }
+}
+
+/// Implementation of methods for `ForwardedHeaders` structure.
+impl ForwardedHeaders {
+    /// Reads the client address from the `Forwarded` header.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHeaders` - The forwarding headers.
+    pub fn forwarded() -> Self {
+        Self {
+            header: ForwardedHeader::Forwarded,
+            ..Default::default()
+        }
+    }
+
+    /// Reads the client address from the `X-Forwarded-For` header.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHeaders` - The forwarding headers.
+    pub fn x_forwarded() -> Self {
+        Self {
+            header: ForwardedHeader::XForwarded,
+            ..Default::default()
+        }
+    }
+
+    /// Reads the protocol as well, from the `proto` parameter or `X-Forwarded-Proto`.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHeaders` - The modified forwarding headers.
+    pub fn with_proto(mut self) -> Self {
+        self.proto = true;
+        self
+    }
+
+    /// Reads the host as well, from the `host` parameter or `X-Forwarded-Host`.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHeaders` - The modified forwarding headers.
+    pub fn with_host(mut self) -> Self {
+        self.host = true;
+        self
+    }
+
+    /// Removes the values of a hop that are not read.
+    ///
+    /// # Arguments
+    ///
+    /// - `ForwardedHop` - The hop parsed from the forwarding header.
+    ///
+    /// # Returns
+    ///
+    /// - `ForwardedHop` - The hop without the protocol and host unless they are read.
+    pub(crate) fn restrict(&self, hop: ForwardedHop) -> ForwardedHop {
+        ForwardedHop {
+            ip: hop.ip,
+            proto: hop.proto.filter(|_| self.proto),
+            host: hop.host.filter(|_| self.host),
+        }
+    }
+}
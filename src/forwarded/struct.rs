@@ -0,0 +1,51 @@
+use crate::*;
+
+/// A block of IP addresses in CIDR notation, such as `10.0.0.0/8`.
+#[derive(Clone, Copy, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct IpCidr {
+    /// The first address of the block.
+    #[get(pub)]
+    pub(super) network: IpAddr,
+    /// The number of leading bits shared by every address of the block.
+    #[get(pub)]
+    pub(super) prefix_len: u8,
+}
+
+/// The forwarding headers read from a trusted proxy.
+///
+/// Only the client address is read by default. The protocol and host are read as well
+/// once enabled, which must only be done if the proxy overwrites the values a client sent.
+#[derive(Clone, Copy, Default, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct ForwardedHeaders {
+    /// The header the proxy sets.
+    #[get(pub)]
+    pub(super) header: ForwardedHeader,
+    /// Whether the protocol the proxy received the request over is read.
+    #[get(pub)]
+    pub(super) proto: bool,
+    /// Whether the host the request was made to is read.
+    #[get(pub)]
+    pub(super) host: bool,
+}
+
+/// A proxy whose forwarding headers are trusted.
+#[derive(Clone, Copy, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub(crate) struct TrustedProxy {
+    /// The addresses of the proxy.
+    #[get(pub(crate))]
+    pub(super) cidr: IpCidr,
+    /// The forwarding headers read from the proxy.
+    #[get(pub(crate))]
+    pub(super) headers: ForwardedHeaders,
+}
+
+/// A hop of a forwarding header, from the client towards the server.
+#[derive(Clone, Default, CustomDebug, Data, DisplayDebug, PartialEq, Eq)]
+pub(crate) struct ForwardedHop {
+    /// The address the hop received the request from, or None if it is hidden or malformed.
+    pub(super) ip: OptionSocketHost,
+    /// The protocol the hop received the request over, such as `https`.
+    pub(super) proto: OptionString,
+    /// The host the request was made to when it reached the hop.
+    pub(super) host: OptionString,
+}
@@ -0,0 +1,10 @@
+use crate::*;
+
+/// A type alias for a list of CIDR blocks.
+pub type VecIpCidr = Vec<IpCidr>;
+
+/// A type alias for a list of trusted proxies.
+pub(crate) type VecTrustedProxy = Vec<TrustedProxy>;
+
+/// A type alias for the hops of a forwarding header.
+pub(crate) type VecForwardedHop = Vec<ForwardedHop>;
@@ -0,0 +1,86 @@
+use crate::*;
+
+/// The connection preface every HTTP/2 client sends first.
+pub(crate) const HTTP2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// The ALPN protocol identifier of HTTP/2 over TLS.
+#[cfg(feature = "tls")]
+pub(crate) const ALPN_H2: &[u8] = b"h2";
+
+/// The ALPN protocol identifier of HTTP/2 over TLS, as text.
+pub(crate) const ALPN_H2_STR: &str = "h2";
+
+/// The header carrying the client settings of an `Upgrade: h2c` request.
+pub(crate) const HTTP2_SETTINGS: &str = "http2-settings";
+
+/// The response that accepts an `Upgrade: h2c` request.
+pub(crate) const H2C_SWITCHING_PROTOCOLS: &[u8] =
+    b"HTTP/1.1 101 Switching Protocols\r\nconnection: Upgrade\r\nupgrade: h2c\r\n\r\n";
+
+/// The default limit of concurrently open streams per connection.
+pub(crate) const DEFAULT_HTTP2_MAX_CONCURRENT_STREAMS: u32 = 100;
+
+/// The default flow-control window of a stream, as defined by RFC 9113.
+pub(crate) const DEFAULT_HTTP2_WINDOW_SIZE: u32 = 65_535;
+
+/// The length of an HTTP/2 frame header.
+pub(crate) const HTTP2_FRAME_HEADER_LEN: usize = 9;
+
+/// The largest frame payload a peer has to accept before it raises its limit.
+pub(crate) const HTTP2_MIN_MAX_FRAME_SIZE: usize = 16_384;
+
+/// The type of a `HEADERS` frame.
+pub(crate) const HTTP2_FRAME_HEADERS: u8 = 0x1;
+
+/// The type of a `SETTINGS` frame.
+pub(crate) const HTTP2_FRAME_SETTINGS: u8 = 0x4;
+
+/// The flags of a `HEADERS` frame that carries a whole request without a body.
+pub(crate) const HTTP2_FLAGS_END_STREAM_END_HEADERS: u8 = 0x5;
+
+/// The stream that answers an upgraded HTTP/1.1 request.
+pub(crate) const HTTP2_UPGRADE_STREAM_ID: u32 = 1;
+
+/// The HPACK representation of a literal header field without indexing and with a new name.
+pub(crate) const HPACK_LITERAL_NEW_NAME: u8 = 0x00;
+
+/// The number of prefix bits of an HPACK string length.
+pub(crate) const HPACK_STRING_PREFIX_BITS: u8 = 7;
+
+/// The pseudo-header of the request method.
+pub(crate) const HTTP2_METHOD: &str = ":method";
+
+/// The pseudo-header of the request scheme.
+pub(crate) const HTTP2_SCHEME: &str = ":scheme";
+
+/// The pseudo-header of the request target.
+pub(crate) const HTTP2_PATH: &str = ":path";
+
+/// The pseudo-header of the request authority.
+pub(crate) const HTTP2_AUTHORITY: &str = ":authority";
+
+/// The scheme of cleartext requests.
+pub(crate) const HTTP_SCHEME: &str = "http";
+
+/// The headers that only apply to a single HTTP/1.1 connection and are not forwarded.
+pub(crate) const HTTP2_CONNECTION_HEADERS: [&str; 7] = [
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "upgrade",
+    "te",
+    HTTP2_SETTINGS,
+];
+
+/// The separator of cookie pairs that HTTP/2 clients may send as separate fields.
+pub(crate) const HTTP2_COOKIE_SEPARATOR: &str = "; ";
+
+/// How long a client may take to send the whole connection preface.
+pub(crate) const HTTP2_PREFACE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The error message of a response head sent twice on the same stream.
+pub(crate) const HTTP2_RESPONSE_HEAD_SENT: &str = "the response head was already sent";
+
+/// The error message of body data sent before the response head.
+pub(crate) const HTTP2_RESPONSE_HEAD_MISSING: &str = "the response head has to be sent first";
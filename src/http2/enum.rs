@@ -0,0 +1,11 @@
+use crate::*;
+
+/// The progress of the response on an HTTP/2 stream.
+pub(crate) enum Http2ResponseState {
+    /// No response head was sent yet.
+    Pending(Http2SendResponse),
+    /// The head was sent and more body data may follow.
+    Streaming(Http2SendStream),
+    /// The stream was ended or reset.
+    Finished,
+}
@@ -0,0 +1,279 @@
+use crate::*;
+
+/// Encodes an integer with an HPACK prefix as described in RFC 7541.
+///
+/// # Arguments
+///
+/// - `&mut Vec<u8>` - The header block to append to.
+/// - `usize` - The integer.
+/// - `u8` - The number of prefix bits.
+/// - `u8` - The bits of the first byte above the prefix.
+fn hpack_encode_integer(block: &mut Vec<u8>, value: usize, prefix_bits: u8, first_byte: u8) {
+    let max_prefix: usize = (1 << prefix_bits) - 1;
+    if value < max_prefix {
+        block.push(first_byte | value as u8);
+        return;
+    }
+    block.push(first_byte | max_prefix as u8);
+    let mut rest: usize = value - max_prefix;
+    while rest >= 0x80 {
+        block.push((rest & 0x7F) as u8 | 0x80);
+        rest >>= 7;
+    }
+    block.push(rest as u8);
+}
+
+/// Encodes a header field as an HPACK literal without indexing and without Huffman coding.
+///
+/// # Arguments
+///
+/// - `&mut Vec<u8>` - The header block to append to.
+/// - `&str` - The lowercase field name.
+/// - `&str` - The field value.
+fn hpack_encode_field(block: &mut Vec<u8>, name: &str, value: &str) {
+    block.push(HPACK_LITERAL_NEW_NAME);
+    for part in [name, value] {
+        hpack_encode_integer(block, part.len(), HPACK_STRING_PREFIX_BITS, 0);
+        block.extend_from_slice(part.as_bytes());
+    }
+}
+
+/// Encodes an upgraded HTTP/1.1 request as the `HEADERS` frame that opens stream 1.
+///
+/// RFC 9113 treats the request that carried `Upgrade: h2c` as the first stream of the
+/// new connection. The frame is placed right after the client settings so the HTTP/2
+/// server sees the request as if the client had sent it.
+///
+/// # Arguments
+///
+/// - `&Request` - The upgraded request, which must not have a body.
+/// - `&str` - The request target exactly as sent by the client.
+///
+/// # Returns
+///
+/// - `OptionVecU8` - The frame, or None if the headers do not fit into a single frame.
+pub(crate) fn encode_h2c_upgrade_frame(request: &Request, target: &str) -> OptionVecU8 {
+    let mut block: Vec<u8> = Vec::new();
+    hpack_encode_field(&mut block, HTTP2_METHOD, &request.get_method().to_string());
+    hpack_encode_field(&mut block, HTTP2_SCHEME, HTTP_SCHEME);
+    hpack_encode_field(&mut block, HTTP2_PATH, target);
+    hpack_encode_field(&mut block, HTTP2_AUTHORITY, request.get_host());
+    for (name, values) in request.get_headers().iter() {
+        if name == HOST
+            || name == CONTENT_LENGTH
+            || HTTP2_CONNECTION_HEADERS.contains(&name.as_str())
+        {
+            continue;
+        }
+        for value in values.iter() {
+            hpack_encode_field(&mut block, name, value);
+        }
+    }
+    if block.len() > HTTP2_MIN_MAX_FRAME_SIZE {
+        return None;
+    }
+    let mut frame: Vec<u8> = Vec::with_capacity(HTTP2_FRAME_HEADER_LEN + block.len());
+    frame.extend_from_slice(&(block.len() as u32).to_be_bytes()[1..]);
+    frame.push(HTTP2_FRAME_HEADERS);
+    frame.push(HTTP2_FLAGS_END_STREAM_END_HEADERS);
+    frame.extend_from_slice(&HTTP2_UPGRADE_STREAM_ID.to_be_bytes());
+    frame.extend_from_slice(&block);
+    Some(frame)
+}
+
+/// Reads the connection preface and the first `SETTINGS` frame of an upgraded client.
+///
+/// # Arguments
+///
+/// - `&mut R` - The connection after the `101 Switching Protocols` response.
+///
+/// # Returns
+///
+/// - `io::Result<Vec<u8>>` - The preface followed by the settings frame.
+pub(crate) async fn read_http2_client_preface<R>(stream: &mut R) -> io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut preface: Vec<u8> = vec![0; HTTP2_PREFACE.len() + HTTP2_FRAME_HEADER_LEN];
+    stream.read_exact(&mut preface).await?;
+    let (magic, header) = preface.split_at(HTTP2_PREFACE.len());
+    if magic != HTTP2_PREFACE || header[3] != HTTP2_FRAME_SETTINGS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing HTTP/2 client preface",
+        ));
+    }
+    let length: usize = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+    if length > HTTP2_MIN_MAX_FRAME_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "oversized HTTP/2 settings frame",
+        ));
+    }
+    let start: usize = preface.len();
+    preface.resize(start + length, 0);
+    stream.read_exact(&mut preface[start..]).await?;
+    Ok(preface)
+}
+
+/// Builds the request of an HTTP/2 stream.
+///
+/// The request keeps the HTTP/2 version, so it reports the protocol it arrived with.
+/// Cookie fields are joined into one header, trailer fields follow the header fields
+/// and the `Content-Length` is set to the length of the received body.
+///
+/// # Arguments
+///
+/// - `&Http2RequestParts` - The method, target and headers of the HTTP/2 request.
+/// - `&OptionHttp2HeaderMap` - The trailer fields sent after the body, if any.
+/// - `Vec<u8>` - The request body.
+///
+/// # Returns
+///
+/// - `Request` - The request.
+pub(crate) fn http2_request(
+    parts: &Http2RequestParts,
+    trailers: &OptionHttp2HeaderMap,
+    body: Vec<u8>,
+) -> Request {
+    let target: &str = parts
+        .uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or(DEFAULT_HTTP_PATH);
+    let (path, query) = target.split_once(QUERY).unwrap_or((target, ""));
+    let host: &str = parts
+        .uri
+        .authority()
+        .map(|authority| authority.as_str())
+        .or_else(|| {
+            parts
+                .headers
+                .get(HOST)
+                .and_then(|value| value.to_str().ok())
+        })
+        .unwrap_or_default();
+    let mut headers: RequestHeaders = hash_map_xx_hash3_64();
+    let mut cookies: VecStrRef = Vec::new();
+    for (name, value) in parts.headers.iter().chain(trailers.iter().flatten()) {
+        let name: &str = name.as_str();
+        if name == HOST || name == CONTENT_LENGTH || HTTP2_CONNECTION_HEADERS.contains(&name) {
+            continue;
+        }
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        if name == COOKIE {
+            cookies.push(value);
+            continue;
+        }
+        headers
+            .entry(name.to_owned())
+            .or_default()
+            .push_back(value.trim().to_owned());
+    }
+    if !cookies.is_empty() {
+        headers.insert(
+            COOKIE.to_owned(),
+            RequestHeadersValue::from([cookies.join(HTTP2_COOKIE_SEPARATOR)]),
+        );
+    }
+    headers.insert(
+        HOST.to_owned(),
+        RequestHeadersValue::from([host.to_owned()]),
+    );
+    headers.insert(
+        CONTENT_LENGTH.to_owned(),
+        RequestHeadersValue::from([body.len().to_string()]),
+    );
+    let mut request: Request = Request::new();
+    request
+        .set_method(parts.method.as_str().parse().unwrap_or_default())
+        .set_host(host.to_owned())
+        .set_version(HttpVersion::HTTP2)
+        .set_path(path.to_owned())
+        .set_querys(Request::parse_querys(query))
+        .set_headers(headers)
+        .set_body(body);
+    request
+}
+
+/// Reads the body of an HTTP/2 request, releasing flow-control capacity as data arrives.
+///
+/// # Arguments
+///
+/// - `&Http2RequestParts` - The method, target and headers of the request.
+/// - `&mut RecvStream` - The body of the request.
+/// - `usize` - The size limit of the body.
+///
+/// # Returns
+///
+/// - `ResultVecU8RequestReadError` - The body, or the reason reading it failed.
+pub(crate) async fn read_http2_body(
+    parts: &Http2RequestParts,
+    body: &mut RecvStream,
+    max_body_size: usize,
+) -> ResultVecU8RequestReadError {
+    let announced: usize = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default();
+    if announced > max_body_size {
+        return Err(RequestReadError::Reject(HTTP_BODY_TOO_LARGE_STATUS_CODE));
+    }
+    let mut data: Vec<u8> = Vec::with_capacity(announced);
+    while let Some(chunk) = body.data().await {
+        let chunk: Bytes =
+            chunk.map_err(|err| RequestReadError::Read(RequestError::Request(err.to_string())))?;
+        let _ = body.flow_control().release_capacity(chunk.len());
+        if data.len() + chunk.len() > max_body_size {
+            return Err(RequestReadError::Reject(HTTP_BODY_TOO_LARGE_STATUS_CODE));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+/// Converts an error of an HTTP/2 stream into the error type of the response senders.
+///
+/// # Arguments
+///
+/// - `H2Error` - The error that ended the stream.
+///
+/// # Returns
+///
+/// - `ResponseError` - `ConnectionClosed` if the peer went away, the error message otherwise.
+pub(crate) fn http2_response_error(error: H2Error) -> ResponseError {
+    if error.is_io() || error.is_reset() || error.is_go_away() {
+        return ResponseError::ConnectionClosed;
+    }
+    ResponseError::Response(error.to_string())
+}
+
+/// Sends body bytes on an HTTP/2 stream, waiting for flow-control capacity as needed.
+///
+/// # Arguments
+///
+/// - `&mut Http2SendStream` - The stream to send on.
+/// - `Bytes` - The data to send.
+///
+/// # Returns
+///
+/// - `ResultH2Error` - Ok once all data is queued, or the error that ended the stream.
+pub(crate) async fn send_http2_data(send: &mut Http2SendStream, mut data: Bytes) -> ResultH2Error {
+    while !data.is_empty() {
+        send.reserve_capacity(data.len());
+        let capacity: usize = match poll_fn(|cx| send.poll_capacity(cx)).await {
+            Some(capacity) => capacity?,
+            None => return Err(H2Error::from(H2Reason::CANCEL)),
+        };
+        if capacity == 0 {
+            continue;
+        }
+        let chunk: Bytes = data.split_to(capacity.min(data.len()));
+        send.send_data(chunk, false)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,265 @@
+use crate::*;
+
+/// Implementation of `Default` trait for `Http2Settings`.
+impl Default for Http2Settings {
+    /// Creates the default HTTP/2 settings.
+    ///
+    /// # Returns
+    ///
+    /// - `Http2Settings` - 100 concurrent streams and the flow-control windows of RFC 9113.
+    fn default() -> Self {
+        Self {
+            max_concurrent_streams: DEFAULT_HTTP2_MAX_CONCURRENT_STREAMS,
+            initial_window_size: DEFAULT_HTTP2_WINDOW_SIZE,
+            initial_connection_window_size: DEFAULT_HTTP2_WINDOW_SIZE,
+        }
+    }
+}
+
+/// Implementation of methods for `Http2Settings` structure.
+impl Http2Settings {
+    /// Creates the default HTTP/2 settings.
+    ///
+    /// # Returns
+    ///
+    /// - `Http2Settings` - The default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of streams a client may keep open at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// - `u32` - The stream limit.
+    ///
+    /// # Returns
+    ///
+    /// - `Http2Settings` - The updated settings.
+    pub fn with_max_concurrent_streams(mut self, max_concurrent_streams: u32) -> Self {
+        self.max_concurrent_streams = max_concurrent_streams;
+        self
+    }
+
+    /// Sets the flow-control window of each stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `u32` - The window size in bytes.
+    ///
+    /// # Returns
+    ///
+    /// - `Http2Settings` - The updated settings.
+    pub fn with_initial_window_size(mut self, initial_window_size: u32) -> Self {
+        self.initial_window_size = initial_window_size;
+        self
+    }
+
+    /// Sets the flow-control window of the whole connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `u32` - The window size in bytes.
+    ///
+    /// # Returns
+    ///
+    /// - `Http2Settings` - The updated settings.
+    pub fn with_initial_connection_window_size(
+        mut self,
+        initial_connection_window_size: u32,
+    ) -> Self {
+        self.initial_connection_window_size = initial_connection_window_size;
+        self
+    }
+
+    /// Creates the connection builder that applies these settings.
+    ///
+    /// # Returns
+    ///
+    /// - `H2ServerBuilder` - The configured builder.
+    pub(crate) fn builder(&self) -> H2ServerBuilder {
+        let mut builder: H2ServerBuilder = H2ServerBuilder::new();
+        builder
+            .max_concurrent_streams(self.max_concurrent_streams)
+            .initial_window_size(self.initial_window_size)
+            .initial_connection_window_size(self.initial_connection_window_size);
+        builder
+    }
+}
+
+/// Implementation of methods for `Http2ResponseMessage` structure.
+impl Http2ResponseMessage {
+    /// Converts a response for an HTTP/2 stream.
+    ///
+    /// The response is prepared the same way `Response::build` prepares it for HTTP/1.1:
+    /// the header fields are taken out of the response, a missing `Content-Type` defaults
+    /// to HTML, the body is compressed as `Content-Encoding` asks and its length is
+    /// announced unless the response is an event stream. Connection-specific header
+    /// fields are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut Response` - The response to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultHttp2ResponseMessageResponseError` - The converted response, or an error if
+    ///   the status code is invalid.
+    pub(crate) fn from_response(
+        response: &mut Response,
+    ) -> ResultHttp2ResponseMessageResponseError {
+        let status_code: Http2StatusCode = u16::try_from(*response.get_status_code())
+            .ok()
+            .and_then(|status_code: u16| Http2StatusCode::from_u16(status_code).ok())
+            .ok_or_else(|| {
+                ResponseError::Response(format!(
+                    "invalid status code {}",
+                    response.get_status_code()
+                ))
+            })?;
+        let mut head: Http2Response = Http2Response::new(());
+        *head.status_mut() = status_code;
+        let headers: ResponseHeaders = response.get_mut_headers().drain().collect();
+        let mut compress: OptionCompress = None;
+        let mut has_content_type: bool = false;
+        let mut open: bool = false;
+        for (name, values) in headers.iter() {
+            let name: String = name.to_ascii_lowercase();
+            if name == CONTENT_LENGTH || HTTP2_CONNECTION_HEADERS.contains(&name.as_str()) {
+                continue;
+            }
+            let Ok(header_name) = Http2HeaderName::from_bytes(name.as_bytes()) else {
+                continue;
+            };
+            for value in values.iter() {
+                if name == CONTENT_ENCODING {
+                    compress = Some(value.parse::<Compress>().unwrap_or_default());
+                } else if name == CONTENT_TYPE {
+                    has_content_type = true;
+                    open |= value.eq_ignore_ascii_case(TEXT_EVENT_STREAM);
+                }
+                if let Ok(value) = Http2HeaderValue::from_str(value) {
+                    head.headers_mut().append(header_name.clone(), value);
+                }
+            }
+        }
+        if !has_content_type {
+            let content_type: String = format!("{}{}{}", TEXT_HTML, SEMICOLON_SPACE, CHARSET_UTF_8);
+            if let Ok(value) = Http2HeaderValue::from_str(&content_type) {
+                head.headers_mut().insert(CONTENT_TYPE, value);
+            }
+        }
+        let mut body: Vec<u8> = response.get_body().clone();
+        if !open {
+            if let Some(compress) = compress.filter(|compress: &Compress| !compress.is_unknown()) {
+                body = compress.encode(&body, DEFAULT_BUFFER_SIZE).into_owned();
+            }
+            head.headers_mut()
+                .insert(CONTENT_LENGTH, Http2HeaderValue::from(body.len()));
+        }
+        Ok(Self { head, body, open })
+    }
+}
+
+/// Implementation of methods for `Http2ResponseWriter` structure.
+impl Http2ResponseWriter {
+    /// Creates the response side of a stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `Http2SendResponse` - The handle that answers the request.
+    /// - `bool` - Whether the stream answers a `HEAD` request.
+    ///
+    /// # Returns
+    ///
+    /// - `Http2ResponseWriter` - The response side, waiting for the response head.
+    pub(crate) fn new(respond: Http2SendResponse, head_request: bool) -> Self {
+        Self {
+            state: Http2ResponseState::Pending(respond),
+            head_request,
+        }
+    }
+
+    /// Sends the response head and body.
+    ///
+    /// The stream is ended afterwards, unless the response is an event stream whose
+    /// events are sent as more body data.
+    ///
+    /// # Arguments
+    ///
+    /// - `Http2ResponseMessage` - The converted response.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub(crate) async fn send_response(&mut self, message: Http2ResponseMessage) -> ResponseResult {
+        let mut respond: Http2SendResponse =
+            match std::mem::replace(&mut self.state, Http2ResponseState::Finished) {
+                Http2ResponseState::Pending(respond) => respond,
+                state => {
+                    self.state = state;
+                    return Err(ResponseError::Response(HTTP2_RESPONSE_HEAD_SENT.to_owned()));
+                }
+            };
+        let Http2ResponseMessage { head, body, open } = message;
+        let bodyless: bool = self.head_request
+            || matches!(
+                head.status(),
+                Http2StatusCode::NO_CONTENT | Http2StatusCode::NOT_MODIFIED
+            );
+        let end: bool = bodyless || (!open && body.is_empty());
+        let mut send: Http2SendStream = respond
+            .send_response(head, end)
+            .map_err(http2_response_error)?;
+        if end {
+            return Ok(());
+        }
+        send_http2_data(&mut send, Bytes::from(body))
+            .await
+            .map_err(http2_response_error)?;
+        if open {
+            self.state = Http2ResponseState::Streaming(send);
+            return Ok(());
+        }
+        send.send_data(Bytes::new(), true)
+            .map_err(http2_response_error)
+    }
+
+    /// Sends more body data after the response head.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The body data.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub(crate) async fn send_data(&mut self, data: &[u8]) -> ResponseResult {
+        match &mut self.state {
+            Http2ResponseState::Streaming(send) => {
+                send_http2_data(send, Bytes::copy_from_slice(data))
+                    .await
+                    .map_err(http2_response_error)
+            }
+            Http2ResponseState::Pending(_) => Err(ResponseError::Response(
+                HTTP2_RESPONSE_HEAD_MISSING.to_owned(),
+            )),
+            Http2ResponseState::Finished => Err(ResponseError::ConnectionClosed),
+        }
+    }
+
+    /// Ends the stream once the request was handled.
+    ///
+    /// A streamed body is ended, and a stream that was never answered is reset.
+    pub(crate) fn finish(&mut self) {
+        match std::mem::replace(&mut self.state, Http2ResponseState::Finished) {
+            Http2ResponseState::Pending(mut respond) => {
+                respond.send_reset(H2Reason::INTERNAL_ERROR);
+            }
+            Http2ResponseState::Streaming(mut send) => {
+                let _ = send.send_data(Bytes::new(), true);
+            }
+            Http2ResponseState::Finished => {}
+        }
+    }
+}
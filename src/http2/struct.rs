@@ -0,0 +1,34 @@
+use crate::*;
+
+/// The settings of HTTP/2 connections.
+#[derive(Clone, Copy, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct Http2Settings {
+    /// The number of streams a client may keep open at the same time.
+    #[get(pub)]
+    pub(super) max_concurrent_streams: u32,
+    /// The flow-control window of each stream, in bytes.
+    #[get(pub)]
+    pub(super) initial_window_size: u32,
+    /// The flow-control window of the whole connection, in bytes.
+    #[get(pub)]
+    pub(super) initial_connection_window_size: u32,
+}
+
+/// A response converted for an HTTP/2 stream.
+#[derive(CustomDebug)]
+pub(crate) struct Http2ResponseMessage {
+    /// The status code and header fields.
+    pub(super) head: Http2Response,
+    /// The body, already compressed if the response asks for it.
+    pub(super) body: Vec<u8>,
+    /// Whether the stream stays open for body data sent afterwards, as for an event stream.
+    pub(super) open: bool,
+}
+
+/// The response side of an HTTP/2 stream.
+pub(crate) struct Http2ResponseWriter {
+    /// The progress of the response.
+    pub(super) state: Http2ResponseState,
+    /// Whether the stream answers a `HEAD` request, whose response never carries a body.
+    pub(super) head_request: bool,
+}
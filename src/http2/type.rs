@@ -0,0 +1,32 @@
+use crate::*;
+
+/// A type alias for optional HTTP/2 settings.
+pub type OptionHttp2Settings = Option<Http2Settings>;
+
+/// A type alias for an HTTP/2 request with a streamed body.
+pub(crate) type Http2Request = HttpRequest<RecvStream>;
+
+/// A type alias for the sending half of an HTTP/2 response body.
+pub(crate) type Http2SendStream = SendStream<Bytes>;
+
+/// A type alias for the handle that answers an HTTP/2 request.
+pub(crate) type Http2SendResponse = SendResponse<Bytes>;
+
+/// A type alias for a `Result` that returns nothing or an `h2::Error`.
+pub(crate) type ResultH2Error = Result<(), H2Error>;
+
+/// A type alias for a response head sent on an HTTP/2 stream.
+pub(crate) type Http2Response = HttpResponse<()>;
+
+/// A type alias for the optional trailer fields of an HTTP/2 request.
+pub(crate) type OptionHttp2HeaderMap = Option<Http2HeaderMap>;
+
+/// A type alias for the optional response side of an HTTP/2 stream.
+pub(crate) type OptionHttp2ResponseWriter = Option<Http2ResponseWriter>;
+
+/// A type alias for a `Result` that returns a converted response or a `ResponseError`.
+pub(crate) type ResultHttp2ResponseMessageResponseError =
+    Result<Http2ResponseMessage, ResponseError>;
+
+/// A type alias for a `Result` that returns a request body or a `RequestReadError`.
+pub(crate) type ResultVecU8RequestReadError = Result<Vec<u8>, RequestReadError>;
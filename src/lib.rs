@@ -11,50 +11,172 @@
 //! seamless networking without platform-specific dependencies.
 
 mod attribute;
+#[cfg(feature = "websocket-client")]
+mod client;
 mod config;
 mod context;
 mod error;
+mod forwarded;
 mod hook;
+#[cfg(feature = "http2")]
+mod http2;
 mod lifecycle;
 mod panic;
+mod proxy;
 mod route;
 mod server;
+mod sse;
+mod stream;
 mod tests;
+mod tls;
+#[cfg(unix)]
+mod unix;
+mod websocket;
 
 pub use attribute::*;
+#[cfg(feature = "websocket-client")]
+pub use client::*;
 pub use context::*;
 pub use error::*;
+pub use forwarded::*;
 pub use hook::*;
+#[cfg(feature = "http2")]
+pub use http2::*;
 pub use panic::*;
+pub use proxy::*;
 pub use route::*;
 pub use server::*;
+pub use sse::*;
+pub use stream::*;
+pub use tls::*;
+#[cfg(unix)]
+pub use unix::*;
+pub use websocket::*;
 
 pub use http_type::*;
 
+#[cfg(feature = "tls")]
+pub use tokio_rustls::rustls::{
+    self,
+    pki_types::{CertificateDer, PrivateKeyDer},
+};
+
 pub(crate) use config::*;
 pub(crate) use lifecycle::*;
 
 pub(crate) use std::{
     any::Any,
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     future::Future,
     io::{self, Write},
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr, TcpListener as StdTcpListener},
     panic::Location,
     panic::{PanicHookInfo, set_hook},
     pin::Pin,
-    sync::Arc,
+    str::{FromStr, Split},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
     time::Duration,
 };
 
+pub(crate) use flate2::{
+    Compress as DeflateCompress, Compression as DeflateLevel, Decompress as DeflateDecompress,
+    FlushCompress, FlushDecompress,
+};
 pub(crate) use lombok_macros::*;
 pub(crate) use regex::Regex;
 pub(crate) use serde::de::DeserializeOwned;
 pub(crate) use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf, split},
     net::{TcpListener, TcpStream},
-    sync::{RwLockReadGuard, RwLockWriteGuard},
-    task::JoinError,
+    sync::{
+        MutexGuard, OwnedMutexGuard, RwLockReadGuard, RwLockWriteGuard,
+        mpsc::{self, Receiver, Sender},
+    },
+    task::{AbortHandle, JoinError, JoinSet},
+    time::Instant,
 };
 
 #[cfg(test)]
 pub(crate) use tokio::task::JoinHandle;
+
+#[cfg(feature = "websocket-client")]
+pub(crate) use std::hash::{BuildHasher, RandomState};
+
+#[cfg(any(unix, feature = "tls"))]
+pub(crate) use std::path::{Path, PathBuf};
+
+#[cfg(feature = "tls")]
+pub(crate) use std::{
+    sync::{
+        PoisonError, RwLock as StdRwLock, RwLockReadGuard as StdRwLockReadGuard,
+        RwLockWriteGuard as StdRwLockWriteGuard,
+    },
+    time::SystemTime,
+};
+#[cfg(feature = "tls")]
+pub(crate) use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        RootCertStore, ServerConfig as TlsServerConfig, ServerConnection,
+        crypto::{CryptoProvider, ring::default_provider as ring_default_provider},
+        pki_types::pem::PemObject,
+        server::{
+            ClientHello, ResolvesServerCert, WebPkiClientVerifier, danger::ClientCertVerifier,
+        },
+        sign::CertifiedKey,
+    },
+    server::TlsStream,
+};
+#[cfg(feature = "tls")]
+pub(crate) use x509_parser::{
+    certificate::X509Certificate, extensions::GeneralName, prelude::FromDer,
+};
+
+#[cfg(feature = "http2")]
+pub(crate) use bytes::Bytes;
+#[cfg(feature = "http2")]
+pub(crate) use h2::{
+    Error as H2Error, Reason as H2Reason, RecvStream, SendStream,
+    server::{Builder as H2ServerBuilder, SendResponse},
+};
+#[cfg(feature = "http2")]
+pub(crate) use http::{
+    HeaderMap as Http2HeaderMap, HeaderName as Http2HeaderName, HeaderValue as Http2HeaderValue,
+    Method as Http2Method, Request as HttpRequest, Response as HttpResponse,
+    StatusCode as Http2StatusCode, request::Parts as Http2RequestParts,
+};
+#[cfg(feature = "http2")]
+pub(crate) use std::future::poll_fn;
+
+#[cfg(any(feature = "http2", feature = "tls"))]
+pub(crate) use std::io::Cursor;
+#[cfg(any(feature = "http2", feature = "tls"))]
+pub(crate) use tokio::io::join as join_io;
+
+#[cfg(unix)]
+pub(crate) use std::{
+    fs::{File as StdFile, Metadata, Permissions},
+    os::{
+        fd::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+        unix::{
+            fs::{FileTypeExt, MetadataExt, PermissionsExt},
+            net::UnixListener as StdUnixListener,
+            process::CommandExt,
+        },
+    },
+    process::{Child, Command},
+    sync::{Mutex as StdMutex, OnceLock, atomic::AtomicUsize},
+};
+#[cfg(unix)]
+pub(crate) use tokio::{
+    net::{
+        UnixListener, UnixStream,
+        unix::{UCred, pipe::pipe as unix_pipe},
+    },
+    signal::unix::{Signal, SignalKind, signal as unix_signal},
+    sync::{Notify, futures::Notified},
+};
@@ -0,0 +1,58 @@
+use crate::*;
+
+/// The prefix of a PROXY protocol version 1 header.
+pub(crate) const PROXY_V1_PREFIX: &[u8] = b"PROXY ";
+
+/// The longest PROXY protocol version 1 header, including the trailing CRLF.
+pub(crate) const PROXY_V1_MAX_LEN: usize = 107;
+
+/// The protocol of a version 1 header carrying IPv4 addresses.
+pub(crate) const PROXY_V1_TCP4: &str = "TCP4";
+
+/// The protocol of a version 1 header carrying IPv6 addresses.
+pub(crate) const PROXY_V1_TCP6: &str = "TCP6";
+
+/// The protocol of a version 1 header whose addresses are unknown.
+pub(crate) const PROXY_V1_UNKNOWN: &str = "UNKNOWN";
+
+/// The signature every PROXY protocol version 2 header starts with.
+pub(crate) const PROXY_V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The length of the fixed part of a version 2 header.
+pub(crate) const PROXY_V2_HEADER_LEN: usize = 16;
+
+/// The version nibble of a version 2 header.
+pub(crate) const PROXY_V2_VERSION: u8 = 0x2;
+
+/// The command of a version 2 header sent by the proxy itself, such as a health check.
+pub(crate) const PROXY_V2_COMMAND_LOCAL: u8 = 0x0;
+
+/// The command of a version 2 header relaying a client connection.
+pub(crate) const PROXY_V2_COMMAND_PROXY: u8 = 0x1;
+
+/// The address family of a version 2 header without addresses.
+pub(crate) const PROXY_V2_FAMILY_UNSPEC: u8 = 0x0;
+
+/// The address family of a version 2 header carrying IPv4 addresses.
+pub(crate) const PROXY_V2_FAMILY_INET: u8 = 0x1;
+
+/// The address family of a version 2 header carrying IPv6 addresses.
+pub(crate) const PROXY_V2_FAMILY_INET6: u8 = 0x2;
+
+/// The address family of a version 2 header carrying Unix domain socket paths.
+pub(crate) const PROXY_V2_FAMILY_UNIX: u8 = 0x3;
+
+/// The length of the IPv4 address block of a version 2 header.
+pub(crate) const PROXY_V2_INET_LEN: usize = 12;
+
+/// The length of the IPv6 address block of a version 2 header.
+pub(crate) const PROXY_V2_INET6_LEN: usize = 36;
+
+/// The length of the Unix domain socket address block of a version 2 header.
+pub(crate) const PROXY_V2_UNIX_LEN: usize = 216;
+
+/// The length of the type and length fields of a version 2 TLV.
+pub(crate) const PROXY_V2_TLV_HEADER_LEN: usize = 3;
+
+/// The longest time to wait for the PROXY protocol header of a connection.
+pub(crate) const PROXY_HEADER_TIMEOUT: Duration = Duration::from_secs(5);
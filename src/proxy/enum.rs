@@ -0,0 +1,39 @@
+use crate::*;
+
+/// Defines whether connections start with a PROXY protocol header.
+///
+/// The header is sent by a load balancer in front of the server and carries the
+/// address of the client it relays the connection for.
+#[derive(CustomDebug, Clone, Copy, Default, PartialEq, Eq, DisplayDebug)]
+pub enum ProxyProtocol {
+    /// Connections are read as HTTP right away.
+    #[default]
+    Disabled,
+    /// A version 1 or version 2 header is read if the connection starts with one and
+    /// comes from a source added with `Server::proxy_protocol_source`.
+    Optional,
+    /// Connections that do not start with a version 1 or version 2 header are closed.
+    Required,
+}
+
+/// The command of a PROXY protocol header.
+#[derive(CustomDebug, Clone, Copy, PartialEq, Eq, DisplayDebug)]
+pub enum ProxyCommand {
+    /// The connection was opened by the proxy itself, such as a health check.
+    Local,
+    /// The connection is relayed on behalf of a client.
+    Proxy,
+}
+
+/// The start of a connection as far as the PROXY protocol is concerned.
+#[derive(CustomDebug, Clone, Copy, PartialEq, Eq, DisplayDebug)]
+pub(crate) enum ProxyHeaderKind {
+    /// The connection starts with a version 1 header.
+    V1,
+    /// The connection starts with a version 2 header.
+    V2,
+    /// The connection does not start with a header.
+    Absent,
+    /// Too few bytes arrived to tell.
+    Incomplete,
+}
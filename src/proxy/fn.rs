@@ -0,0 +1,114 @@
+use crate::*;
+
+/// Tells from the first bytes of a connection whether it starts with a PROXY protocol header.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The bytes received so far.
+///
+/// # Returns
+///
+/// - `ProxyHeaderKind` - The kind of header, or `Incomplete` if the bytes match the start
+///   of a signature.
+pub(crate) fn detect_proxy_header(bytes: &[u8]) -> ProxyHeaderKind {
+    let matches = |signature: &[u8]| -> bool {
+        let len: usize = bytes.len().min(signature.len());
+        bytes[..len] == signature[..len]
+    };
+    if matches(PROXY_V1_PREFIX) {
+        if bytes.len() >= PROXY_V1_PREFIX.len() {
+            return ProxyHeaderKind::V1;
+        }
+        return ProxyHeaderKind::Incomplete;
+    }
+    if matches(PROXY_V2_SIGNATURE) {
+        if bytes.len() >= PROXY_V2_SIGNATURE.len() {
+            return ProxyHeaderKind::V2;
+        }
+        return ProxyHeaderKind::Incomplete;
+    }
+    ProxyHeaderKind::Absent
+}
+
+/// Finds the length of a PROXY protocol header at the start of the buffer.
+///
+/// # Arguments
+///
+/// - `ProxyHeaderKind` - The kind of header the buffer starts with.
+/// - `&[u8]` - The bytes received so far.
+///
+/// # Returns
+///
+/// - `io::Result<OptionUsize>` - The length of the header, None if more bytes are needed,
+///   or an error if a version 1 header line is too long.
+fn proxy_header_len(kind: ProxyHeaderKind, buffer: &[u8]) -> io::Result<OptionUsize> {
+    if kind == ProxyHeaderKind::V2 {
+        if buffer.len() < PROXY_V2_HEADER_LEN {
+            return Ok(None);
+        }
+        let len: usize =
+            PROXY_V2_HEADER_LEN + u16::from_be_bytes([buffer[14], buffer[15]]) as usize;
+        return Ok((buffer.len() >= len).then_some(len));
+    }
+    let line: &[u8] = &buffer[..buffer.len().min(PROXY_V1_MAX_LEN)];
+    if let Some(index) = line.windows(2).position(|window: &[u8]| window == b"\r\n") {
+        return Ok(Some(index + 2));
+    }
+    if line.len() == PROXY_V1_MAX_LEN {
+        return Err(io::Error::from(io::ErrorKind::InvalidData));
+    }
+    Ok(None)
+}
+
+/// Reads the PROXY protocol header a connection starts with.
+///
+/// The connection is read into the buffer, and the bytes following the header are
+/// left in it, so they can be handed to the request reader. A connection that does not
+/// finish the header within the header timeout is dropped.
+///
+/// # Arguments
+///
+/// - `&mut S` - The accepted connection.
+/// - `&mut Vec<u8>` - The buffer to read into, holding the bytes after the header afterwards.
+///
+/// # Returns
+///
+/// - `io::Result<OptionProxyHeader>` - The header, None if the connection does not start
+///   with one, or an error if the header is malformed, incomplete or too slow.
+pub(crate) async fn read_proxy_header<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    buffer: &mut Vec<u8>,
+) -> io::Result<OptionProxyHeader> {
+    let read = async {
+        let kind: ProxyHeaderKind = loop {
+            match detect_proxy_header(buffer) {
+                ProxyHeaderKind::Absent => return Ok(None),
+                ProxyHeaderKind::Incomplete => {
+                    if stream.read_buf(buffer).await? == 0 {
+                        return Ok(None);
+                    }
+                }
+                kind => break kind,
+            }
+        };
+        let len: usize = loop {
+            if let Some(len) = proxy_header_len(kind, buffer)? {
+                break len;
+            }
+            if stream.read_buf(buffer).await? == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+        };
+        let header: OptionProxyHeader = match kind {
+            ProxyHeaderKind::V1 => ProxyHeader::parse_v1(&buffer[..len]),
+            _ => ProxyHeader::parse_v2(&buffer[..len]),
+        };
+        buffer.drain(..len);
+        header
+            .map(Some)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))
+    };
+    tokio::time::timeout(PROXY_HEADER_TIMEOUT, read)
+        .await
+        .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))?
+}
@@ -0,0 +1,172 @@
+use crate::*;
+
+/// Implementation of methods for `ProxyHeader` structure.
+impl ProxyHeader {
+    /// Parses a version 1 header.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The header line, including the trailing CRLF.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionProxyHeader` - The header, or None if it is malformed.
+    pub(crate) fn parse_v1(line: &[u8]) -> OptionProxyHeader {
+        let line: &str = std::str::from_utf8(line).ok()?.strip_suffix("\r\n")?;
+        let mut fields: Split<'_, char> = line
+            .strip_prefix(std::str::from_utf8(PROXY_V1_PREFIX).ok()?)?
+            .split(' ');
+        let protocol: &str = fields.next()?;
+        let mut header: Self = Self {
+            version: 1,
+            command: ProxyCommand::Proxy,
+            source: None,
+            destination: None,
+            tlvs: Vec::new(),
+        };
+        if protocol == PROXY_V1_UNKNOWN {
+            return Some(header);
+        }
+        let source_ip: IpAddr = fields.next()?.parse().ok()?;
+        let destination_ip: IpAddr = fields.next()?.parse().ok()?;
+        let source_port: u16 = fields.next()?.parse().ok()?;
+        let destination_port: u16 = fields.next()?.parse().ok()?;
+        let family_matches: bool = match protocol {
+            PROXY_V1_TCP4 => source_ip.is_ipv4() && destination_ip.is_ipv4(),
+            PROXY_V1_TCP6 => source_ip.is_ipv6() && destination_ip.is_ipv6(),
+            _ => false,
+        };
+        if !family_matches || fields.next().is_some() {
+            return None;
+        }
+        header.source = Some(SocketAddr::new(source_ip, source_port));
+        header.destination = Some(SocketAddr::new(destination_ip, destination_port));
+        Some(header)
+    }
+
+    /// Parses a version 2 header.
+    ///
+    /// Addresses of families other than IPv4 and IPv6, such as Unix domain sockets,
+    /// are skipped, as are the addresses of a `LOCAL` command.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The whole header, starting with the signature.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionProxyHeader` - The header, or None if it is malformed.
+    pub(crate) fn parse_v2(bytes: &[u8]) -> OptionProxyHeader {
+        if bytes.len() < PROXY_V2_HEADER_LEN || !bytes.starts_with(PROXY_V2_SIGNATURE) {
+            return None;
+        }
+        let version_command: u8 = bytes[12];
+        if version_command >> 4 != PROXY_V2_VERSION {
+            return None;
+        }
+        let command: ProxyCommand = match version_command & 0x0F {
+            PROXY_V2_COMMAND_LOCAL => ProxyCommand::Local,
+            PROXY_V2_COMMAND_PROXY => ProxyCommand::Proxy,
+            _ => return None,
+        };
+        let length: usize = u16::from_be_bytes([bytes[14], bytes[15]]) as usize;
+        let payload: &[u8] = bytes.get(PROXY_V2_HEADER_LEN..PROXY_V2_HEADER_LEN + length)?;
+        let (addresses, address_len): (Option<(SocketAddr, SocketAddr)>, usize) =
+            match bytes[13] >> 4 {
+                PROXY_V2_FAMILY_INET => {
+                    let block: &[u8] = payload.get(..PROXY_V2_INET_LEN)?;
+                    let source: [u8; 4] = block[0..4].try_into().ok()?;
+                    let destination: [u8; 4] = block[4..8].try_into().ok()?;
+                    (
+                        Some((
+                            SocketAddr::new(
+                                IpAddr::from(source),
+                                u16::from_be_bytes([block[8], block[9]]),
+                            ),
+                            SocketAddr::new(
+                                IpAddr::from(destination),
+                                u16::from_be_bytes([block[10], block[11]]),
+                            ),
+                        )),
+                        PROXY_V2_INET_LEN,
+                    )
+                }
+                PROXY_V2_FAMILY_INET6 => {
+                    let block: &[u8] = payload.get(..PROXY_V2_INET6_LEN)?;
+                    let source: [u8; 16] = block[0..16].try_into().ok()?;
+                    let destination: [u8; 16] = block[16..32].try_into().ok()?;
+                    (
+                        Some((
+                            SocketAddr::new(
+                                IpAddr::from(source),
+                                u16::from_be_bytes([block[32], block[33]]),
+                            ),
+                            SocketAddr::new(
+                                IpAddr::from(destination),
+                                u16::from_be_bytes([block[34], block[35]]),
+                            ),
+                        )),
+                        PROXY_V2_INET6_LEN,
+                    )
+                }
+                PROXY_V2_FAMILY_UNSPEC => (None, 0),
+                PROXY_V2_FAMILY_UNIX => {
+                    payload.get(..PROXY_V2_UNIX_LEN)?;
+                    (None, PROXY_V2_UNIX_LEN)
+                }
+                _ => return None,
+            };
+        let addresses: Option<(SocketAddr, SocketAddr)> =
+            addresses.filter(|_| command == ProxyCommand::Proxy);
+        Some(Self {
+            version: 2,
+            command,
+            source: addresses.map(|(source, _)| source),
+            destination: addresses.map(|(_, destination)| destination),
+            tlvs: ProxyTlv::parse_all(&payload[address_len..])?,
+        })
+    }
+
+    /// Retrieves the value of the first TLV of a type.
+    ///
+    /// # Arguments
+    ///
+    /// - `u8` - The type of the TLV, such as `0x02` for the authority the client asked for.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&[u8]>` - The value of the TLV, or None if the header has none of the type.
+    pub fn get_tlv(&self, kind: u8) -> Option<&[u8]> {
+        self.tlvs
+            .iter()
+            .find(|tlv: &&ProxyTlv| tlv.kind == kind)
+            .map(|tlv: &ProxyTlv| tlv.value.as_slice())
+    }
+}
+
+/// Implementation of methods for `ProxyTlv` structure.
+impl ProxyTlv {
+    /// Parses the TLVs that follow the addresses of a version 2 header.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The bytes after the address block.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<VecProxyTlv>` - The TLVs, or None if one of them is truncated.
+    pub(crate) fn parse_all(mut bytes: &[u8]) -> Option<VecProxyTlv> {
+        let mut tlvs: VecProxyTlv = Vec::new();
+        while !bytes.is_empty() {
+            let header: &[u8] = bytes.get(..PROXY_V2_TLV_HEADER_LEN)?;
+            let length: usize = u16::from_be_bytes([header[1], header[2]]) as usize;
+            let end: usize = PROXY_V2_TLV_HEADER_LEN + length;
+            tlvs.push(Self {
+                kind: header[0],
+                value: bytes.get(PROXY_V2_TLV_HEADER_LEN..end)?.to_vec(),
+            });
+            bytes = &bytes[end..];
+        }
+        Some(tlvs)
+    }
+}
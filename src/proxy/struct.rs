@@ -0,0 +1,32 @@
+use crate::*;
+
+/// The PROXY protocol header a connection started with.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct ProxyHeader {
+    /// The protocol version, 1 for the text format and 2 for the binary format.
+    #[get(pub)]
+    pub(super) version: u8,
+    /// Whether the connection is relayed for a client or opened by the proxy itself.
+    #[get(pub)]
+    pub(super) command: ProxyCommand,
+    /// The address of the client, if the proxy relayed an IPv4 or IPv6 connection.
+    #[get(pub)]
+    pub(super) source: OptionSocketAddr,
+    /// The address the client connected to, if the proxy relayed an IPv4 or IPv6 connection.
+    #[get(pub)]
+    pub(super) destination: OptionSocketAddr,
+    /// The TLVs of a version 2 header, in the order they were sent.
+    #[get(pub)]
+    pub(super) tlvs: VecProxyTlv,
+}
+
+/// A type-length-value field of a PROXY protocol version 2 header.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct ProxyTlv {
+    /// The type of the field, such as `0x02` for the authority the client asked for.
+    #[get(pub)]
+    pub(super) kind: u8,
+    /// The raw value of the field.
+    #[get(pub)]
+    pub(super) value: Vec<u8>,
+}
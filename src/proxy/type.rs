@@ -0,0 +1,7 @@
+use crate::*;
+
+/// A type alias for the optional PROXY protocol header of a connection.
+pub type OptionProxyHeader = Option<ProxyHeader>;
+
+/// A type alias for the TLVs of a PROXY protocol header.
+pub type VecProxyTlv = Vec<ProxyTlv>;
@@ -2,3 +2,19 @@
 pub(crate) const DYNAMIC_ROUTE_LEFT_BRACKET: &str = "{";
 /// The character used to denote the end of a dynamic route segment.
 pub(crate) const DYNAMIC_ROUTE_RIGHT_BRACKET: &str = "}";
+/// The separator between the labels of a host name.
+pub(crate) const HOST_LABEL_SEPARATOR: &str = ".";
+/// The label used to denote a wildcard subdomain in a host pattern.
+pub(crate) const HOST_WILDCARD_LABEL: &str = "*";
+/// The path segment that refers to the current directory.
+pub(crate) const CURRENT_DIR_SEGMENT: &str = ".";
+/// The path segment that refers to the parent directory.
+pub(crate) const PARENT_DIR_SEGMENT: &str = "..";
+/// The byte that introduces a percent-encoded octet.
+pub(crate) const PERCENT_ENCODING_PREFIX: u8 = b'%';
+/// The media range that matches every media type.
+pub(crate) const ANY_MEDIA_RANGE: &str = "*/*";
+/// The media subtype wildcard used in ranges such as `text/*`.
+pub(crate) const ANY_MEDIA_SUBTYPE: &str = "*";
+/// The media range parameter holding the relative quality of an `Accept` entry.
+pub(crate) const QUALITY_PARAM: &str = "q";
@@ -20,3 +20,30 @@ pub(crate) enum RouteSegment {
     /// name, and the second is the compiled `Regex` object.
     Regex(String, Regex),
 }
+
+/// Represents the different types of labels that can make up a host pattern.
+///
+/// A host pattern is split on `.` into a sequence of these labels. For example, the pattern
+/// `{tenant}.example.com` is broken down into `Dynamic("tenant")`, `Static("example")`
+/// and `Static("com")`.
+#[derive(CustomDebug, Clone, PartialEq, Eq)]
+pub(crate) enum HostSegment {
+    /// A static label that must match exactly, ignoring ASCII case.
+    Static(String),
+    /// A label that captures its value into the route parameters under the given name.
+    Dynamic(String),
+    /// A leading `*` label that matches one or more arbitrary labels.
+    Wildcard,
+}
+
+/// Defines how a trailing slash in the request path is treated during route matching.
+#[derive(CustomDebug, Clone, Copy, Default, PartialEq, Eq, DisplayDebug)]
+pub enum TrailingSlash {
+    /// The trailing slash is significant, so `/users/` and `/users` are different routes.
+    #[default]
+    Strict,
+    /// The path is matched with the trailing slash added or removed when it does not match as is.
+    Ignore,
+    /// Like `Ignore`, but the client is redirected to the registered path with `308 Permanent Redirect`.
+    Redirect,
+}
@@ -0,0 +1,241 @@
+use crate::*;
+
+/// Percent-encodes a single path segment value.
+///
+/// Unreserved characters as defined by RFC 3986 are kept as they are, every other
+/// byte is written as an uppercase `%XX` escape. This makes the value safe to be
+/// placed between two `/` separators of a generated URL.
+///
+/// # Arguments
+///
+/// - `&str` - The raw segment value.
+///
+/// # Returns
+///
+/// - `String` - The percent-encoded segment.
+pub(crate) fn encode_route_segment(value: &str) -> String {
+    let mut encoded: String = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push(PERCENT_ENCODING_PREFIX as char);
+            encoded.push_str(&format!("{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Percent-encodes a value that may span several path segments.
+///
+/// Each part between `/` separators is encoded with `encode_route_segment`
+/// while the separators themselves are preserved.
+///
+/// # Arguments
+///
+/// - `&str` - The raw path value.
+///
+/// # Returns
+///
+/// - `String` - The percent-encoded path.
+pub(crate) fn encode_route_path(value: &str) -> String {
+    value
+        .split(DEFAULT_HTTP_PATH)
+        .map(encode_route_segment)
+        .collect::<Vec<String>>()
+        .join(DEFAULT_HTTP_PATH)
+}
+
+/// Decodes percent-encoded bytes in a captured route parameter.
+///
+/// Escapes not followed by two hexadecimal digits are kept as they are and invalid UTF-8 sequences are
+/// replaced with the Unicode replacement character.
+///
+/// # Arguments
+///
+/// - `&str` - The raw parameter value.
+///
+/// # Returns
+///
+/// - `String` - The decoded parameter value.
+pub(crate) fn decode_route_param(value: &str) -> String {
+    let bytes: &[u8] = value.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if bytes[index] == PERCENT_ENCODING_PREFIX
+            && index + 2 < bytes.len()
+            && bytes[index + 1].is_ascii_hexdigit()
+            && bytes[index + 2].is_ascii_hexdigit()
+        {
+            let hex: &str = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap_or_default();
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Normalizes a request path before it is matched against the registered routes.
+///
+/// # Arguments
+///
+/// - `&str` - The raw request path.
+/// - `bool` - Whether consecutive slashes are collapsed into one.
+/// - `bool` - Whether `.` and `..` segments are removed as described in RFC 3986.
+///
+/// # Returns
+///
+/// - `String` - The normalized path.
+pub(crate) fn normalize_path(path: &str, merge_slashes: bool, remove_dot_segments: bool) -> String {
+    let mut segments: VecStrRef = Vec::new();
+    let mut split: VecStrRef = path.split(DEFAULT_HTTP_PATH).collect();
+    if split.first() == Some(&EMPTY_STR) {
+        split.remove(0);
+    }
+    let last_index: usize = split.len().saturating_sub(1);
+    for (index, segment) in split.into_iter().enumerate() {
+        let is_last: bool = index == last_index;
+        if merge_slashes && segment.is_empty() && !is_last {
+            continue;
+        }
+        if remove_dot_segments && segment == CURRENT_DIR_SEGMENT {
+            if is_last {
+                segments.push(EMPTY_STR);
+            }
+            continue;
+        }
+        if remove_dot_segments && segment == PARENT_DIR_SEGMENT {
+            segments.pop();
+            if is_last {
+                segments.push(EMPTY_STR);
+            }
+            continue;
+        }
+        segments.push(segment);
+    }
+    format!("{}{}", DEFAULT_HTTP_PATH, segments.join(DEFAULT_HTTP_PATH))
+}
+
+/// Adds or removes the trailing slash of a path.
+///
+/// # Arguments
+///
+/// - `&str` - The path to toggle.
+///
+/// # Returns
+///
+/// - `OptionString` - The alternate path, or None for the root path.
+pub(crate) fn toggle_trailing_slash(path: &str) -> OptionString {
+    if path == DEFAULT_HTTP_PATH || path.is_empty() {
+        return None;
+    }
+    match path.strip_suffix(DEFAULT_HTTP_PATH) {
+        Some(trimmed) => Some(trimmed.to_owned()),
+        None => Some(format!("{}{}", path, DEFAULT_HTTP_PATH)),
+    }
+}
+
+/// Checks whether a media type matches a media range.
+///
+/// Parameters after `;` are ignored and the comparison is case-insensitive.
+/// The range may use `*/*` or `type/*` wildcards.
+///
+/// # Arguments
+///
+/// - `&str` - The media type, such as the value of a `Content-Type` header.
+/// - `&str` - The media range to match against.
+///
+/// # Returns
+///
+/// - `bool` - true if the media type is covered by the range, false otherwise.
+pub(crate) fn media_type_matches(media_type: &str, range: &str) -> bool {
+    let essence = |value: &str| -> String {
+        value
+            .split(SEMICOLON)
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    };
+    let media_type: String = essence(media_type);
+    let range: String = essence(range);
+    if range == ANY_MEDIA_RANGE || range == media_type {
+        return true;
+    }
+    match (
+        range.split_once(DEFAULT_HTTP_PATH),
+        media_type.split_once(DEFAULT_HTTP_PATH),
+    ) {
+        (Some((range_type, ANY_MEDIA_SUBTYPE)), Some((main_type, _))) => range_type == main_type,
+        _ => false,
+    }
+}
+
+/// Computes the quality an `Accept` header assigns to a media type.
+///
+/// The most specific range matching the media type decides, `type/subtype` over
+/// `type/*` over `*/*`, and of equally specific ranges the first one listed. A missing
+/// or invalid `q` parameter counts as 1.
+///
+/// # Arguments
+///
+/// - `&str` - The value of the `Accept` header.
+/// - `&str` - The media type, such as `application/json`.
+///
+/// # Returns
+///
+/// - `f32` - The quality between 0 and 1, 0 if no range matches.
+pub(crate) fn accept_quality(accept: &str, media_type: &str) -> f32 {
+    let specificity = |range: &str| -> usize {
+        let essence: &str = range.split(SEMICOLON).next().unwrap_or_default().trim();
+        if essence == ANY_MEDIA_RANGE {
+            0
+        } else if essence.ends_with(ANY_MEDIA_SUBTYPE) {
+            1
+        } else {
+            2
+        }
+    };
+    let quality = |range: &str| -> f32 {
+        range
+            .split(SEMICOLON)
+            .skip(1)
+            .filter_map(|param: &str| param.split_once(EQUAL))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(QUALITY_PARAM))
+            .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+            .map(|value: f32| value.clamp(0.0, 1.0))
+            .unwrap_or(1.0)
+    };
+    accept
+        .split(COMMA)
+        .filter(|range: &&str| media_type_matches(media_type, range))
+        .min_by_key(|range: &&str| Reverse(specificity(range)))
+        .map(quality)
+        .unwrap_or(0.0)
+}
+
+/// Creates a handler that only sets the response status code.
+///
+/// # Arguments
+///
+/// - `ResponseStatusCode` - The status code to respond with.
+///
+/// # Returns
+///
+/// - `ArcFnPinBoxSendSync` - The status handler.
+pub(crate) fn status_handler(status_code: ResponseStatusCode) -> ArcFnPinBoxSendSync {
+    Arc::new(move |ctx: Context| {
+        Box::pin(async move {
+            ctx.set_response_status_code(status_code)
+                .await
+                .set_response_body(Vec::new())
+                .await;
+        })
+    })
+}
@@ -12,6 +12,7 @@ impl Default for RouteMatcher {
             static_routes: hash_map_xx_hash3_64(),
             dynamic_routes: Vec::new(),
             regex_routes: Vec::new(),
+            named_routes: hash_map_xx_hash3_64(),
         }
     }
 }
@@ -134,16 +135,29 @@ impl RoutePattern {
     /// Matches this route pattern against a request path.
     ///
     /// If the pattern matches, extracts any dynamic or regex parameters.
+    /// Unless empty segments are significant, they are skipped, so `/users//1/`
+    /// matches `/users/{id}`.
     ///
     /// # Arguments
     ///
     /// - `&str` - The request path to match against.
+    /// - `bool` - Whether static segments are compared case-sensitively.
+    /// - `bool` - Whether empty path segments are significant.
     ///
     /// # Returns
     ///
     /// - `Option<RouteParams>` - Some with parameters if matched, None otherwise.
-    pub(crate) fn match_path(&self, path: &str) -> OptionRouteParams {
-        let path: &str = path.trim_start_matches(DEFAULT_HTTP_PATH);
+    pub(crate) fn match_path(
+        &self,
+        path: &str,
+        case_sensitive: bool,
+        empty_segments: bool,
+    ) -> OptionRouteParams {
+        let path: &str = if empty_segments {
+            path.strip_prefix(DEFAULT_HTTP_PATH).unwrap_or(path)
+        } else {
+            path.trim_start_matches(DEFAULT_HTTP_PATH)
+        };
         let route_segments_len: usize = self.get_0().len();
         let is_tail_regex: bool = matches!(self.get_0().last(), Some(RouteSegment::Regex(_, _)));
         if path.is_empty() {
@@ -152,21 +166,10 @@ impl RoutePattern {
             }
             return None;
         }
-        let mut path_segments: VecStrRef = Vec::with_capacity(route_segments_len);
-        let mut segment_start: usize = 0;
-        let path_bytes: &[u8] = path.as_bytes();
-        let path_separator_byte: u8 = b'/';
-        for i in 0..path_bytes.len() {
-            if path_bytes[i] == path_separator_byte {
-                if segment_start < i {
-                    path_segments.push(&path[segment_start..i]);
-                }
-                segment_start = i + 1;
-            }
-        }
-        if segment_start < path.len() {
-            path_segments.push(&path[segment_start..]);
-        }
+        let path_segments: VecStrRef = path
+            .split(DEFAULT_HTTP_PATH)
+            .filter(|segment: &&str| empty_segments || !segment.is_empty())
+            .collect();
         let path_segments_len: usize = path_segments.len();
         if (!is_tail_regex && path_segments_len != route_segments_len)
             || (is_tail_regex && path_segments_len < route_segments_len - 1)
@@ -177,12 +180,21 @@ impl RoutePattern {
         for (idx, segment) in self.get_0().iter().enumerate() {
             match segment {
                 RouteSegment::Static(expected_path) => {
-                    if path_segments.get(idx).copied() != Some(expected_path.as_str()) {
+                    let Some(value) = path_segments.get(idx) else {
+                        return None;
+                    };
+                    let is_equal: bool = if case_sensitive {
+                        value == expected_path
+                    } else {
+                        value.eq_ignore_ascii_case(expected_path)
+                    };
+                    if !is_equal {
                         return None;
                     }
                 }
                 RouteSegment::Dynamic(param_name) => {
-                    let Some(value) = path_segments.get(idx) else {
+                    let Some(value) = path_segments.get(idx).filter(|value| !value.is_empty())
+                    else {
                         return None;
                     };
                     params.insert(param_name.clone(), value.to_string());
@@ -213,6 +225,125 @@ impl RoutePattern {
         Some(params)
     }
 
+    /// Builds a concrete path from this route pattern by substituting parameters.
+    ///
+    /// Dynamic and regex segments are replaced with the supplied values, which are
+    /// percent-encoded. Regex segments are validated against their expression first.
+    ///
+    /// # Arguments
+    ///
+    /// - `&RouteParams` - The parameter values keyed by segment name.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<String, RouteError>` - The generated path on success, or RouteError on failure.
+    pub(crate) fn build_path(&self, params: &RouteParams) -> ResultStringRouteError {
+        let route_segments_len: usize = self.get_0().len();
+        let mut path_segments: Vec<String> = Vec::with_capacity(route_segments_len);
+        for (idx, segment) in self.get_0().iter().enumerate() {
+            match segment {
+                RouteSegment::Static(expected_path) => {
+                    path_segments.push(expected_path.clone());
+                }
+                RouteSegment::Dynamic(param_name) => {
+                    let value: &String = params
+                        .get(param_name)
+                        .ok_or_else(|| RouteError::MissingRouteParam(param_name.clone()))?;
+                    path_segments.push(encode_route_segment(value));
+                }
+                RouteSegment::Regex(param_name, regex) => {
+                    let value: &String = params
+                        .get(param_name)
+                        .ok_or_else(|| RouteError::MissingRouteParam(param_name.clone()))?;
+                    let is_tail: bool = idx == route_segments_len - 1;
+                    let is_full_match: bool = regex
+                        .find(value)
+                        .map(|mat| mat.start() == 0 && mat.end() == value.len())
+                        .unwrap_or(false);
+                    if !is_full_match || (!is_tail && value.contains(DEFAULT_HTTP_PATH)) {
+                        return Err(RouteError::InvalidRouteParam(format!(
+                            "{}{}{}",
+                            param_name, COLON_SPACE, value
+                        )));
+                    }
+                    if is_tail {
+                        path_segments.push(encode_route_path(value));
+                    } else {
+                        path_segments.push(encode_route_segment(value));
+                    }
+                }
+            }
+        }
+        Ok(format!(
+            "{}{}",
+            DEFAULT_HTTP_PATH,
+            path_segments.join(DEFAULT_HTTP_PATH)
+        ))
+    }
+
+    /// Creates a new route pattern by appending the segments of another pattern.
+    ///
+    /// # Arguments
+    ///
+    /// - `&RoutePattern` - The pattern appended to this one.
+    ///
+    /// # Returns
+    ///
+    /// - `RoutePattern` - The combined pattern.
+    pub(crate) fn join(&self, other: &RoutePattern) -> RoutePattern {
+        let mut segments: VecRouteSegment = self.get_0().clone();
+        segments.extend(other.get_0().iter().cloned());
+        Self(segments)
+    }
+
+    /// Renders the route pattern back into its string form.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The route pattern string, such as `/users/{id}`.
+    pub(crate) fn to_string_pattern(&self) -> String {
+        let segments: Vec<String> = self
+            .get_0()
+            .iter()
+            .map(|segment: &RouteSegment| match segment {
+                RouteSegment::Static(path) => path.clone(),
+                RouteSegment::Dynamic(name) => format!(
+                    "{}{}{}",
+                    DYNAMIC_ROUTE_LEFT_BRACKET, name, DYNAMIC_ROUTE_RIGHT_BRACKET
+                ),
+                RouteSegment::Regex(name, regex) => format!(
+                    "{}{}{}{}{}",
+                    DYNAMIC_ROUTE_LEFT_BRACKET,
+                    name,
+                    COLON,
+                    regex.as_str(),
+                    DYNAMIC_ROUTE_RIGHT_BRACKET
+                ),
+            })
+            .collect();
+        format!("{}{}", DEFAULT_HTTP_PATH, segments.join(DEFAULT_HTTP_PATH))
+    }
+
+    /// Returns the number of segments in the route pattern.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The segment count.
+    pub(crate) fn len(&self) -> usize {
+        self.get_0().len()
+    }
+
+    /// Checks if the route pattern contains any regex segment.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if a regex segment is present, false otherwise.
+    pub(crate) fn has_regex(&self) -> bool {
+        self.get_0()
+            .iter()
+            .any(|segment: &RouteSegment| matches!(segment, RouteSegment::Regex(_, _)))
+    }
+
     /// Checks if the route pattern is static.
     ///
     /// # Returns
@@ -240,6 +371,353 @@ impl RoutePattern {
     }
 }
 
+/// Provides constructors for the common kinds of route guards.
+impl RouteGuard {
+    /// Creates a guard from a custom predicate.
+    ///
+    /// # Arguments
+    ///
+    /// - `ResponseStatusCode` - The status code responded when the guard rejects the request.
+    /// - `F` - The predicate evaluated against the request.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The new guard.
+    pub fn new<F>(status_code: ResponseStatusCode, predicate: F) -> Self
+    where
+        F: Fn(&Request) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            predicate: Arc::new(predicate),
+            status_code,
+        }
+    }
+
+    /// Creates a guard that requires a header with the given value.
+    ///
+    /// The guard rejects with `400 Bad Request`, since the path exists and only the
+    /// request is missing what the route needs.
+    ///
+    /// # Arguments
+    ///
+    /// - `K` - The header name implementing `ToString`.
+    /// - `V` - The expected header value implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The new guard.
+    pub fn header<K: ToString, V: ToString>(key: K, value: V) -> Self {
+        let key: String = key.to_string().to_ascii_lowercase();
+        let value: String = value.to_string();
+        Self::new(400, move |request: &Request| {
+            request.has_header_value(key.as_str(), value.as_str())
+        })
+    }
+
+    /// Creates a guard that requires a query parameter with the given value.
+    ///
+    /// The guard rejects with `400 Bad Request`, since the path exists and only the
+    /// request is missing what the route needs.
+    ///
+    /// # Arguments
+    ///
+    /// - `K` - The query parameter name implementing `ToString`.
+    /// - `V` - The expected query parameter value implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The new guard.
+    pub fn query<K: ToString, V: ToString>(key: K, value: V) -> Self {
+        let key: String = key.to_string();
+        let value: String = value.to_string();
+        Self::new(400, move |request: &Request| {
+            request.get_query(key.as_str()).as_ref() == Some(&value)
+        })
+    }
+
+    /// Creates a guard that requires a query parameter to be present.
+    ///
+    /// The guard rejects with `400 Bad Request`, since the path exists and only the
+    /// request is missing what the route needs.
+    ///
+    /// # Arguments
+    ///
+    /// - `K` - The query parameter name implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The new guard.
+    pub fn has_query<K: ToString>(key: K) -> Self {
+        let key: String = key.to_string();
+        Self::new(400, move |request: &Request| {
+            request.get_querys().contains_key(&key)
+        })
+    }
+
+    /// Creates a guard that requires the request body to have the given media type.
+    ///
+    /// Parameters such as `charset` are ignored. The guard rejects with
+    /// `415 Unsupported Media Type`.
+    ///
+    /// # Arguments
+    ///
+    /// - `T` - The media type implementing `ToString`, such as `application/json`.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The new guard.
+    pub fn content_type<T: ToString>(media_type: T) -> Self {
+        let media_type: String = media_type.to_string();
+        Self::new(415, move |request: &Request| {
+            request
+                .get_header_back(CONTENT_TYPE)
+                .map(|value: String| media_type_matches(&value, &media_type))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Creates a guard that requires the client to accept the given media type.
+    ///
+    /// Wildcards such as `*/*` and `application/*` in the `Accept` header are honored,
+    /// and a missing `Accept` header accepts everything. The most specific matching
+    /// range decides, so `application/json;q=0, */*` refuses JSON. Among equally specific
+    /// ranges the first one listed decides. The guard rejects
+    /// with `406 Not Acceptable`.
+    ///
+    /// # Arguments
+    ///
+    /// - `T` - The media type implementing `ToString`, such as `application/json`.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The new guard.
+    pub fn accept<T: ToString>(media_type: T) -> Self {
+        let media_type: String = media_type.to_string();
+        Self::new(406, move |request: &Request| {
+            let Some(accept) = request.get_header_back(ACCEPT) else {
+                return true;
+            };
+            accept_quality(&accept, &media_type) > 0.0
+        })
+    }
+
+    /// Overrides the status code responded when the guard rejects the request.
+    ///
+    /// # Arguments
+    ///
+    /// - `ResponseStatusCode` - The new status code.
+    ///
+    /// # Returns
+    ///
+    /// - `RouteGuard` - The modified guard.
+    pub fn with_status_code(mut self, status_code: ResponseStatusCode) -> Self {
+        self.status_code = status_code;
+        self
+    }
+
+    /// Evaluates the guard against a request.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Request` - The request to check.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the request passes the guard, false otherwise.
+    pub fn check(&self, request: &Request) -> bool {
+        (self.get_predicate())(request)
+    }
+}
+
+/// Selects the handler of a route pattern for a request.
+impl RouteEntry {
+    /// Adds a handler to the entry.
+    ///
+    /// # Arguments
+    ///
+    /// - `VecRouteGuard` - The guards the handler requires, empty for an unguarded handler.
+    /// - `ArcFnPinBoxSendSync` - The handler function.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - false if an unguarded handler is already present, true otherwise.
+    pub(crate) fn insert(&mut self, guards: VecRouteGuard, handler: ArcFnPinBoxSendSync) -> bool {
+        if !guards.is_empty() {
+            self.get_mut_guarded().push((guards, handler));
+            return true;
+        }
+        if self.get_handler().is_some() {
+            return false;
+        }
+        *self.get_mut_handler() = Some(handler);
+        true
+    }
+
+    /// Merges the handlers of another entry into this one.
+    ///
+    /// # Arguments
+    ///
+    /// - `&RouteEntry` - The entry whose handlers are merged.
+    /// - `W` - The function wrapping each merged handler.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - false if both entries have an unguarded handler, true otherwise.
+    pub(crate) fn merge<W>(&mut self, other: &RouteEntry, wrap: &W) -> bool
+    where
+        W: Fn(&ArcFnPinBoxSendSync) -> ArcFnPinBoxSendSync,
+    {
+        if self.get_handler().is_some() && other.get_handler().is_some() {
+            return false;
+        }
+        if let Some(handler) = other.get_handler() {
+            *self.get_mut_handler() = Some(wrap(handler));
+        }
+        for (guards, handler) in other.get_guarded().iter() {
+            self.get_mut_guarded().push((guards.clone(), wrap(handler)));
+        }
+        true
+    }
+
+    /// Selects the first handler whose guards all pass.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Request` - The request to check the guards against.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultArcFnPinBoxSendSyncResponseStatusCode` - The selected handler, or the status code
+    ///   of the first guard that rejected the request.
+    pub(crate) fn select(&self, request: &Request) -> ResultArcFnPinBoxSendSyncResponseStatusCode {
+        let mut rejected_status_code: OptionUsize = None;
+        for (guards, handler) in self.get_guarded().iter() {
+            match guards
+                .iter()
+                .find(|guard: &&RouteGuard| !guard.check(request))
+            {
+                Some(guard) => {
+                    rejected_status_code.get_or_insert(*guard.get_status_code());
+                }
+                None => return Ok(handler.clone()),
+            }
+        }
+        if let Some(handler) = self.get_handler() {
+            return Ok(handler.clone());
+        }
+        Err(rejected_status_code.unwrap_or(404))
+    }
+}
+
+/// Parses and matches host patterns used for virtual hosting.
+///
+/// Supported forms are exact hosts such as `api.example.com`, wildcard subdomains
+/// such as `*.example.com` and captured labels such as `{tenant}.example.com`.
+impl HostPattern {
+    /// Creates a new HostPattern by parsing a host pattern string.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The raw host pattern to parse.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<HostPattern, RouteError>` - The parsed HostPattern on success, or RouteError on failure.
+    pub(crate) fn new(host: &str) -> ResultHostPatternRouteError {
+        let host: &str = host.trim().trim_end_matches(HOST_LABEL_SEPARATOR);
+        if host.is_empty() {
+            return Err(RouteError::InvalidHostPattern(host.to_owned()));
+        }
+        let mut segments: VecHostSegment = Vec::new();
+        for (idx, label) in host.split(HOST_LABEL_SEPARATOR).enumerate() {
+            if label.is_empty() {
+                return Err(RouteError::InvalidHostPattern(host.to_owned()));
+            }
+            if label == HOST_WILDCARD_LABEL {
+                if idx != 0 {
+                    return Err(RouteError::InvalidHostPattern(host.to_owned()));
+                }
+                segments.push(HostSegment::Wildcard);
+            } else if label.starts_with(DYNAMIC_ROUTE_LEFT_BRACKET)
+                && label.ends_with(DYNAMIC_ROUTE_RIGHT_BRACKET)
+                && label.len() > 2
+            {
+                segments.push(HostSegment::Dynamic(label[1..label.len() - 1].to_owned()));
+            } else {
+                segments.push(HostSegment::Static(label.to_ascii_lowercase()));
+            }
+        }
+        Ok(Self(segments))
+    }
+
+    /// Removes the port from a `Host` header value.
+    ///
+    /// Bracketed IPv6 literals such as `[::1]:8080` are handled as well.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The raw host header value.
+    ///
+    /// # Returns
+    ///
+    /// - `&str` - The host name without port.
+    pub(crate) fn strip_port(host: &str) -> &str {
+        if host.starts_with('[') {
+            return host.find(']').map_or(host, |end| &host[..=end]);
+        }
+        host.rsplit_once(':').map_or(host, |(name, _)| name)
+    }
+
+    /// Matches this host pattern against a request host.
+    ///
+    /// The port is ignored and static labels are compared case-insensitively.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The request host to match against.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<RouteParams>` - Some with captured labels if matched, None otherwise.
+    pub(crate) fn match_host(&self, host: &str) -> OptionRouteParams {
+        let host: String = Self::strip_port(host)
+            .trim_end_matches(HOST_LABEL_SEPARATOR)
+            .to_ascii_lowercase();
+        if host.is_empty() {
+            return None;
+        }
+        let labels: VecStrRef = host.split(HOST_LABEL_SEPARATOR).collect();
+        let has_wildcard: bool = matches!(self.get_0().first(), Some(HostSegment::Wildcard));
+        let fixed_len: usize = self.get_0().len() - has_wildcard as usize;
+        if (!has_wildcard && labels.len() != fixed_len)
+            || (has_wildcard && labels.len() <= fixed_len)
+        {
+            return None;
+        }
+        let offset: usize = labels.len() - fixed_len;
+        let mut params: RouteParams = hash_map_xx_hash3_64();
+        for (segment, label) in self
+            .get_0()
+            .iter()
+            .skip(has_wildcard as usize)
+            .zip(labels[offset..].iter())
+        {
+            match segment {
+                HostSegment::Static(expected) => {
+                    if expected != label {
+                        return None;
+                    }
+                }
+                HostSegment::Dynamic(name) => {
+                    params.insert(name.clone(), label.to_string());
+                }
+                HostSegment::Wildcard => return None,
+            }
+        }
+        Some(params)
+    }
+}
+
 /// Manages a collection of routes, enabling efficient lookup and dispatch.
 ///
 /// This struct stores routes categorized by type (static, dynamic, regex)
@@ -255,6 +733,7 @@ impl RouteMatcher {
             static_routes: hash_map_xx_hash3_64(),
             dynamic_routes: Vec::new(),
             regex_routes: Vec::new(),
+            named_routes: hash_map_xx_hash3_64(),
         }
     }
 
@@ -271,30 +750,183 @@ impl RouteMatcher {
     ///
     /// - `Result<(), RouteError>` - Ok on success, or RouteError if pattern is duplicate.
     pub(crate) fn add(&mut self, pattern: &str, handler: ArcFnPinBoxSendSync) -> ResultAddRoute {
+        self.add_with_guards(pattern, Vec::new(), handler)
+    }
+
+    /// Adds a new route with guards and its handler to the matcher.
+    ///
+    /// Several guarded handlers may share a pattern, but only one unguarded handler
+    /// can be registered for it.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The route pattern string.
+    /// - `VecRouteGuard` - The guards the handler requires.
+    /// - `ArcFnPinBoxSendSync` - The handler function for this route.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), RouteError>` - Ok on success, or RouteError if pattern is duplicate.
+    pub(crate) fn add_with_guards(
+        &mut self,
+        pattern: &str,
+        guards: VecRouteGuard,
+        handler: ArcFnPinBoxSendSync,
+    ) -> ResultAddRoute {
         let route_pattern: RoutePattern = RoutePattern::new(pattern)?;
+        let entry: &mut RouteEntry = self.get_mut_entry(pattern, route_pattern);
+        if !entry.insert(guards, handler) {
+            return Err(RouteError::DuplicatePattern(pattern.to_owned()));
+        }
+        Ok(())
+    }
+
+    /// Retrieves the entry of a route pattern, creating an empty one if it does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The key used for static routes.
+    /// - `RoutePattern` - The parsed route pattern.
+    ///
+    /// # Returns
+    ///
+    /// - `&mut RouteEntry` - The entry holding the handlers of the pattern.
+    fn get_mut_entry(&mut self, key: &str, route_pattern: RoutePattern) -> &mut RouteEntry {
         if route_pattern.is_static() {
-            if self.get_static_routes().contains_key(pattern) {
-                return Err(RouteError::DuplicatePattern(pattern.to_owned()));
-            }
-            self.get_mut_static_routes()
-                .insert(pattern.to_string(), handler);
-            return Ok(());
+            return self
+                .get_mut_static_routes()
+                .entry(key.to_string())
+                .or_default();
         }
-        let target_vec: &mut VecRoutePatternArcFnPinBoxSendSync = if route_pattern.is_dynamic() {
+        let target_vec: &mut VecRoutePatternRouteEntry = if route_pattern.is_dynamic() {
             self.get_mut_dynamic_routes()
         } else {
             self.get_mut_regex_routes()
         };
-        let has_same_pattern: bool = target_vec
+        let position: usize = match target_vec
             .iter()
-            .any(|(tmp_pattern, _)| tmp_pattern == &route_pattern);
-        if has_same_pattern {
-            return Err(RouteError::DuplicatePattern(pattern.to_owned()));
+            .position(|(tmp_pattern, _)| tmp_pattern == &route_pattern)
+        {
+            Some(position) => position,
+            None => {
+                target_vec.push((route_pattern, RouteEntry::default()));
+                target_vec.len() - 1
+            }
+        };
+        &mut target_vec[position].1
+    }
+
+    /// Merges the routes of another matcher into this one under a path prefix.
+    ///
+    /// Every handler of the other matcher is passed through the wrap function before
+    /// it is stored, and named routes keep their names with the prefixed pattern.
+    ///
+    /// # Arguments
+    ///
+    /// - `&RoutePattern` - The parsed prefix the routes are mounted under.
+    /// - `&RouteMatcher` - The matcher whose routes are merged.
+    /// - `W` - The function wrapping each merged handler.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), RouteError>` - Ok on success, or RouteError if a pattern or name is duplicate.
+    pub(crate) fn merge<W>(
+        &mut self,
+        prefix: &RoutePattern,
+        other: &RouteMatcher,
+        wrap: W,
+    ) -> ResultAddRoute
+    where
+        W: Fn(&ArcFnPinBoxSendSync) -> ArcFnPinBoxSendSync,
+    {
+        let mut patterns: Vec<(RoutePattern, &RouteEntry)> = Vec::new();
+        for (key, entry) in other.get_static_routes().iter() {
+            patterns.push((RoutePattern::new(key)?, entry));
+        }
+        for (pattern, entry) in other
+            .get_dynamic_routes()
+            .iter()
+            .chain(other.get_regex_routes().iter())
+        {
+            patterns.push((pattern.clone(), entry));
+        }
+        for (pattern, entry) in patterns {
+            let route_pattern: RoutePattern = prefix.join(&pattern);
+            let key: String = route_pattern.to_string_pattern();
+            if !self.get_mut_entry(&key, route_pattern).merge(entry, &wrap) {
+                return Err(RouteError::DuplicatePattern(key));
+            }
+        }
+        for (name, pattern) in other.get_named_routes().iter() {
+            if self.get_named_routes().contains_key(name) {
+                return Err(RouteError::DuplicateRouteName(name.clone()));
+            }
+            self.get_mut_named_routes()
+                .insert(name.clone(), prefix.join(pattern));
         }
-        target_vec.push((route_pattern, handler));
         Ok(())
     }
 
+    /// Adds a new named route and its handler to the matcher.
+    ///
+    /// The route is registered like any other route and its parsed pattern is
+    /// additionally stored under the given name for reverse URL generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The unique name of the route.
+    /// - `&str` - The route pattern string.
+    /// - `ArcFnPinBoxSendSync` - The handler function for this route.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), RouteError>` - Ok on success, or RouteError if name or pattern is duplicate.
+    pub(crate) fn add_named(
+        &mut self,
+        name: &str,
+        pattern: &str,
+        handler: ArcFnPinBoxSendSync,
+    ) -> ResultAddRoute {
+        if self.get_named_routes().contains_key(name) {
+            return Err(RouteError::DuplicateRouteName(name.to_owned()));
+        }
+        self.add(pattern, handler)?;
+        let route_pattern: RoutePattern = RoutePattern::new(pattern)?;
+        self.get_mut_named_routes()
+            .insert(name.to_owned(), route_pattern);
+        Ok(())
+    }
+
+    /// Checks whether a route is registered under a name.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The route name.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if a route has the name, false otherwise.
+    pub(crate) fn has_named_route(&self, name: &str) -> bool {
+        self.get_named_routes().contains_key(name)
+    }
+
+    /// Generates the URL path of a named route.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The name the route was registered with.
+    /// - `&RouteParams` - The values to substitute into the route pattern.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<String, RouteError>` - The generated path on success, or RouteError on failure.
+    pub(crate) fn url_for(&self, name: &str, params: &RouteParams) -> ResultStringRouteError {
+        self.get_named_routes()
+            .get(name)
+            .ok_or_else(|| RouteError::UnknownRouteName(name.to_owned()))?
+            .build_path(params)
+    }
+
     /// Removes a route from the matcher based on its pattern.
     ///
     /// # Arguments
@@ -306,11 +938,12 @@ impl RouteMatcher {
     /// - `bool` - true if route was removed, false otherwise.
     pub(crate) fn remove(&mut self, pattern: &str) -> bool {
         if let Ok(route_pattern) = RoutePattern::new(pattern) {
+            self.get_mut_named_routes()
+                .retain(|_, named_pattern| named_pattern != &route_pattern);
             if route_pattern.is_static() {
                 return self.get_mut_static_routes().remove(pattern).is_some();
             }
-            let target_vec: &mut VecRoutePatternArcFnPinBoxSendSync = if route_pattern.is_dynamic()
-            {
+            let target_vec: &mut VecRoutePatternRouteEntry = if route_pattern.is_dynamic() {
                 self.get_mut_dynamic_routes()
             } else {
                 self.get_mut_regex_routes()
@@ -340,47 +973,73 @@ impl RouteMatcher {
             return true;
         }
         for (pattern, _) in self.get_dynamic_routes().iter() {
-            if pattern.match_path(path).is_some() {
+            if pattern.match_path(path, true, false).is_some() {
                 return true;
             }
         }
         for (pattern, _) in self.get_regex_routes().iter() {
-            if pattern.match_path(path).is_some() {
+            if pattern.match_path(path, true, false).is_some() {
                 return true;
             }
         }
         false
     }
 
-    /// Finds the handler for a path by matching against registered routes.
+    /// Finds the handler and captured parameters for a request by matching against registered routes.
+    ///
+    /// When the path matches but every candidate is rejected by its guards, the next
+    /// matching pattern is tried. The status code of the first rejecting guard is
+    /// recorded, so the caller can respond with it once no other route accepts.
     ///
     /// # Arguments
     ///
-    /// - `&Context` - The request context.
+    /// - `&Request` - The request the route guards are checked against.
     /// - `&str` - The request path to resolve.
+    /// - `bool` - Whether static segments are compared case-sensitively.
+    /// - `bool` - Whether empty path segments are significant for dynamic and regex routes.
+    /// - `&mut OptionUsize` - The status code of the first guard rejection, kept if already set.
     ///
     /// # Returns
     ///
-    /// - `Option<ArcFnPinBoxSendSync>` - Some handler if match found, None otherwise.
-    pub(crate) async fn resolve_route(
+    /// - `OptionArcFnPinBoxSendSyncRouteParams` - Some handler with its parameters if match found, None otherwise.
+    pub(crate) fn find_route(
         &self,
-        ctx: &Context,
+        request: &Request,
         path: &str,
-    ) -> OptionArcFnPinBoxSendSync {
-        if let Some(handler) = self.get_static_routes().get(path) {
-            ctx.set_route_params(RouteParams::default()).await;
-            return Some(handler.clone());
-        }
-        for (pattern, handler) in self.get_dynamic_routes().iter() {
-            if let Some(params) = pattern.match_path(path) {
-                ctx.set_route_params(params).await;
-                return Some(handler.clone());
+        case_sensitive: bool,
+        empty_segments: bool,
+        rejected_status_code: &mut OptionUsize,
+    ) -> OptionArcFnPinBoxSendSyncRouteParams {
+        let mut select = |entry: &RouteEntry| -> OptionArcFnPinBoxSendSync {
+            match entry.select(request) {
+                Ok(handler) => Some(handler),
+                Err(status_code) => {
+                    rejected_status_code.get_or_insert(status_code);
+                    None
+                }
             }
+        };
+        let static_entry: Option<&RouteEntry> = match self.get_static_routes().get(path) {
+            Some(entry) => Some(entry),
+            None if !case_sensitive => self
+                .get_static_routes()
+                .iter()
+                .find(|(static_path, _)| static_path.eq_ignore_ascii_case(path))
+                .map(|(_, entry)| entry),
+            None => None,
+        };
+        if let Some(handler) = static_entry.and_then(&mut select) {
+            return Some((handler, RouteParams::default()));
         }
-        for (pattern, handler) in self.get_regex_routes().iter() {
-            if let Some(params) = pattern.match_path(path) {
-                ctx.set_route_params(params).await;
-                return Some(handler.clone());
+        for (pattern, entry) in self
+            .get_dynamic_routes()
+            .iter()
+            .chain(self.get_regex_routes().iter())
+        {
+            if let Some(params) = pattern.match_path(path, case_sensitive, empty_segments) {
+                if let Some(handler) = select(entry) {
+                    return Some((handler, params));
+                }
             }
         }
         None
@@ -12,6 +12,51 @@ pub(crate) struct RoutePattern(
     pub(super) VecRouteSegment,
 );
 
+/// A predicate that decides whether a route handler accepts a request.
+///
+/// Guards are evaluated after the path of a route has matched. When a guard fails,
+/// the next candidate registered for the same path is tried, and if none is left,
+/// the status code of the failed guard is returned to the client.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug)]
+pub struct RouteGuard {
+    /// The predicate evaluated against the request.
+    #[debug(skip)]
+    #[get(pub(crate))]
+    pub(super) predicate: ArcFnRequestBoolSendSync,
+    /// The status code responded when the guard rejects the request.
+    #[get(pub)]
+    pub(super) status_code: ResponseStatusCode,
+}
+
+/// Holds the handlers registered for a single route pattern.
+///
+/// A pattern can have one unguarded handler and any number of guarded handlers.
+/// Guarded handlers are tried in registration order before the unguarded one.
+#[derive(Clone, Default, CustomDebug, Getter, GetterMut, DisplayDebug)]
+pub(crate) struct RouteEntry {
+    /// The handler used when no guarded handler accepts the request.
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    pub(super) handler: OptionArcFnPinBoxSendSync,
+    /// The guarded handlers, each paired with the guards it requires.
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    pub(super) guarded: VecVecRouteGuardArcFnPinBoxSendSync,
+}
+
+/// Represents a parsed host pattern used for virtual hosting.
+///
+/// This struct wraps a vector of `HostSegment`s, one for each label of the host name.
+/// It is used to select the group of routes that serves a given `Host` header.
+#[derive(Debug, Clone, Getter, PartialEq, Eq, DisplayDebug)]
+pub(crate) struct HostPattern(
+    /// The collection of labels that make up the host pattern.
+    #[get(pub(super))]
+    pub(super) VecHostSegment,
+);
+
 /// The core routing engine responsible for matching request paths to their corresponding handlers.
 ///
 /// The matcher categorizes routes into three types for optimized performance:
@@ -28,17 +73,23 @@ pub(crate) struct RouteMatcher {
     #[debug(skip)]
     #[get(pub(super))]
     #[get_mut(pub(super))]
-    pub(super) static_routes: HashMapStringArcFnPinBoxSendSyncXxHash3_64,
+    pub(super) static_routes: HashMapStringRouteEntryXxHash3_64,
     /// A vector of routes that contain dynamic segments (e.g., path parameters).
     /// These are evaluated sequentially if no static route matches.
     #[debug(skip)]
     #[get(pub(super))]
     #[get_mut(pub(super))]
-    pub(super) dynamic_routes: VecRoutePatternArcFnPinBoxSendSync,
+    pub(super) dynamic_routes: VecRoutePatternRouteEntry,
     /// A vector of routes that use regular expressions for matching.
     /// These provide the most flexibility but are evaluated last due to their performance overhead.
     #[debug(skip)]
     #[get(pub(super))]
     #[get_mut(pub(super))]
-    pub(super) regex_routes: VecRoutePatternArcFnPinBoxSendSync,
+    pub(super) regex_routes: VecRoutePatternRouteEntry,
+    /// A hash map from route names to their parsed patterns.
+    /// It is used to build URLs for named routes in reverse.
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    pub(super) named_routes: HashMapStringRoutePatternXxHash3_64,
 }
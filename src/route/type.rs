@@ -4,23 +4,49 @@ use crate::*;
 /// The key is the parameter name (e.g., "id"), and the value is the captured string.
 pub type RouteParams = HashMapXxHash3_64<String, String>;
 
+/// A type alias for a thread-safe predicate evaluated against a request.
+pub type ArcFnRequestBoolSendSync = Arc<dyn Fn(&Request) -> bool + Send + Sync>;
+
+/// A type alias for a vector of `RouteGuard`s that must all pass for a handler to be selected.
+pub type VecRouteGuard = Vec<RouteGuard>;
+
+/// A type alias for an optional `TrailingSlash` policy.
+pub(crate) type OptionTrailingSlash = Option<TrailingSlash>;
 /// A type alias for a vector of `RouteSegment`s, representing a parsed route.
 pub(crate) type VecRouteSegment = Vec<RouteSegment>;
+/// A type alias for a vector of `HostSegment`s, representing a parsed host pattern.
+pub(crate) type VecHostSegment = Vec<HostSegment>;
+/// A type alias for a vector containing tuples of a `HostPattern` and the routes served for it.
+pub(crate) type VecHostPatternRouteMatcher = Vec<(HostPattern, RouteMatcher)>;
 /// A type alias for a vector of string slices, often used for path components.
 pub(crate) type VecStrRef<'a> = Vec<&'a str>;
-/// A type alias for a vector containing tuples of a `RoutePattern` and its associated handler function.
+/// A type alias for a vector containing tuples of a `RoutePattern` and its associated route entry.
 /// This is used for storing dynamic and regex routes.
-pub(crate) type VecRoutePatternArcFnPinBoxSendSync = Vec<(RoutePattern, ArcFnPinBoxSendSync)>;
-/// A type alias for a hash map that stores static routes and their handlers.
+pub(crate) type VecRoutePatternRouteEntry = Vec<(RoutePattern, RouteEntry)>;
+/// A type alias for a hash map that stores static routes and their route entries.
 /// The key is the exact path string.
-pub(crate) type HashMapStringArcFnPinBoxSendSyncXxHash3_64 =
-    HashMapXxHash3_64<String, ArcFnPinBoxSendSync>;
+pub(crate) type HashMapStringRouteEntryXxHash3_64 = HashMapXxHash3_64<String, RouteEntry>;
+/// A type alias for a vector of guarded handlers, each paired with the guards it requires.
+pub(crate) type VecVecRouteGuardArcFnPinBoxSendSync = Vec<(VecRouteGuard, ArcFnPinBoxSendSync)>;
+/// A type alias for the outcome of selecting a handler from a route entry.
+/// The error holds the status code of the guard that rejected the request.
+pub(crate) type ResultArcFnPinBoxSendSyncResponseStatusCode =
+    Result<ArcFnPinBoxSendSync, ResponseStatusCode>;
+/// A type alias for a hash map that stores named routes and their parsed patterns.
+/// The key is the route name given at registration.
+pub(crate) type HashMapStringRoutePatternXxHash3_64 = HashMapXxHash3_64<String, RoutePattern>;
 /// A type alias for a `Result` returned when adding a new route, indicating success or a `RouteError`.
 pub(crate) type ResultAddRoute = Result<(), RouteError>;
 /// A type alias for a `Result` from parsing a route string, yielding a vector of `RouteSegment`s or a `RouteError`.
 pub(crate) type ResultVecRouteSegmentRouteError = Result<VecRouteSegment, RouteError>;
+/// A type alias for a `Result` from creating a `HostPattern`, which can fail with a `RouteError`.
+pub(crate) type ResultHostPatternRouteError = Result<HostPattern, RouteError>;
+/// A type alias for an optional matched handler together with its captured parameters.
+pub(crate) type OptionArcFnPinBoxSendSyncRouteParams = Option<(ArcFnPinBoxSendSync, RouteParams)>;
 /// A type alias for a `Result` from creating a `RoutePattern`, which can fail with a `RouteError`.
 pub(crate) type ResultRoutePatternRouteError = Result<RoutePattern, RouteError>;
 /// A type alias for an optional `RouteParams` map.
 /// It is `Some` if a dynamic or regex route matches and captures parameters, and `None` otherwise.
 pub(crate) type OptionRouteParams = Option<RouteParams>;
+/// A type alias for a `Result` from generating a URL for a named route.
+pub type ResultStringRouteError = Result<String, RouteError>;
@@ -0,0 +1,53 @@
+use crate::*;
+
+/// The environment variable holding the process id systemd passed the listeners to.
+#[cfg(unix)]
+pub(crate) const LISTEN_PID: &str = "LISTEN_PID";
+
+/// The environment variable holding the number of listeners passed by systemd.
+#[cfg(unix)]
+pub(crate) const LISTEN_FDS: &str = "LISTEN_FDS";
+
+/// The environment variable holding the names of the listeners passed by systemd.
+#[cfg(unix)]
+pub(crate) const LISTEN_FDNAMES: &str = "LISTEN_FDNAMES";
+
+/// The separator of the names in `LISTEN_FDNAMES`.
+#[cfg(unix)]
+pub(crate) const LISTEN_FDNAMES_SEPARATOR: char = ':';
+
+/// The first file descriptor passed through systemd socket activation.
+#[cfg(unix)]
+pub(crate) const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// The environment variable holding the token that identifies the readiness pipe of an upgrade.
+#[cfg(unix)]
+pub(crate) const UPGRADE_TOKEN: &str = "HYPERLANE_UPGRADE_TOKEN";
+
+/// The environment variable holding the file descriptor of the readiness pipe of an upgrade.
+#[cfg(unix)]
+pub(crate) const UPGRADE_READY_FD: &str = "HYPERLANE_UPGRADE_READY_FD";
+
+/// The environment variable holding the file descriptors handed over, separated by `:`.
+#[cfg(unix)]
+pub(crate) const UPGRADE_FDS: &str = "HYPERLANE_UPGRADE_FDS";
+
+/// The environment variable holding the tags of the listeners handed over, separated by `:`.
+#[cfg(unix)]
+pub(crate) const UPGRADE_FDNAMES: &str = "HYPERLANE_UPGRADE_FDNAMES";
+
+/// The suffix Linux appends to the path of a running executable that was replaced on disk.
+#[cfg(unix)]
+pub(crate) const DELETED_EXE_SUFFIX: &str = " (deleted)";
+
+/// The byte a new process writes to the readiness pipe once it serves the listeners.
+#[cfg(unix)]
+pub(crate) const UPGRADE_READY_SIGNAL: &[u8] = b"R";
+
+/// The time an upgrading server waits for the new process to serve the listeners.
+#[cfg(unix)]
+pub(crate) const UPGRADE_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default time an upgraded server waits for in-flight connections.
+#[cfg(unix)]
+pub(crate) const DEFAULT_UPGRADE_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
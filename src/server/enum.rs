@@ -0,0 +1,20 @@
+use crate::*;
+
+/// A bound listener together with what its connections are tagged with.
+pub(crate) enum ServerListener {
+    /// A TCP listener and its optional tag.
+    Tcp(TcpListener, OptionString),
+    /// A Unix domain socket listener and the path of its socket file.
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+/// A pre-opened listening socket of a supported kind.
+#[derive(CustomDebug)]
+pub(crate) enum InheritedSocket {
+    /// A TCP listener.
+    Tcp(StdTcpListener),
+    /// A Unix domain socket listener.
+    #[cfg(unix)]
+    Unix(StdUnixListener),
+}
@@ -0,0 +1,287 @@
+use crate::*;
+
+/// Pairs file descriptors with the names listed next to them.
+///
+/// # Arguments
+///
+/// - `VecRawFd` - The file descriptors.
+/// - `Option<&str>` - The names separated by `:`, where an empty name means no name.
+///
+/// # Returns
+///
+/// - `VecRawFdOptionString` - The file descriptors and their names.
+#[cfg(unix)]
+fn name_listen_fds(fds: VecRawFd, names: Option<&str>) -> VecRawFdOptionString {
+    let names: VecStrRef = names
+        .unwrap_or_default()
+        .split(LISTEN_FDNAMES_SEPARATOR)
+        .collect();
+    fds.into_iter()
+        .enumerate()
+        .map(|(index, fd): (usize, RawFd)| {
+            let name: OptionString = names
+                .get(index)
+                .filter(|name: &&&str| !name.is_empty())
+                .map(|name: &&str| name.to_string());
+            (fd, name)
+        })
+        .collect()
+}
+
+/// Reads the listeners passed through systemd socket activation.
+///
+/// The listeners are only meant for this process if `LISTEN_PID` holds its id.
+/// They start at file descriptor 3 and are named by `LISTEN_FDNAMES`, if set.
+///
+/// # Arguments
+///
+/// - `Option<&str>` - The value of `LISTEN_PID`.
+/// - `Option<&str>` - The value of `LISTEN_FDS`.
+/// - `Option<&str>` - The value of `LISTEN_FDNAMES`.
+/// - `u32` - The id of this process.
+///
+/// # Returns
+///
+/// - `VecRawFdOptionString` - The file descriptors and their names, or nothing if none were passed.
+#[cfg(unix)]
+pub(crate) fn parse_listen_fds(
+    listen_pid: Option<&str>,
+    listen_fds: Option<&str>,
+    listen_fdnames: Option<&str>,
+    pid: u32,
+) -> VecRawFdOptionString {
+    if listen_pid.and_then(|value| value.parse::<u32>().ok()) != Some(pid) {
+        return Vec::new();
+    }
+    let count: RawFd = listen_fds
+        .and_then(|value| value.parse::<RawFd>().ok())
+        .unwrap_or_default();
+    let fds: VecRawFd = (0..count.max(0))
+        .map(|index: RawFd| SD_LISTEN_FDS_START + index)
+        .collect();
+    name_listen_fds(fds, listen_fdnames)
+}
+
+/// Reads the listeners handed over by the process this one was upgraded from.
+///
+/// # Arguments
+///
+/// - `Option<&str>` - The value of `HYPERLANE_UPGRADE_FDS`.
+/// - `Option<&str>` - The value of `HYPERLANE_UPGRADE_FDNAMES`.
+///
+/// # Returns
+///
+/// - `VecRawFdOptionString` - The file descriptors and their tags, or nothing if none were handed over.
+#[cfg(unix)]
+pub(crate) fn parse_upgrade_fds(
+    upgrade_fds: Option<&str>,
+    upgrade_fdnames: Option<&str>,
+) -> VecRawFdOptionString {
+    let fds: VecRawFd = upgrade_fds
+        .unwrap_or_default()
+        .split(LISTEN_FDNAMES_SEPARATOR)
+        .filter_map(|fd: &str| fd.parse::<RawFd>().ok())
+        .collect();
+    name_listen_fds(fds, upgrade_fdnames)
+}
+
+/// Computes the token that identifies the readiness pipe of an upgrade.
+///
+/// The token is made of the device and inode numbers of the pipe, which only the
+/// process holding the pipe can present.
+///
+/// # Arguments
+///
+/// - `&StdFile` - An end of the readiness pipe.
+///
+/// # Returns
+///
+/// - `io::Result<String>` - The token.
+#[cfg(unix)]
+pub(crate) fn upgrade_token(file: &StdFile) -> io::Result<String> {
+    let metadata: Metadata = file.metadata()?;
+    Ok(format!(
+        "{}{}{}",
+        metadata.dev(),
+        LISTEN_FDNAMES_SEPARATOR,
+        metadata.ino()
+    ))
+}
+
+/// Takes ownership of the readiness pipe handed over by the process this one was upgraded from.
+///
+/// The listeners handed over are only meant for this process if the descriptor is the
+/// pipe the token identifies, so processes started later by this one ignore them. The
+/// pipe is set to close-on-exec, so it does not leak into such processes.
+///
+/// # Arguments
+///
+/// - `Option<&str>` - The value of `HYPERLANE_UPGRADE_TOKEN`.
+/// - `Option<&str>` - The value of `HYPERLANE_UPGRADE_READY_FD`.
+///
+/// # Returns
+///
+/// - `OptionStdFile` - The write end of the readiness pipe, or None if it was not handed over.
+#[cfg(unix)]
+pub(crate) fn adopt_upgrade_ready(token: Option<&str>, ready_fd: Option<&str>) -> OptionStdFile {
+    let token: &str = token?;
+    let fd: RawFd = ready_fd?.parse::<RawFd>().ok()?;
+    // SAFETY: the descriptor was passed to this process as the readiness pipe. It is
+    // released again without being closed if the token does not identify it.
+    let file: StdFile = unsafe { StdFile::from_raw_fd(fd) };
+    if upgrade_token(&file).ok().as_deref() != Some(token) {
+        let _ = file.into_raw_fd();
+        return None;
+    }
+    // SAFETY: the descriptor is owned by `file` and stays open during the call.
+    if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+        return None;
+    }
+    Some(file)
+}
+
+/// Takes ownership of an inherited listening socket.
+///
+/// The descriptor is duplicated with close-on-exec set, so it does not leak into
+/// processes spawned later, and the original descriptor is closed. A descriptor
+/// that is not a stream socket of the internet or Unix domain is left untouched.
+///
+/// # Arguments
+///
+/// - `RawFd` - The inherited file descriptor.
+///
+/// # Returns
+///
+/// - `io::Result<InheritedSocket>` - The listener, or the error that shows it is not a stream socket.
+#[cfg(unix)]
+pub(crate) fn adopt_listen_fd(fd: RawFd) -> io::Result<InheritedSocket> {
+    let mut socket_type: libc::c_int = 0;
+    let mut len: libc::socklen_t = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    // SAFETY: the option value points to a `c_int` whose size is passed along with it.
+    let result: libc::c_int = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_TYPE,
+            (&mut socket_type as *mut libc::c_int).cast(),
+            &mut len,
+        )
+    };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if socket_type != libc::SOCK_STREAM {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("file descriptor {} is not a stream socket", fd),
+        ));
+    }
+    // SAFETY: the descriptor was passed to this process to listen on and nothing else
+    // owns it. It is released again without being closed if it is not a socket.
+    let listener: StdTcpListener = unsafe { StdTcpListener::from_raw_fd(fd) };
+    if listener.local_addr().is_ok() {
+        return listener.try_clone().map(InheritedSocket::Tcp);
+    }
+    let fd: RawFd = listener.into_raw_fd();
+    // SAFETY: as above, the descriptor is still owned by nothing else.
+    let listener: StdUnixListener = unsafe { StdUnixListener::from_raw_fd(fd) };
+    if let Err(err) = listener.local_addr() {
+        let _ = listener.into_raw_fd();
+        return Err(err);
+    }
+    listener.try_clone().map(InheritedSocket::Unix)
+}
+
+/// Adopts the listeners named by file descriptors.
+///
+/// # Arguments
+///
+/// - `VecRawFdOptionString` - The file descriptors and their names.
+///
+/// # Returns
+///
+/// - `VecInheritedListener` - The listeners that could be adopted, tagged with their names.
+#[cfg(unix)]
+fn adopt_listen_fds(fds: VecRawFdOptionString) -> VecInheritedListener {
+    fds.into_iter()
+        .filter_map(|(fd, tag)| {
+            adopt_listen_fd(fd)
+                .ok()
+                .map(|socket: InheritedSocket| InheritedListener {
+                    socket: Arc::new(socket),
+                    tag,
+                })
+        })
+        .collect()
+}
+
+/// Reads the listeners passed to this process through its environment.
+///
+/// These are the listeners of systemd socket activation, which are only meant for this
+/// process if `LISTEN_PID` is its id, and the ones handed over by an upgraded server.
+/// The descriptors can only be adopted once, so the environment is read on the first call.
+///
+/// # Returns
+///
+/// - `&'static InheritedEnv` - The listeners and the readiness pipe of an upgrade.
+#[cfg(unix)]
+pub(crate) fn inherited_env() -> &'static InheritedEnv {
+    static INHERITED_ENV: OnceLock<InheritedEnv> = OnceLock::new();
+    INHERITED_ENV.get_or_init(|| {
+        let var = |name: &str| -> OptionString { std::env::var(name).ok() };
+        let activated: VecInheritedListener = adopt_listen_fds(parse_listen_fds(
+            var(LISTEN_PID).as_deref(),
+            var(LISTEN_FDS).as_deref(),
+            var(LISTEN_FDNAMES).as_deref(),
+            std::process::id(),
+        ));
+        let ready: OptionStdFile = adopt_upgrade_ready(
+            var(UPGRADE_TOKEN).as_deref(),
+            var(UPGRADE_READY_FD).as_deref(),
+        );
+        let upgraded: VecInheritedListener = match ready {
+            Some(_) => adopt_listen_fds(parse_upgrade_fds(
+                var(UPGRADE_FDS).as_deref(),
+                var(UPGRADE_FDNAMES).as_deref(),
+            )),
+            None => Vec::new(),
+        };
+        InheritedEnv {
+            activated: StdMutex::new(activated),
+            upgraded,
+            ready: StdMutex::new(ready),
+        }
+    })
+}
+
+/// Claims the listeners of systemd socket activation.
+///
+/// Only the first call gets them, so they are served by a single server.
+///
+/// # Returns
+///
+/// - `VecInheritedListener` - The listeners, or nothing if they were claimed already.
+#[cfg(unix)]
+pub(crate) fn take_activated_listeners() -> VecInheritedListener {
+    inherited_env()
+        .get_activated()
+        .lock()
+        .map(|mut activated| std::mem::take(&mut *activated))
+        .unwrap_or_default()
+}
+
+/// Tells the process this one was upgraded from that the listeners are served now.
+///
+/// The upgrading process stops accepting connections once it is told. Later calls
+/// do nothing.
+#[cfg(unix)]
+pub(crate) fn notify_upgrade_ready() {
+    let ready: OptionStdFile = inherited_env()
+        .get_ready()
+        .lock()
+        .ok()
+        .and_then(|mut ready| ready.take());
+    if let Some(mut ready) = ready {
+        let _ = ready.write_all(UPGRADE_READY_SIGNAL);
+    }
+}
@@ -11,10 +11,34 @@ impl Default for ServerInner {
         Self {
             config: ServerConfig::default(),
             route: RouteMatcher::new(),
+            host_route: Vec::new(),
             request_middleware: vec![],
             response_middleware: vec![],
             pre_upgrade_hook: vec![],
             connected_hook: vec![],
+            ws_closed_hook: vec![],
+            ws_route_heartbeat: vec![],
+            ws_route_policy: vec![],
+            ws_hub: WebSocketHub::new(),
+            #[cfg(feature = "tls")]
+            tls: None,
+            #[cfg(feature = "tls")]
+            tls_resolver: None,
+            #[cfg(feature = "tls")]
+            tls_client_auth: None,
+            inherited_listeners: Vec::new(),
+            #[cfg(unix)]
+            listener_fds: Vec::new(),
+            #[cfg(unix)]
+            upgrading: false,
+            #[cfg(unix)]
+            listen_inherited: false,
+            #[cfg(unix)]
+            connections: Arc::new(AtomicUsize::new(0)),
+            #[cfg(unix)]
+            connections_drained: Arc::new(Notify::new()),
+            #[cfg(unix)]
+            upgrade_notify: Arc::new(Notify::new()),
             disable_http_hook: RouteMatcher::new(),
             disable_ws_hook: RouteMatcher::new(),
             panic_hook: Arc::new(|ctx: Context| Box::pin(default_panic_hook(ctx))),
@@ -31,13 +55,13 @@ impl<'a> HandlerState<'a> {
     ///
     /// # Arguments
     ///
-    /// - `&'a ArcRwLockStream` - The network stream.
+    /// - `&'a ConnectionStream` - The network stream.
     /// - `&'a Context` - The request context.
     ///
     /// # Returns
     ///
     /// - `HandlerState` - The newly created handler state.
-    pub(super) fn new(stream: &'a ArcRwLockStream, ctx: &'a Context) -> Self {
+    pub(super) fn new(stream: &'a ConnectionStream, ctx: &'a Context) -> Self {
         Self { stream, ctx }
     }
 }
@@ -57,6 +81,29 @@ impl Server {
         Self(arc_rwlock(server))
     }
 
+    /// Creates a new server that accepts connections on a pre-opened listener.
+    ///
+    /// The host and port are not bound unless other listeners are configured as well.
+    /// Listeners passed through systemd socket activation are adopted automatically.
+    ///
+    /// # Arguments
+    ///
+    /// - `StdTcpListener` - The listener, such as one inherited from a parent process.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - A new `Server` instance.
+    pub fn from_listener(listener: StdTcpListener) -> Self {
+        let mut server: ServerInner = ServerInner::default();
+        server
+            .get_mut_inherited_listeners()
+            .push(InheritedListener {
+                socket: Arc::new(InheritedSocket::Tcp(listener)),
+                tag: None,
+            });
+        Self(arc_rwlock(server))
+    }
+
     /// Acquires a read lock on the inner server data.
     ///
     /// # Returns
@@ -66,6 +113,15 @@ impl Server {
         self.get_0().read().await
     }
 
+    /// Retrieves a snapshot of the server configuration.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerConfig` - A clone of the current configuration.
+    pub(crate) async fn get_config(&self) -> ServerConfig {
+        self.get_read().await.get_config().clone()
+    }
+
     /// Acquires a write lock on the inner server data.
     ///
     /// # Returns
@@ -106,6 +162,81 @@ impl Server {
         self
     }
 
+    /// Adds an address to listen on.
+    ///
+    /// Once an address is added, the host and port are no longer bound, so every
+    /// address to serve must be added, such as both an IPv4 and an IPv6 address.
+    ///
+    /// # Arguments
+    ///
+    /// - `A` - The address to bind, such as `0.0.0.0:80` or `[::]:80`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn listen<A: ToString>(&self, addr: A) -> &Self {
+        self.add_listen_addr(addr.to_string(), None).await
+    }
+
+    /// Adds a tagged address to listen on.
+    ///
+    /// Connections accepted on the address carry the tag, which `Context::get_listener_tag`
+    /// returns, so middleware can for example restrict admin routes to an admin port.
+    ///
+    /// # Arguments
+    ///
+    /// - `A` - The address to bind, such as `127.0.0.1:9000`.
+    /// - `T` - The tag of the listener, such as `admin`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn listen_tagged<A: ToString, T: ToString>(&self, addr: A, tag: T) -> &Self {
+        self.add_listen_addr(addr.to_string(), Some(tag.to_string()))
+            .await
+    }
+
+    /// Serves the listeners handed over by a server upgraded with `upgrade_with`.
+    ///
+    /// A configured address or socket file that matches one of them reuses it, and the
+    /// rest are served with the names passed along with them as tags. A process started
+    /// by `upgrade_with` must call this, as the upgrading server keeps serving until a
+    /// server of the new process runs with the handed over listeners.
+    ///
+    /// Listeners of systemd socket activation are adopted without calling this.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn listen_inherited(&self) -> &Self {
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        server
+            .get_mut_inherited_listeners()
+            .extend(inherited_env().get_upgraded().iter().cloned());
+        server.set_listen_inherited(true);
+        self
+    }
+
+    /// Appends an address to the listeners of the server's configuration.
+    ///
+    /// # Arguments
+    ///
+    /// - `String` - The address to bind.
+    /// - `OptionString` - The tag of the listener.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    async fn add_listen_addr(&self, addr: String, tag: OptionString) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .get_mut_listen()
+            .push(ListenAddr { addr, tag });
+        self
+    }
+
     /// Sets the read buffer size for HTTP connections.
     ///
     /// # Arguments
@@ -128,6 +259,33 @@ impl Server {
         self
     }
 
+    /// Sets the size limit of a request body.
+    ///
+    /// Requests announcing or sending a larger body are answered with
+    /// `413 Content Too Large`, on HTTP/1.1 and HTTP/2 alike.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The size limit in bytes.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size limit is 0.
+    pub async fn http_max_body_size(&self, size: usize) -> &Self {
+        if size == 0 {
+            panic!("the request body size limit must be greater than 0");
+        }
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_http_max_body_size(size);
+        self
+    }
+
     /// Sets the read buffer size for WebSocket connections.
     ///
     /// # Arguments
@@ -150,300 +308,1603 @@ impl Server {
         self
     }
 
-    /// Sets a custom panic hook for request processing.
+    /// Sets how often a keep-alive comment is sent on an idle event stream.
+    ///
+    /// Comments are only sent when no event was written during the interval, so that
+    /// proxies do not time out quiet streams.
     ///
     /// # Arguments
     ///
-    /// - `F` - The panic handler function implementing `ErrorHandler<Fut>`.
-    /// - `Fut` - The future type implementing `FutureSendStatic<()>`.
+    /// - `Duration` - The keep-alive interval, zero to disable keep-alive comments.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn panic_hook<F, Fut>(&self, func: F) -> &Self
-    where
-        F: ErrorHandler<Fut>,
-        Fut: FutureSendStatic<()>,
-    {
+    pub async fn sse_keep_alive(&self, interval: Duration) -> &Self {
         self.get_write()
             .await
-            .set_panic_hook(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+            .get_mut_config()
+            .set_sse_keep_alive(interval);
         self
     }
 
-    /// Sets the TCP_NODELAY socket option.
+    /// Sets the size limit of a reassembled WebSocket message.
+    ///
+    /// Connections receiving a larger message, or a frame announcing one, are closed
+    /// with `WS_CLOSE_MESSAGE_TOO_BIG`. The limit also applies after decompression.
     ///
     /// # Arguments
     ///
-    /// - `bool` - Whether to enable TCP_NODELAY.
+    /// - `usize` - The size limit in bytes.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn set_nodelay(&self, nodelay: bool) -> &Self {
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size limit is 0.
+    pub async fn ws_max_message_size(&self, size: usize) -> &Self {
+        if size == 0 {
+            panic!("the WebSocket message size limit must be greater than 0");
+        }
         self.get_write()
             .await
             .get_mut_config()
-            .set_nodelay(Some(nodelay));
+            .set_ws_max_message_size(size);
         self
     }
 
-    /// Enables the TCP_NODELAY socket option.
+    /// Sets how long `Context::ws_close` waits for the peer's close frame.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// - `&Self` - Reference to self for method chaining.
-    pub async fn enable_nodelay(&self) -> &Self {
-        self.set_nodelay(true).await
-    }
-
-    /// Disables the TCP_NODELAY socket option.
+    /// - `Duration` - The close handshake timeout.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn disable_nodelay(&self) -> &Self {
-        self.set_nodelay(false).await
+    pub async fn ws_close_timeout(&self, timeout: Duration) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_ws_close_timeout(timeout);
+        self
     }
 
-    /// Sets the SO_LINGER socket option.
+    /// Sets the `permessage-deflate` compression settings of WebSocket connections.
     ///
     /// # Arguments
     ///
-    /// - `OptionDuration` - The linger duration.
+    /// - `OptionWebSocketDeflate` - The compression settings, or None to disable compression.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn set_linger(&self, linger: OptionDuration) -> &Self {
-        self.get_write().await.get_mut_config().set_linger(linger);
+    pub async fn ws_deflate(&self, deflate: OptionWebSocketDeflate) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_ws_deflate(deflate);
         self
     }
 
-    /// Enables the SO_LINGER socket option.
+    /// Enables `permessage-deflate` compression for WebSocket connections.
+    ///
+    /// The extension is negotiated during the upgrade handshake. Incoming compressed
+    /// messages are inflated before handlers see them, and messages sent with
+    /// `Context::send_body` are deflated.
     ///
     /// # Arguments
     ///
-    /// - `Duration` - The linger duration.
+    /// - `WebSocketDeflate` - The compression settings.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn enable_linger(&self, linger: Duration) -> &Self {
-        self.set_linger(Some(linger)).await
+    pub async fn enable_ws_deflate(&self, deflate: WebSocketDeflate) -> &Self {
+        self.ws_deflate(Some(deflate)).await
     }
 
-    /// Disables the SO_LINGER socket option.
+    /// Disables `permessage-deflate` compression for WebSocket connections.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn disable_linger(&self) -> &Self {
-        self.set_linger(None).await
+    pub async fn disable_ws_deflate(&self) -> &Self {
+        self.ws_deflate(None).await
     }
 
-    /// Sets the IP_TTL socket option.
+    /// Sets the heartbeat applied to WebSocket connections.
+    ///
+    /// The server pings a connection that stayed quiet for the ping interval and closes
+    /// it when the pong does not arrive in time or no frame was received for the idle
+    /// timeout. Routes configured with `ws_route_heartbeat` use their own setting instead.
     ///
     /// # Arguments
     ///
-    /// - `u32` - The TTL value.
+    /// - `WebSocketHeartbeat` - The heartbeat settings.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn set_ttl(&self, ttl: u32) -> &Self {
-        self.get_write().await.get_mut_config().set_ttl(Some(ttl));
+    pub async fn ws_heartbeat(&self, heartbeat: WebSocketHeartbeat) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_ws_heartbeat(heartbeat);
         self
     }
 
-    /// Adds a route handler for a specific path.
+    /// Sets the heartbeat applied to WebSocket connections on a specific route.
     ///
     /// # Arguments
     ///
     /// - `R` - The route path pattern implementing `ToString`.
-    /// - `F` - The handler function implementing `FnSendSyncStatic<Fut>`.
-    /// - `Fut` - The future type.
+    /// - `WebSocketHeartbeat` - The heartbeat settings.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn route<R, F, Fut>(&self, route: R, func: F) -> &Self
-    where
-        R: ToString,
-        F: FnSendSyncStatic<Fut>,
-        Fut: FutureSendStatic<()>,
-    {
-        let route_str: String = route.to_string();
-        self.get_write()
-            .await
-            .get_mut_route()
-            .add(
-                &route_str,
-                Arc::new(move |ctx: Context| Box::pin(func(ctx))),
-            )
-            .unwrap_or_else(|err| panic!("{}", err));
+    ///
+    /// # Panics
+    ///
+    /// Panics if the route pattern is invalid.
+    pub async fn ws_route_heartbeat<R: ToString>(
+        &self,
+        route: R,
+        heartbeat: WebSocketHeartbeat,
+    ) -> &Self {
+        let route_pattern: RoutePattern =
+            RoutePattern::new(&route.to_string()).unwrap_or_else(|err| panic!("{}", err));
+        upsert_route_setting(
+            self.get_write().await.get_mut_ws_route_heartbeat(),
+            route_pattern,
+            heartbeat,
+        );
         self
     }
 
-    /// Adds request middleware to the processing pipeline.
+    /// Resolves the heartbeat settings for a WebSocket route.
     ///
     /// # Arguments
     ///
-    /// - `F` - The middleware function implementing `FnSendSyncStatic<Fut>`.
-    /// - `Fut` - The future type.
+    /// - `&str` - The normalized path the route was matched against.
     ///
     /// # Returns
     ///
-    /// - `&Self` - Reference to self for method chaining.
-    pub async fn request_middleware<F, Fut>(&self, func: F) -> &Self
-    where
-        F: FnSendSyncStatic<Fut>,
-        Fut: FutureSendStatic<()>,
-    {
-        self.get_write()
-            .await
-            .get_mut_request_middleware()
-            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
-        self
+    /// - `WebSocketHeartbeat` - The route-specific setting, or the server-level one.
+    async fn get_ws_heartbeat(&self, route: &str) -> WebSocketHeartbeat {
+        let server: RwLockReadGuardServerInner = self.get_read().await;
+        let case_sensitive: bool = *server.get_config().get_case_sensitive();
+        let empty_segments: bool = server.get_config().get_trailing_slash().is_some();
+        find_route_setting(
+            server.get_ws_route_heartbeat(),
+            route,
+            case_sensitive,
+            empty_segments,
+        )
+        .copied()
+        .unwrap_or(*server.get_config().get_ws_heartbeat())
     }
 
-    /// Adds response middleware to the processing pipeline.
+    /// Retrieves the broadcast hub shared by the server's WebSocket connections.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketHub` - The hub of the server.
+    pub async fn get_ws_hub(&self) -> WebSocketHub {
+        self.get_read().await.get_ws_hub().clone()
+    }
+
+    /// Sets how many broadcast messages are queued per WebSocket connection.
+    ///
+    /// When a connection's queue is full, further broadcasts skip it until it catches up.
     ///
     /// # Arguments
     ///
-    /// - `F` - The middleware function implementing `FnSendSyncStatic<Fut>`.
-    /// - `Fut` - The future type.
+    /// - `usize` - The queue capacity, 0 restores the default.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn response_middleware<F, Fut>(&self, func: F) -> &Self
-    where
-        F: FnSendSyncStatic<Fut>,
-        Fut: FutureSendStatic<()>,
-    {
+    pub async fn ws_queue_capacity(&self, capacity: usize) -> &Self {
+        let capacity: usize = if capacity == 0 {
+            DEFAULT_WS_QUEUE_CAPACITY
+        } else {
+            capacity
+        };
         self.get_write()
             .await
-            .get_mut_response_middleware()
-            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+            .get_mut_config()
+            .set_ws_queue_capacity(capacity);
         self
     }
 
-    /// Adds a hook executed before WebSocket connection upgrade.
+    /// Sets the upgrade policy applied to WebSocket handshakes.
+    ///
+    /// Routes configured with `ws_route_policy` inherit the settings their own policy
+    /// leaves empty.
     ///
     /// # Arguments
     ///
-    /// - `F` - The hook function implementing `FnSendSyncStatic<Fut>`.
-    /// - `Fut` - The future type.
+    /// - `WebSocketPolicy` - The upgrade policy.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn pre_upgrade_hook<F, Fut>(&self, func: F) -> &Self
-    where
-        F: FnSendSyncStatic<Fut>,
-        Fut: FutureSendStatic<()>,
-    {
+    pub async fn ws_policy(&self, policy: WebSocketPolicy) -> &Self {
         self.get_write()
             .await
-            .get_mut_pre_upgrade_hook()
-            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+            .get_mut_config()
+            .set_ws_policy(policy);
         self
     }
 
-    /// Adds a hook executed after new client connection is established.
+    /// Sets the upgrade policy applied to WebSocket handshakes on a specific route.
+    ///
+    /// A handshake whose `Origin` is not allowed, or that does not offer any supported
+    /// subprotocol while one is required, is rejected with 403 before the 101 response.
+    /// Subprotocols and allowed origins left empty are inherited from `ws_policy`.
     ///
     /// # Arguments
     ///
-    /// - `F` - The hook function implementing `FnSendSyncStatic<Fut>`.
-    /// - `Fut` - The future type.
+    /// - `R` - The route path pattern implementing `ToString`.
+    /// - `WebSocketPolicy` - The upgrade policy.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn connected_hook<F, Fut>(&self, func: F) -> &Self
-    where
-        F: FnSendSyncStatic<Fut>,
-        Fut: FutureSendStatic<()>,
-    {
-        self.get_write()
-            .await
-            .get_mut_connected_hook()
-            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+    ///
+    /// # Panics
+    ///
+    /// Panics if the route pattern is invalid.
+    pub async fn ws_route_policy<R: ToString>(&self, route: R, policy: WebSocketPolicy) -> &Self {
+        let route_pattern: RoutePattern =
+            RoutePattern::new(&route.to_string()).unwrap_or_else(|err| panic!("{}", err));
+        upsert_route_setting(
+            self.get_write().await.get_mut_ws_route_policy(),
+            route_pattern,
+            policy,
+        );
         self
     }
 
-    /// Re-enables default HTTP handling for a route.
+    /// Resolves the upgrade policy for a WebSocket route.
     ///
     /// # Arguments
     ///
-    /// - `R` - The route path implementing `ToString`.
+    /// - `&str` - The normalized path the route was matched against.
     ///
     /// # Returns
     ///
-    /// - `&Self` - Reference to self for method chaining.
-    pub async fn enable_http_hook<R: ToString>(&self, route: R) -> &Self {
-        let route_string: String = route.to_string();
-        self.get_write()
-            .await
-            .get_mut_disable_http_hook()
-            .remove(&route_string);
-        self
+    /// - `WebSocketPolicy` - The route-specific policy merged over the server-level one.
+    async fn get_ws_policy(&self, route: &str) -> WebSocketPolicy {
+        let server: RwLockReadGuardServerInner = self.get_read().await;
+        let case_sensitive: bool = *server.get_config().get_case_sensitive();
+        let empty_segments: bool = server.get_config().get_trailing_slash().is_some();
+        let policy: &WebSocketPolicy = server.get_config().get_ws_policy();
+        match find_route_setting(
+            server.get_ws_route_policy(),
+            route,
+            case_sensitive,
+            empty_segments,
+        ) {
+            Some(route_policy) => route_policy.merge(policy),
+            None => policy.clone(),
+        }
     }
 
-    /// Disables default HTTP handling for a route.
+    /// Serves every connection over TLS with the given certificate.
+    ///
+    /// HTTP/1.1, and HTTP/2 once enabled, are advertised through ALPN. The negotiated parameters of a connection
+    /// are available through `Context::get_tls_info`. The certificate becomes the default
+    /// certificate of a new `TlsCertResolver`, which can be extended with certificates for
+    /// other server names through `Server::get_tls_resolver`.
     ///
     /// # Arguments
     ///
-    /// - `R` - The route path implementing `ToString`.
+    /// - `VecCertificateDer` - The certificate chain, leaf certificate first.
+    /// - `PrivateKeyDer<'static>` - The private key of the leaf certificate.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn disable_http_hook<R: ToString>(&self, route: R) -> &Self {
-        let route_string: String = route.to_string();
-        let _ = self
-            .get_write()
-            .await
-            .get_mut_disable_http_hook()
-            .add(&route_string, Arc::new(|_: Context| Box::pin(async {})));
-        self
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chain is empty or the key does not match the certificate.
+    #[cfg(feature = "tls")]
+    pub async fn tls(&self, cert_chain: VecCertificateDer, key: PrivateKeyDer<'static>) -> &Self {
+        let certificate: TlsCertificate =
+            TlsCertificate::from_der(cert_chain, key).unwrap_or_else(|err| panic!("{}", err));
+        let resolver: TlsCertResolver = TlsCertResolver::new();
+        resolver.set_default(certificate);
+        self.tls_resolver(resolver).await
     }
 
-    /// Re-enables default WebSocket handling for a route.
+    /// Serves every connection over TLS with certificates picked by a resolver.
     ///
     /// # Arguments
     ///
-    /// - `R` - The route path implementing `ToString`.
+    /// - `TlsCertResolver` - The resolver that selects the certificate from the server name.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn enable_ws_hook<R: ToString>(&self, route: R) -> &Self {
-        let route_string: String = route.to_string();
-        self.get_write()
-            .await
-            .get_mut_disable_ws_hook()
-            .remove(&route_string);
+    #[cfg(feature = "tls")]
+    pub async fn tls_resolver(&self, resolver: TlsCertResolver) -> &Self {
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        server.set_tls_resolver(Some(resolver));
+        Self::refresh_tls_acceptor(&mut server);
+        drop(server);
         self
     }
 
-    /// Disables default WebSocket handling for a route.
+    /// Verifies client certificates against a CA bundle during TLS handshakes.
+    ///
+    /// This takes effect for handshakes that start afterwards, whether it is called
+    /// before or after TLS is enabled. The verified certificate of a client is
+    /// available through `Context::get_tls_info`.
     ///
     /// # Arguments
     ///
-    /// - `R` - The route path implementing `ToString`.
+    /// - `TlsClientAuth` - The client certificate verification.
     ///
     /// # Returns
     ///
     /// - `&Self` - Reference to self for method chaining.
-    pub async fn disable_ws_hook<R: ToString>(&self, route: R) -> &Self {
-        let route_string: String = route.to_string();
-        let _ = self
-            .get_write()
-            .await
-            .get_mut_disable_ws_hook()
-            .add(&route_string, Arc::new(|_: Context| Box::pin(async {})));
+    #[cfg(feature = "tls")]
+    pub async fn tls_client_auth(&self, client_auth: TlsClientAuth) -> &Self {
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        server.set_tls_client_auth(Some(client_auth));
+        Self::refresh_tls_acceptor(&mut server);
+        drop(server);
+        self
+    }
+
+    /// Sets the longest time a client may take to complete the TLS handshake.
+    ///
+    /// Connections that do not finish the handshake in time are closed, so clients
+    /// that connect and stay silent do not hold on to a connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The handshake timeout.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timeout is zero.
+    #[cfg(feature = "tls")]
+    pub async fn tls_handshake_timeout(&self, timeout: Duration) -> &Self {
+        if timeout.is_zero() {
+            panic!("the TLS handshake timeout must be greater than 0");
+        }
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_tls_handshake_timeout(timeout);
+        self
+    }
+
+    /// Serves HTTP/2 next to HTTP/1.1 with the given settings.
+    ///
+    /// Plain connections speak HTTP/2 when they start with the connection preface or
+    /// upgrade with `Upgrade: h2c`, and TLS connections when the client selects `h2`
+    /// through ALPN. Every stream gets its own `Context` and runs through the same
+    /// middleware and routes as an HTTP/1.1 request.
+    ///
+    /// # Arguments
+    ///
+    /// - `Http2Settings` - The stream limit and flow-control windows.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(feature = "http2")]
+    pub async fn http2(&self, settings: Http2Settings) -> &Self {
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        server.get_mut_config().set_http2(Some(settings));
+        #[cfg(feature = "tls")]
+        Self::refresh_tls_acceptor(&mut server);
+        drop(server);
+        self
+    }
+
+    /// Rebuilds the TLS acceptor from the certificate resolver and client verification.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut ServerInner` - The server state holding the TLS settings.
+    #[cfg(feature = "tls")]
+    fn refresh_tls_acceptor(server: &mut ServerInner) {
+        let acceptor: OptionTlsAcceptor =
+            server
+                .get_tls_resolver()
+                .as_ref()
+                .map(|resolver: &TlsCertResolver| {
+                    create_tls_acceptor(
+                        resolver,
+                        server.get_tls_client_auth().as_ref(),
+                        Self::alpn_protocols(server),
+                    )
+                });
+        server.set_tls(acceptor);
+    }
+
+    /// Lists the protocols to offer through ALPN, most preferred first.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ServerInner` - The server state holding the protocol settings.
+    ///
+    /// # Returns
+    ///
+    /// - `VecVecU8` - `h2` if HTTP/2 is enabled, followed by `http/1.1`.
+    #[cfg(feature = "tls")]
+    #[cfg_attr(not(feature = "http2"), allow(unused_variables))]
+    fn alpn_protocols(server: &ServerInner) -> VecVecU8 {
+        let mut protocols: VecVecU8 = Vec::new();
+        #[cfg(feature = "http2")]
+        if server.get_config().get_http2().is_some() {
+            protocols.push(ALPN_H2.to_vec());
+        }
+        protocols.push(ALPN_HTTP1_1.to_vec());
+        protocols
+    }
+
+    /// Retrieves the certificate resolver of the server.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionTlsCertResolver` - The resolver, or None if TLS is not enabled.
+    #[cfg(feature = "tls")]
+    pub async fn get_tls_resolver(&self) -> OptionTlsCertResolver {
+        self.get_read().await.get_tls_resolver().clone()
+    }
+
+    /// Replaces the default certificate without restarting the server.
+    ///
+    /// Established connections keep their session, new handshakes use the new certificate.
+    ///
+    /// # Arguments
+    ///
+    /// - `VecCertificateDer` - The certificate chain, leaf certificate first.
+    /// - `PrivateKeyDer<'static>` - The private key of the leaf certificate.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultTlsError` - Ok if the certificate was swapped, or a `TlsError` on failure.
+    #[cfg(feature = "tls")]
+    pub async fn reload_tls(
+        &self,
+        cert_chain: VecCertificateDer,
+        key: PrivateKeyDer<'static>,
+    ) -> ResultTlsError {
+        let resolver: TlsCertResolver =
+            self.get_tls_resolver().await.ok_or(TlsError::NotEnabled)?;
+        resolver.set_default(TlsCertificate::from_der(cert_chain, key)?);
+        Ok(())
+    }
+
+    /// Sets a custom panic hook for request processing.
+    ///
+    /// # Arguments
+    ///
+    /// - `F` - The panic handler function implementing `ErrorHandler<Fut>`.
+    /// - `Fut` - The future type implementing `FutureSendStatic<()>`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn panic_hook<F, Fut>(&self, func: F) -> &Self
+    where
+        F: ErrorHandler<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        self.get_write()
+            .await
+            .set_panic_hook(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    /// Sets the TCP_NODELAY socket option.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether to enable TCP_NODELAY.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn set_nodelay(&self, nodelay: bool) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_nodelay(Some(nodelay));
+        self
+    }
+
+    /// Enables the TCP_NODELAY socket option.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_nodelay(&self) -> &Self {
+        self.set_nodelay(true).await
+    }
+
+    /// Disables the TCP_NODELAY socket option.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_nodelay(&self) -> &Self {
+        self.set_nodelay(false).await
+    }
+
+    /// Sets the SO_LINGER socket option.
+    ///
+    /// # Arguments
+    ///
+    /// - `OptionDuration` - The linger duration.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn set_linger(&self, linger: OptionDuration) -> &Self {
+        self.get_write().await.get_mut_config().set_linger(linger);
+        self
+    }
+
+    /// Enables the SO_LINGER socket option.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The linger duration.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_linger(&self, linger: Duration) -> &Self {
+        self.set_linger(Some(linger)).await
+    }
+
+    /// Disables the SO_LINGER socket option.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_linger(&self) -> &Self {
+        self.set_linger(None).await
+    }
+
+    /// Sets the IP_TTL socket option.
+    ///
+    /// # Arguments
+    ///
+    /// - `u32` - The TTL value.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn set_ttl(&self, ttl: u32) -> &Self {
+        self.get_write().await.get_mut_config().set_ttl(Some(ttl));
+        self
+    }
+
+    /// Sets whether connections start with a PROXY protocol header.
+    ///
+    /// The client address of the header replaces the peer address reported by
+    /// `Context::get_socket_addr`, and the whole header is available through
+    /// `Context::get_proxy_header`. Only require it behind a load balancer that
+    /// sends the header, as clients could otherwise claim any address. An optional
+    /// header is only read from the sources added with `proxy_protocol_source`.
+    ///
+    /// # Arguments
+    ///
+    /// - `ProxyProtocol` - Whether the header is disabled, optional or required.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn proxy_protocol(&self, proxy_protocol: ProxyProtocol) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_proxy_protocol(proxy_protocol);
+        self
+    }
+
+    /// Reads an optional PROXY protocol header from connections in an address block.
+    ///
+    /// With `ProxyProtocol::Optional`, only connections from these sources may start
+    /// with a header; connections from elsewhere are served as they are, so a client
+    /// cannot claim another address. Connections on a Unix domain socket have no
+    /// address and never match.
+    ///
+    /// # Arguments
+    ///
+    /// - `C` - An address or CIDR block such as `10.0.0.0/8`, implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address or CIDR block is invalid.
+    pub async fn proxy_protocol_source<C: ToString>(&self, cidr: C) -> &Self {
+        let cidr: IpCidr = cidr
+            .to_string()
+            .parse()
+            .unwrap_or_else(|err| panic!("{}", err));
+        self.get_write()
+            .await
+            .get_mut_config()
+            .get_mut_proxy_protocol_sources()
+            .push(cidr);
+        self
+    }
+
+    /// Trusts the forwarding headers sent by proxies in an address block.
+    ///
+    /// Only the header set of the proxy the connection comes from is read, so a client
+    /// cannot add the other one. `Context::get_client_ip` walks it from the right for as
+    /// long as the hops are trusted proxies setting the same header. Connections on a
+    /// Unix domain socket have no address and are never trusted.
+    ///
+    /// # Arguments
+    ///
+    /// - `C` - An address or CIDR block such as `10.0.0.0/8`, implementing `ToString`.
+    /// - `ForwardedHeaders` - The forwarding headers the proxies set.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address or CIDR block is invalid.
+    pub async fn trusted_proxy<C: ToString>(&self, cidr: C, headers: ForwardedHeaders) -> &Self {
+        let cidr: IpCidr = cidr
+            .to_string()
+            .parse()
+            .unwrap_or_else(|err| panic!("{}", err));
+        self.get_write()
+            .await
+            .get_mut_config()
+            .get_mut_trusted_proxies()
+            .push(TrustedProxy::new(cidr, headers));
+        self
+    }
+
+    /// Listens on a Unix domain socket instead of the TCP host and port.
+    ///
+    /// The peer credentials of every connection are available through
+    /// `Context::get_unix_peer`. The socket file is removed when the server stops,
+    /// unless it was handed over to a new process by an upgrade.
+    ///
+    /// # Arguments
+    ///
+    /// - `P` - The path of the socket file.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn unix_socket<P: Into<PathBuf>>(&self, path: P) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_unix_socket(Some(path.into()));
+        self
+    }
+
+    /// Sets the file mode of the Unix domain socket file, such as `0o660`.
+    ///
+    /// # Arguments
+    ///
+    /// - `u32` - The file mode.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn unix_socket_mode(&self, mode: u32) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_unix_socket_mode(Some(mode));
+        self
+    }
+
+    /// Sets whether a socket file left behind by a stopped server is removed before binding.
+    ///
+    /// A socket file is only considered stale if connecting to it is refused.
+    /// Enabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether to remove a stale socket file.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn unix_socket_remove_stale(&self, remove_stale: bool) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_unix_socket_remove_stale(remove_stale);
+        self
+    }
+
+    /// Upgrades the server when the process receives `SIGUSR2`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn enable_upgrade_signal(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_upgrade_signal(true);
+        self
+    }
+
+    /// Ignores `SIGUSR2`, which is the default.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn disable_upgrade_signal(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_upgrade_signal(false);
+        self
+    }
+
+    /// Sets how long an upgraded server waits for in-flight connections before `run` returns.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The drain timeout.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    #[cfg(unix)]
+    pub async fn upgrade_drain_timeout(&self, timeout: Duration) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_upgrade_drain_timeout(timeout);
+        self
+    }
+
+    /// Hands the listeners over to a new process running the current executable with the
+    /// same arguments.
+    ///
+    /// See `upgrade_with` for how the handover works.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<u32>` - The process id of the new process, or the error that
+    ///   prevented starting it.
+    #[cfg(unix)]
+    pub async fn upgrade(&self) -> ServerResult<u32> {
+        let exe: PathBuf =
+            std::env::current_exe().map_err(|err| ServerError::Upgrade(err.to_string()))?;
+        let exe: PathBuf = exe
+            .to_str()
+            .and_then(|path| path.strip_suffix(DELETED_EXE_SUFFIX))
+            .map(PathBuf::from)
+            .unwrap_or(exe);
+        let mut command: Command = Command::new(exe);
+        command.args(std::env::args_os().skip(1));
+        self.upgrade_with(command).await
+    }
+
+    /// Hands the listeners over to a new process started from a command.
+    ///
+    /// The listening sockets are passed to the new process, which adopts them with
+    /// `listen_inherited` and starts serving right away. Once it serves them, this server
+    /// stops accepting connections and `run` returns after the in-flight connections
+    /// finished or the drain timeout elapsed. If the new process exits or does not serve
+    /// the listeners within `UPGRADE_READY_TIMEOUT`, it is killed and this server keeps
+    /// serving.
+    ///
+    /// # Arguments
+    ///
+    /// - `Command` - The command starting the new process.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<u32>` - The process id of the new process, or the error that
+    ///   prevented it from taking over.
+    #[cfg(unix)]
+    pub async fn upgrade_with(&self, command: Command) -> ServerResult<u32> {
+        let listener_fds: VecRawFdOptionString = {
+            let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+            if server.get_listener_fds().is_empty() {
+                return Err(ServerError::Upgrade(
+                    "the server is not running".to_string(),
+                ));
+            }
+            if std::mem::replace(server.get_mut_upgrading(), true) {
+                return Err(ServerError::Upgrade(
+                    "an upgrade is already in progress".to_string(),
+                ));
+            }
+            server.get_listener_fds().clone()
+        };
+        let result: ServerResult<u32> = Self::spawn_upgrade(command, &listener_fds).await;
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        server.set_upgrading(false);
+        if result.is_ok() {
+            server.get_mut_listener_fds().clear();
+            server.get_upgrade_notify().notify_one();
+        }
+        result
+    }
+
+    /// Starts the process of an upgrade and waits until it serves the listeners.
+    ///
+    /// The new process receives the listeners and the write end of a readiness pipe,
+    /// along with a token identifying the pipe. It is killed if it closes the pipe
+    /// without writing to it or the ready timeout elapses.
+    ///
+    /// # Arguments
+    ///
+    /// - `Command` - The command starting the new process.
+    /// - `&[(RawFd, OptionString)]` - The file descriptors and tags of the listeners.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<u32>` - The process id of the new process, or the error that
+    ///   prevented it from taking over.
+    #[cfg(unix)]
+    async fn spawn_upgrade(
+        mut command: Command,
+        listener_fds: &[(RawFd, OptionString)],
+    ) -> ServerResult<u32> {
+        let upgrade_error = |err: io::Error| ServerError::Upgrade(err.to_string());
+        let (sender, mut receiver) = unix_pipe().map_err(upgrade_error)?;
+        let ready: StdFile = StdFile::from(sender.into_blocking_fd().map_err(upgrade_error)?);
+        let ready_fd: RawFd = ready.as_raw_fd();
+        let mut fds: VecRawFd = listener_fds.iter().map(|(fd, _)| *fd).collect();
+        let join_fds: String = fds
+            .iter()
+            .map(RawFd::to_string)
+            .collect::<Vec<String>>()
+            .join(&LISTEN_FDNAMES_SEPARATOR.to_string());
+        let join_names: String = listener_fds
+            .iter()
+            .map(|(_, tag)| tag.clone().unwrap_or_default())
+            .collect::<Vec<String>>()
+            .join(&LISTEN_FDNAMES_SEPARATOR.to_string());
+        command
+            .env(UPGRADE_TOKEN, upgrade_token(&ready).map_err(upgrade_error)?)
+            .env(UPGRADE_READY_FD, ready_fd.to_string())
+            .env(UPGRADE_FDS, join_fds)
+            .env(UPGRADE_FDNAMES, join_names);
+        fds.push(ready_fd);
+        // SAFETY: only the async-signal-safe `fcntl` runs between fork and exec.
+        unsafe {
+            command.pre_exec(move || {
+                for fd in fds.iter() {
+                    if libc::fcntl(*fd, libc::F_SETFD, 0) == -1 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+        let mut child: Child = command.spawn().map_err(upgrade_error)?;
+        drop(ready);
+        let mut signal: Vec<u8> = vec![0; UPGRADE_READY_SIGNAL.len()];
+        let ready: bool =
+            tokio::time::timeout(UPGRADE_READY_TIMEOUT, receiver.read_exact(&mut signal))
+                .await
+                .is_ok_and(|result: io::Result<usize>| result.is_ok())
+                && signal == UPGRADE_READY_SIGNAL;
+        if ready {
+            return Ok(child.id());
+        }
+        let _ = child.kill();
+        let _ = tokio::task::spawn_blocking(move || child.wait()).await;
+        Err(ServerError::Upgrade(
+            "the new process did not serve the listeners".to_string(),
+        ))
+    }
+
+    /// Sets the policy applied to a trailing slash in the request path.
+    ///
+    /// Without a policy, empty path segments are skipped when matching dynamic and regex
+    /// routes, so `/users/1/`, `/users//1` and `//users/1` all match `/users/{id}`.
+    /// Once a policy is set, every segment of the normalized path is significant.
+    ///
+    /// # Arguments
+    ///
+    /// - `TrailingSlash` - The trailing slash policy.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn trailing_slash(&self, trailing_slash: TrailingSlash) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_trailing_slash(Some(trailing_slash));
+        self
+    }
+
+    /// Enables the collapsing of consecutive slashes in the request path before matching.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_merge_slashes(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_merge_slashes(true);
+        self
+    }
+
+    /// Disables the collapsing of consecutive slashes in the request path before matching.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_merge_slashes(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_merge_slashes(false);
+        self
+    }
+
+    /// Enables the removal of `.` and `..` segments from the request path before matching.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_remove_dot_segments(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_remove_dot_segments(true);
+        self
+    }
+
+    /// Disables the removal of `.` and `..` segments from the request path before matching.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_remove_dot_segments(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_remove_dot_segments(false);
+        self
+    }
+
+    /// Enables the percent-decoding of captured route parameters.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_decode_route_params(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_decode_route_params(true);
+        self
+    }
+
+    /// Disables the percent-decoding of captured route parameters.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_decode_route_params(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_decode_route_params(false);
+        self
+    }
+
+    /// Enables the case-sensitive matching of static route segments.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_case_sensitive(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_case_sensitive(true);
+        self
+    }
+
+    /// Disables the case-sensitive matching of static route segments.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_case_sensitive(&self) -> &Self {
+        self.get_write()
+            .await
+            .get_mut_config()
+            .set_case_sensitive(false);
+        self
+    }
+
+    /// Adds a route handler for a specific path.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The route path pattern implementing `ToString`.
+    /// - `F` - The handler function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn route<R, F, Fut>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        let route_str: String = route.to_string();
+        self.get_write()
+            .await
+            .get_mut_route()
+            .add(
+                &route_str,
+                Arc::new(move |ctx: Context| Box::pin(func(ctx))),
+            )
+            .unwrap_or_else(|err| panic!("{}", err));
+        self
+    }
+
+    /// Adds a route handler for a specific path that is only served for matching hosts.
+    ///
+    /// Routes registered with the same host pattern form a group. The host pattern can be
+    /// an exact host, a wildcard subdomain such as `*.example.com`, or contain captured
+    /// labels such as `{tenant}.example.com`, whose values are added to the route parameters.
+    ///
+    /// # Arguments
+    ///
+    /// - `H` - The host pattern implementing `ToString`.
+    /// - `R` - The route path pattern implementing `ToString`.
+    /// - `F` - The handler function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn host_route<H, R, F, Fut>(&self, host: H, route: R, func: F) -> &Self
+    where
+        H: ToString,
+        R: ToString,
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        let host_pattern: HostPattern =
+            HostPattern::new(&host.to_string()).unwrap_or_else(|err| panic!("{}", err));
+        let route_str: String = route.to_string();
+        let handler: ArcFnPinBoxSendSync = Arc::new(move |ctx: Context| Box::pin(func(ctx)));
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        Self::host_route_matcher(&mut server, &host_pattern)
+            .add(&route_str, handler)
+            .unwrap_or_else(|err| panic!("{}", err));
+        self
+    }
+
+    /// Adds a named route handler for a specific path that is only served for matching hosts.
+    ///
+    /// The name shares one namespace with the names of `named_route`, and `url_for`
+    /// generates the path of the route like for any other named route.
+    ///
+    /// # Arguments
+    ///
+    /// - `H` - The host pattern implementing `ToString`.
+    /// - `N` - The unique route name implementing `ToString`.
+    /// - `R` - The route path pattern implementing `ToString`.
+    /// - `F` - The handler function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn host_named_route<H, N, R, F, Fut>(
+        &self,
+        host: H,
+        name: N,
+        route: R,
+        func: F,
+    ) -> &Self
+    where
+        H: ToString,
+        N: ToString,
+        R: ToString,
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        let host_pattern: HostPattern =
+            HostPattern::new(&host.to_string()).unwrap_or_else(|err| panic!("{}", err));
+        let name_str: String = name.to_string();
+        let route_str: String = route.to_string();
+        let handler: ArcFnPinBoxSendSync = Arc::new(move |ctx: Context| Box::pin(func(ctx)));
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        if Self::is_route_name_taken(&server, &name_str) {
+            panic!("{}", RouteError::DuplicateRouteName(name_str));
+        }
+        Self::host_route_matcher(&mut server, &host_pattern)
+            .add_named(&name_str, &route_str, handler)
+            .unwrap_or_else(|err| panic!("{}", err));
+        self
+    }
+
+    /// Retrieves the routes of a host pattern, creating the group on first use.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut ServerInner` - The server state holding the host routes.
+    /// - `&HostPattern` - The host pattern of the group.
+    ///
+    /// # Returns
+    ///
+    /// - `&mut RouteMatcher` - The routes served for the host pattern.
+    fn host_route_matcher<'a>(
+        server: &'a mut ServerInner,
+        host_pattern: &HostPattern,
+    ) -> &'a mut RouteMatcher {
+        let host_route: &mut VecHostPatternRouteMatcher = server.get_mut_host_route();
+        let position: usize = match host_route
+            .iter()
+            .position(|(pattern, _)| pattern == host_pattern)
+        {
+            Some(position) => position,
+            None => {
+                host_route.push((host_pattern.clone(), RouteMatcher::new()));
+                host_route.len() - 1
+            }
+        };
+        &mut host_route[position].1
+    }
+
+    /// Checks whether a route name is used by a host-agnostic or a host-scoped route.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ServerInner` - The server state holding the routes.
+    /// - `&str` - The route name.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the name is already used, false otherwise.
+    fn is_route_name_taken(server: &ServerInner, name: &str) -> bool {
+        server.get_route().has_named_route(name)
+            || server
+                .get_host_route()
+                .iter()
+                .any(|(_, route)| route.has_named_route(name))
+    }
+
+    /// Adds a route handler for a specific path that is only selected when all guards pass.
+    ///
+    /// Several guarded handlers can share a path with each other and with one unguarded
+    /// handler. Candidates are tried in registration order, the unguarded handler last.
+    /// When every candidate is rejected, the status code of the first failed guard is
+    /// responded, such as `406 Not Acceptable` or `415 Unsupported Media Type`.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The route path pattern implementing `ToString`.
+    /// - `VecRouteGuard` - The guards evaluated against the request.
+    /// - `F` - The handler function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn guarded_route<R, F, Fut>(&self, route: R, guards: VecRouteGuard, func: F) -> &Self
+    where
+        R: ToString,
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        let route_str: String = route.to_string();
+        self.get_write()
+            .await
+            .get_mut_route()
+            .add_with_guards(
+                &route_str,
+                guards,
+                Arc::new(move |ctx: Context| Box::pin(func(ctx))),
+            )
+            .unwrap_or_else(|err| panic!("{}", err));
+        self
+    }
+
+    /// Adds a named route handler for a specific path.
+    ///
+    /// The name can later be used to generate URLs for the route with `url_for`.
+    ///
+    /// # Arguments
+    ///
+    /// - `N` - The unique route name implementing `ToString`.
+    /// - `R` - The route path pattern implementing `ToString`.
+    /// - `F` - The handler function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn named_route<N, R, F, Fut>(&self, name: N, route: R, func: F) -> &Self
+    where
+        N: ToString,
+        R: ToString,
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        let name_str: String = name.to_string();
+        let route_str: String = route.to_string();
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        if Self::is_route_name_taken(&server, &name_str) {
+            panic!("{}", RouteError::DuplicateRouteName(name_str));
+        }
+        server
+            .get_mut_route()
+            .add_named(
+                &name_str,
+                &route_str,
+                Arc::new(move |ctx: Context| Box::pin(func(ctx))),
+            )
+            .unwrap_or_else(|err| panic!("{}", err));
+        self
+    }
+
+    /// Mounts the routes and middleware of another server under a path prefix.
+    ///
+    /// Every route of the nested server is registered on this server with the prefix
+    /// prepended, and the prefix may contain dynamic segments such as `/orgs/{org}`.
+    /// The request and response middleware of the nested server only run for its own
+    /// routes, after the middleware of this server has run. Inside those handlers,
+    /// `Context::get_mount_prefix` and `Context::get_request_relative_path` expose the
+    /// matched prefix and the remaining path of the normalized path, while
+    /// `Context::get_request_path` still returns the full path as received.
+    ///
+    /// The nested server is copied when it is mounted: routes and middleware added to it
+    /// afterwards are not visible through this server, so it should be fully configured
+    /// before calling `nest`.
+    ///
+    /// # Arguments
+    ///
+    /// - `P` - The mount prefix implementing `ToString`.
+    /// - `&Server` - The server whose routes are mounted.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix is invalid or a mounted route or route name is already defined.
+    pub async fn nest<P: ToString>(&self, prefix: P, router: &Server) -> &Self {
+        let prefix_str: String = prefix.to_string();
+        let prefix_pattern: RoutePattern =
+            RoutePattern::new(prefix_str.trim_end_matches(DEFAULT_HTTP_PATH))
+                .or_else(|_| RoutePattern::new(DEFAULT_HTTP_PATH))
+                .unwrap_or_else(|err| panic!("{}", err));
+        if prefix_str.is_empty() || prefix_pattern.has_regex() {
+            panic!("{}", RouteError::InvalidMountPrefix(prefix_str));
+        }
+        let (route, host_route, request_middleware, response_middleware) = {
+            let router: RwLockReadGuardServerInner = router.get_read().await;
+            (
+                router.get_route().clone(),
+                router.get_host_route().clone(),
+                router.get_request_middleware().clone(),
+                router.get_response_middleware().clone(),
+            )
+        };
+        let depth: usize = prefix_pattern.len();
+        let wrap = |handler: &ArcFnPinBoxSendSync| -> ArcFnPinBoxSendSync {
+            Self::mount_handler(
+                depth,
+                handler.clone(),
+                request_middleware.clone(),
+                response_middleware.clone(),
+            )
+        };
+        let mut server: RwLockWriteGuardServerInner = self.get_write().await;
+        server
+            .get_mut_route()
+            .merge(&prefix_pattern, &route, wrap)
+            .unwrap_or_else(|err| panic!("{}", err));
+        for (host_pattern, matcher) in host_route.iter() {
+            Self::host_route_matcher(&mut server, host_pattern)
+                .merge(&prefix_pattern, matcher, wrap)
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
+        drop(server);
+        self
+    }
+
+    /// Wraps a mounted route handler with the middleware of its nested server.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The number of path segments consumed by the mount prefix.
+    /// - `ArcFnPinBoxSendSync` - The route handler of the nested server.
+    /// - `VecArcFnPinBoxSendSync` - The request middleware of the nested server.
+    /// - `VecArcFnPinBoxSendSync` - The response middleware of the nested server.
+    ///
+    /// # Returns
+    ///
+    /// - `ArcFnPinBoxSendSync` - The wrapped handler.
+    fn mount_handler(
+        depth: usize,
+        handler: ArcFnPinBoxSendSync,
+        request_middleware: VecArcFnPinBoxSendSync,
+        response_middleware: VecArcFnPinBoxSendSync,
+    ) -> ArcFnPinBoxSendSync {
+        Arc::new(move |ctx: Context| {
+            let handler: ArcFnPinBoxSendSync = handler.clone();
+            let request_middleware: VecArcFnPinBoxSendSync = request_middleware.clone();
+            let response_middleware: VecArcFnPinBoxSendSync = response_middleware.clone();
+            Box::pin(async move {
+                let parent_depth: usize = ctx.get_mount_depth().await;
+                ctx.set_mount_depth(parent_depth + depth).await;
+                for hook in request_middleware.iter() {
+                    hook(ctx.clone()).await;
+                    if ctx.get_aborted().await {
+                        ctx.set_mount_depth(parent_depth).await;
+                        return;
+                    }
+                }
+                handler(ctx.clone()).await;
+                for hook in response_middleware.iter() {
+                    if ctx.get_aborted().await {
+                        break;
+                    }
+                    hook(ctx.clone()).await;
+                }
+                ctx.set_mount_depth(parent_depth).await;
+            })
+        })
+    }
+
+    /// Generates the URL path of a named route.
+    ///
+    /// Parameter values are percent-encoded and regex segments are validated
+    /// against the supplied values. Host-scoped routes are covered as well, and
+    /// only the path is generated for them.
+    ///
+    /// # Arguments
+    ///
+    /// - `N` - The route name implementing `ToString`.
+    /// - `&RouteParams` - The values to substitute into the route pattern.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultStringRouteError` - The generated path, or a `RouteError` on failure.
+    pub async fn url_for<N: ToString>(
+        &self,
+        name: N,
+        params: &RouteParams,
+    ) -> ResultStringRouteError {
+        let name: String = name.to_string();
+        let server: RwLockReadGuardServerInner = self.get_read().await;
+        server
+            .get_host_route()
+            .iter()
+            .map(|(_, route)| route)
+            .find(|route: &&RouteMatcher| route.has_named_route(&name))
+            .unwrap_or(server.get_route())
+            .url_for(&name, params)
+    }
+
+    /// Adds request middleware to the processing pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// - `F` - The middleware function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn request_middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        self.get_write()
+            .await
+            .get_mut_request_middleware()
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    /// Adds response middleware to the processing pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// - `F` - The middleware function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn response_middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        self.get_write()
+            .await
+            .get_mut_response_middleware()
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    /// Adds a hook executed before WebSocket connection upgrade.
+    ///
+    /// # Arguments
+    ///
+    /// - `F` - The hook function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn pre_upgrade_hook<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        self.get_write()
+            .await
+            .get_mut_pre_upgrade_hook()
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    /// Adds a hook executed after new client connection is established.
+    ///
+    /// # Arguments
+    ///
+    /// - `F` - The hook function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn connected_hook<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        self.get_write()
+            .await
+            .get_mut_connected_hook()
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    /// Adds a hook executed after a WebSocket connection has been closed.
+    ///
+    /// The hook runs whichever side initiated the closure, and also when the connection
+    /// ended without a close handshake. The close code and reason are available through
+    /// `Context::get_ws_close`.
+    ///
+    /// # Arguments
+    ///
+    /// - `F` - The hook function implementing `FnSendSyncStatic<Fut>`.
+    /// - `Fut` - The future type.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn ws_closed_hook<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FnSendSyncStatic<Fut>,
+        Fut: FutureSendStatic<()>,
+    {
+        self.get_write()
+            .await
+            .get_mut_ws_closed_hook()
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    /// Re-enables default HTTP handling for a route.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The route path implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_http_hook<R: ToString>(&self, route: R) -> &Self {
+        let route_string: String = route.to_string();
+        self.get_write()
+            .await
+            .get_mut_disable_http_hook()
+            .remove(&route_string);
+        self
+    }
+
+    /// Disables default HTTP handling for a route.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The route path implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_http_hook<R: ToString>(&self, route: R) -> &Self {
+        let route_string: String = route.to_string();
+        let _ = self
+            .get_write()
+            .await
+            .get_mut_disable_http_hook()
+            .add(&route_string, Arc::new(|_: Context| Box::pin(async {})));
+        self
+    }
+
+    /// Re-enables default WebSocket handling for a route.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The route path implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn enable_ws_hook<R: ToString>(&self, route: R) -> &Self {
+        let route_string: String = route.to_string();
+        self.get_write()
+            .await
+            .get_mut_disable_ws_hook()
+            .remove(&route_string);
+        self
+    }
+
+    /// Disables default WebSocket handling for a route.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The route path implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn disable_ws_hook<R: ToString>(&self, route: R) -> &Self {
+        let route_string: String = route.to_string();
+        let _ = self
+            .get_write()
+            .await
+            .get_mut_disable_ws_hook()
+            .add(&route_string, Arc::new(|_: Context| Box::pin(async {})));
         self
     }
 
@@ -512,147 +1973,861 @@ impl Server {
         }));
     }
 
-    /// Handles a panic that occurred within a request's context.
-    ///
-    /// This function associates the panic information with the context and invokes the
-    /// server's configured panic hook.
+    /// Handles a panic that occurred within a request's context.
+    ///
+    /// This function associates the panic information with the context and invokes the
+    /// server's configured panic hook.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Context` - The context of the request during which the panic occurred.
+    /// - `&Panic` - The captured panic information.
+    async fn handle_panic_with_context(&self, ctx: &Context, panic: &Panic) {
+        let panic_clone: Panic = panic.clone();
+        let _ = ctx.set_panic(panic_clone).await;
+        self.get_read().await.get_panic_hook()(ctx.clone()).await;
+    }
+
+    /// Handles a panic that occurred within a spawned Tokio task.
+    ///
+    /// It extracts the panic information from the `JoinError` and processes it.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Context` - The context associated with the task.
+    /// - `JoinError` - The `JoinError` returned from the panicked task.
+    async fn handle_task_panic(&self, ctx: &Context, join_error: JoinError) {
+        let panic: Panic = Panic::from_join_error(join_error);
+        self.handle_panic_with_context(&ctx, &panic).await;
+    }
+
+    /// Executes a given hook function within a spawned task and manages the request lifecycle.
+    ///
+    /// This function also handles panics that may occur within the hook's execution.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Context` - The request context.
+    /// - `&mut Lifecycle` - A mutable reference to the current `Lifecycle` state.
+    /// - `F` - The hook function to execute.
+    async fn run_hook_with_lifecycle<F>(
+        &self,
+        ctx: &Context,
+        lifecycle: &mut Lifecycle,
+        hook_func: F,
+    ) where
+        F: Fn(Context) -> PinBoxFutureSendStatic,
+    {
+        let result: ResultJoinError<()> = tokio::spawn(hook_func(ctx.clone())).await;
+        ctx.update_lifecycle_status(lifecycle).await;
+        if let Err(join_error) = result {
+            if join_error.is_panic() {
+                self.handle_task_panic(&ctx, join_error).await;
+            }
+        }
+    }
+
+    /// Creates and binds a `TcpListener` to an address.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The address to bind.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ServerResult` containing the bound `TcpListener` on success,
+    /// or a `ServerError` on failure.
+    async fn create_tcp_listener(&self, addr: &str) -> ServerResult<TcpListener> {
+        TcpListener::bind(addr)
+            .await
+            .map_err(|err| ServerError::TcpBind(err.to_string()))
+    }
+
+    /// Creates and binds every listener of the server's configuration.
+    ///
+    /// A configured address or socket file that matches a pre-opened listener, including
+    /// the ones adopted with `listen_inherited`, reuses it instead of binding again. On
+    /// unix, the first server to run also claims the listeners of systemd socket activation,
+    /// which are only passed on if `LISTEN_PID` is the id of this process. Remaining
+    /// pre-opened listeners are served with their own tags. The host and port are only
+    /// bound if no other listener was configured.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ServerResult` containing the bound listeners on success,
+    /// or the `ServerError` of the first listener that failed to bind.
+    async fn create_listeners(&self) -> ServerResult<VecServerListener> {
+        let config: ServerConfig = self.get_config().await;
+        let mut listeners: VecServerListener = Vec::new();
+        let mut inherited: VecInheritedListener =
+            self.get_read().await.get_inherited_listeners().clone();
+        #[cfg(unix)]
+        inherited.extend(take_activated_listeners());
+        for listen_addr in config.get_listen().iter() {
+            let tag: OptionString = listen_addr.get_tag().clone();
+            let position: OptionUsize = inherited
+                .iter()
+                .position(|listener| listener.is_bound_to_addr(listen_addr.get_addr()));
+            match position {
+                Some(index) => listeners.push(
+                    inherited
+                        .remove(index)
+                        .to_server_listener(tag)
+                        .map_err(|err| ServerError::AdoptListener(err.to_string()))?,
+                ),
+                None => listeners.push(ServerListener::Tcp(
+                    self.create_tcp_listener(listen_addr.get_addr()).await?,
+                    tag,
+                )),
+            }
+        }
+        #[cfg(unix)]
+        if let Some(path) = config.get_unix_socket() {
+            let position: OptionUsize = inherited
+                .iter()
+                .position(|listener| listener.is_bound_to_path(path));
+            match position {
+                Some(index) => listeners.push(
+                    inherited
+                        .remove(index)
+                        .to_server_listener(None)
+                        .map_err(|err| ServerError::AdoptListener(err.to_string()))?,
+                ),
+                None => listeners.push(ServerListener::Unix(
+                    self.create_unix_listener(path).await?,
+                    path.clone(),
+                )),
+            }
+        }
+        for inherited_listener in inherited.iter() {
+            listeners.push(
+                inherited_listener
+                    .to_server_listener(inherited_listener.get_tag().clone())
+                    .map_err(|err| ServerError::AdoptListener(err.to_string()))?,
+            );
+        }
+        if listeners.is_empty() {
+            let addr: String = Self::format_host_port(config.get_host(), config.get_port());
+            let tcp_listener: TcpListener = self.create_tcp_listener(&addr).await?;
+            listeners.push(ServerListener::Tcp(tcp_listener, None));
+        }
+        Ok(listeners)
+    }
+
+    /// Accepts connections on a bound listener until it fails.
+    ///
+    /// # Arguments
+    ///
+    /// - `ServerListener` - The listener to accept connections from.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<()>` - A `ServerResult` which is typically `Ok(())` unless an unrecoverable
+    ///   error occurs.
+    async fn accept_listener(&self, listener: ServerListener) -> ServerResult<()> {
+        match listener {
+            ServerListener::Tcp(tcp_listener, tag) => {
+                self.accept_connections(&tcp_listener, &tag).await
+            }
+            #[cfg(unix)]
+            ServerListener::Unix(unix_listener, path) => {
+                self.accept_unix_connections(&unix_listener, &path).await
+            }
+        }
+    }
+
+    /// Creates and binds a `UnixListener` based on the server's configuration.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path` - The path of the socket file.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ServerResult` containing the bound `UnixListener` on success,
+    /// or a `ServerError` on failure.
+    #[cfg(unix)]
+    async fn create_unix_listener(&self, path: &Path) -> ServerResult<UnixListener> {
+        let config: ServerConfig = self.get_read().await.get_config().clone();
+        bind_unix_listener(
+            path,
+            *config.get_unix_socket_mode(),
+            *config.get_unix_socket_remove_stale(),
+        )
+        .await
+        .map_err(|err| ServerError::UnixBind(err.to_string()))
+    }
+
+    /// Enters a loop to accept incoming Unix domain socket connections and spawn handlers for them.
+    ///
+    /// # Arguments
+    ///
+    /// - `&UnixListener` - A reference to the `UnixListener` to accept connections from.
+    /// - `&Path` - The path of the socket file.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<()>` - A `ServerResult` which is typically `Ok(())` unless an unrecoverable
+    ///   error occurs.
+    #[cfg(unix)]
+    async fn accept_unix_connections(
+        &self,
+        unix_listener: &UnixListener,
+        path: &Path,
+    ) -> ServerResult<()> {
+        while let Ok((stream, _)) = unix_listener.accept().await {
+            let Ok(peer) = UnixPeer::from_stream(&stream, path) else {
+                continue;
+            };
+            self.spawn_connection_handler(stream, ConnectionInfo::from_unix(peer))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Enters a loop to accept incoming TCP connections and spawn handlers for them.
+    ///
+    /// # Arguments
+    ///
+    /// - `&TcpListener` - A reference to the `TcpListener` to accept connections from.
+    /// - `&OptionString` - The tag of the listener.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<()>` - A `ServerResult` which is typically `Ok(())` unless an unrecoverable
+    /// error occurs.
+    async fn accept_connections(
+        &self,
+        tcp_listener: &TcpListener,
+        tag: &OptionString,
+    ) -> ServerResult<()> {
+        while let Ok((stream, socket_addr)) = tcp_listener.accept().await {
+            self.configure_stream(&stream).await;
+            self.spawn_connection_handler(stream, ConnectionInfo::new(socket_addr, tag.clone()))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Configures socket options for a newly accepted `TcpStream`.
+    ///
+    /// This applies settings like `SO_LINGER`, `TCP_NODELAY`, and `IP_TTL` from the server's configuration.
+    ///
+    /// # Arguments
+    ///
+    /// - `&TcpStream` - A reference to the `TcpStream` to configure.
+    async fn configure_stream(&self, stream: &TcpStream) {
+        let config: ServerConfig = self.get_read().await.get_config().clone();
+        let nodelay_opt: OptionBool = *config.get_nodelay();
+        let linger_opt: OptionDuration = *config.get_linger();
+        let ttl_opt: OptionU32 = *config.get_ttl();
+        let _ = stream.set_linger(linger_opt);
+        if let Some(nodelay) = nodelay_opt {
+            let _ = stream.set_nodelay(nodelay);
+        }
+        if let Some(ttl) = ttl_opt {
+            let _ = stream.set_ttl(ttl);
+        }
+    }
+
+    /// Spawns a new asynchronous task to handle a single client connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `S` - The accepted client connection, such as a TCP or Unix domain socket connection.
+    /// - `ConnectionInfo` - The details gathered when the connection was accepted.
+    async fn spawn_connection_handler<S: ConnectionIo>(
+        &self,
+        stream: S,
+        mut connection: ConnectionInfo,
+    ) {
+        let server: Server = self.clone();
+        #[cfg(unix)]
+        let guard: ConnectionGuard = {
+            let inner: RwLockReadGuardServerInner = self.get_read().await;
+            ConnectionGuard::new(inner.get_connections(), inner.get_connections_drained())
+        };
+        tokio::spawn(async move {
+            #[cfg(unix)]
+            let _guard: ConnectionGuard = guard;
+            let mut buffer: Vec<u8> = Vec::new();
+            let Some(stream) = server
+                .accept_proxy_header(stream, &mut buffer, &mut connection)
+                .await
+            else {
+                return;
+            };
+            let Some(io) = server
+                .secure_stream(stream, &mut buffer, &mut connection)
+                .await
+            else {
+                return;
+            };
+            server.serve_connection(io, buffer, &connection).await;
+        });
+    }
+
+    /// Serves an established connection with the protocol the client speaks.
+    ///
+    /// # Arguments
+    ///
+    /// - `BoxConnectionIo` - The transport of the connection, already decrypted if TLS is enabled.
+    /// - `Vec<u8>` - The bytes already read from the transport.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    async fn serve_connection(
+        &self,
+        io: BoxConnectionIo,
+        buffer: Vec<u8>,
+        connection: &ConnectionInfo,
+    ) {
+        #[cfg(feature = "http2")]
+        if self.is_http2_alpn(connection).await {
+            self.serve_http2(io, connection).await;
+            return;
+        }
+        let stream: ConnectionStream = ConnectionStream::with_buffer(io, buffer);
+        #[cfg(feature = "http2")]
+        match self.is_http2_prior_knowledge(&stream, connection).await {
+            Some(true) => {
+                if let Some((buffer, reader, writer)) = stream.detach().await {
+                    let io = join_io(Cursor::new(buffer).chain(reader), writer);
+                    self.serve_http2(io, connection).await;
+                }
+                return;
+            }
+            Some(false) => {}
+            None => return,
+        }
+        self.handle_connection(stream, connection).await;
+    }
+
+    /// Reads the PROXY protocol header of an accepted connection if the protocol is enabled.
+    ///
+    /// An optional header is only read from the sources added with `proxy_protocol_source`.
+    /// The client address of the header replaces the peer address in the connection details.
+    ///
+    /// # Arguments
+    ///
+    /// - `S` - The accepted client connection.
+    /// - `&mut Vec<u8>` - The buffer receiving the bytes read past the header.
+    /// - `&mut ConnectionInfo` - The connection details to record the header in.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<S>` - The stream to read requests from, or None if the header is
+    ///   malformed or missing although it is required.
+    async fn accept_proxy_header<S: ConnectionIo>(
+        &self,
+        mut stream: S,
+        buffer: &mut Vec<u8>,
+        connection: &mut ConnectionInfo,
+    ) -> Option<S> {
+        let (proxy_protocol, trusted) = {
+            let server: RwLockReadGuardServerInner = self.get_read().await;
+            let config: &ServerConfig = server.get_config();
+            let trusted: bool = connection.get_peer_addr().is_some_and(|addr: SocketAddr| {
+                config
+                    .get_proxy_protocol_sources()
+                    .iter()
+                    .any(|cidr: &IpCidr| cidr.contains(&addr.ip()))
+            });
+            (*config.get_proxy_protocol(), trusted)
+        };
+        match proxy_protocol {
+            ProxyProtocol::Disabled => return Some(stream),
+            ProxyProtocol::Optional if !trusted => return Some(stream),
+            _ => {}
+        }
+        let header: OptionProxyHeader = read_proxy_header(&mut stream, buffer).await.ok()?;
+        let Some(header) = header else {
+            return (proxy_protocol == ProxyProtocol::Optional).then_some(stream);
+        };
+        if let Some(source) = header.get_source() {
+            connection.set_peer_addr(Some(*source));
+        }
+        connection.set_proxy(Some(header));
+        Some(stream)
+    }
+
+    /// Performs the TLS handshake of an accepted connection if TLS is enabled.
+    ///
+    /// The negotiated parameters are recorded in the connection details and the
+    /// session is served directly, so decrypted bytes never leave the process. Bytes
+    /// already read from the connection are fed to the handshake first. A handshake that
+    /// does not complete within the handshake timeout fails.
+    ///
+    /// # Arguments
+    ///
+    /// - `S` - The accepted client connection.
+    /// - `&mut Vec<u8>` - The bytes already read from the connection, emptied once they are encrypted.
+    /// - `&mut ConnectionInfo` - The connection details to record the TLS parameters in.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionBoxConnectionIo` - The transport to read requests from, or None if the handshake failed.
+    #[cfg(feature = "tls")]
+    async fn secure_stream<S: ConnectionIo>(
+        &self,
+        stream: S,
+        buffer: &mut Vec<u8>,
+        connection: &mut ConnectionInfo,
+    ) -> OptionBoxConnectionIo {
+        let (acceptor, handshake_timeout) = {
+            let server: RwLockReadGuardServerInner = self.get_read().await;
+            let handshake_timeout: Duration = *server.get_config().get_tls_handshake_timeout();
+            (server.get_tls().clone(), handshake_timeout)
+        };
+        let Some(acceptor) = acceptor else {
+            return Some(Box::new(stream));
+        };
+        let stream: BoxConnectionIo = if buffer.is_empty() {
+            Box::new(stream)
+        } else {
+            let (reader, writer) = split(stream);
+            Box::new(join_io(
+                Cursor::new(std::mem::take(buffer)).chain(reader),
+                writer,
+            ))
+        };
+        let tls_stream: TlsStream<BoxConnectionIo> =
+            tokio::time::timeout(handshake_timeout, acceptor.accept(stream))
+                .await
+                .ok()?
+                .ok()?;
+        connection.set_tls(Some(TlsInfo::from_connection(tls_stream.get_ref().1)));
+        Some(Box::new(tls_stream))
+    }
+
+    /// Hands back an accepted connection unchanged, as TLS support is not compiled in.
+    ///
+    /// # Arguments
+    ///
+    /// - `S` - The accepted client connection.
+    /// - `&mut Vec<u8>` - The bytes already read from the connection.
+    /// - `&mut ConnectionInfo` - The connection details.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionBoxConnectionIo` - The transport to read requests from.
+    #[cfg(not(feature = "tls"))]
+    async fn secure_stream<S: ConnectionIo>(
+        &self,
+        stream: S,
+        _buffer: &mut Vec<u8>,
+        _connection: &mut ConnectionInfo,
+    ) -> OptionBoxConnectionIo {
+        Some(Box::new(stream))
+    }
+
+    /// Answers a request that exceeds a size limit or is malformed and ends the connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ConnectionStream` - The connection.
+    /// - `usize` - The status code to respond with.
+    async fn reject_request(stream: &ConnectionStream, status_code: usize) {
+        let mut response: Response = Response::default();
+        response
+            .set_version(HttpVersion::HTTP1_1)
+            .set_status_code(status_code)
+            .set_header(CONNECTION, CLOSE);
+        let _ = stream.send(&response.build()).await;
+    }
+
+    /// Handles a single client connection, determining whether it's an HTTP or WebSocket request.
+    ///
+    /// It reads the initial request from the stream and dispatches it to the appropriate handler.
+    ///
+    /// # Arguments
+    ///
+    /// - `ConnectionStream` - The client connection.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    async fn handle_connection(&self, stream: ConnectionStream, connection: &ConnectionInfo) {
+        let config: ServerConfig = self.get_config().await;
+        let received: ResultReceivedRequestRequestReadError = stream
+            .read_request(*config.get_http_buffer(), *config.get_http_max_body_size())
+            .await;
+        let (mut request, target) = match received {
+            Ok(received) => received.into_parts(),
+            Err(RequestReadError::Reject(status_code)) => {
+                Self::reject_request(&stream, status_code).await;
+                return;
+            }
+            Err(RequestReadError::Read(_)) => return,
+        };
+        #[cfg(feature = "http2")]
+        if let Some(frame) = self
+            .get_h2c_upgrade_frame(&request, &target, connection)
+            .await
+        {
+            self.upgrade_to_http2(stream, frame, connection).await;
+            return;
+        }
+        let ctx: Context = Context::create_context(&stream, &request, self, connection);
+        ctx.set_request_target(target).await;
+        let handler: HandlerState = HandlerState::new(&stream, &ctx);
+        if request.is_ws() {
+            self.ws_hook(&handler, &mut request).await;
+        } else {
+            self.http_hook(&handler, &request).await;
+        }
+    }
+
+    /// Checks whether the client selected HTTP/2 through ALPN.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if HTTP/2 is enabled and was negotiated during the TLS handshake.
+    #[cfg(feature = "http2")]
+    async fn is_http2_alpn(&self, connection: &ConnectionInfo) -> bool {
+        self.get_read().await.get_config().get_http2().is_some()
+            && connection
+                .get_tls()
+                .as_ref()
+                .is_some_and(|tls: &TlsInfo| tls.get_alpn().as_deref() == Some(ALPN_H2_STR))
+    }
+
+    /// Checks whether a plain connection speaks HTTP/2 from its first byte.
     ///
     /// # Arguments
     ///
-    /// - `&Context` - The context of the request during which the panic occurred.
-    /// - `&Panic` - The captured panic information.
-    async fn handle_panic_with_context(&self, ctx: &Context, panic: &Panic) {
-        let panic_clone: Panic = panic.clone();
-        let _ = ctx.set_panic(panic_clone).await;
-        self.get_read().await.get_panic_hook()(ctx.clone()).await;
+    /// - `&ConnectionStream` - The connection.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionBool` - Whether HTTP/2 is enabled and the client sent the connection preface,
+    ///   or None if the client stalled within the preface for longer than `HTTP2_PREFACE_TIMEOUT`.
+    #[cfg(feature = "http2")]
+    async fn is_http2_prior_knowledge(
+        &self,
+        stream: &ConnectionStream,
+        connection: &ConnectionInfo,
+    ) -> OptionBool {
+        if self.get_read().await.get_config().get_http2().is_none()
+            || connection.get_tls().is_some()
+        {
+            return Some(false);
+        }
+        stream
+            .starts_with(HTTP2_PREFACE, HTTP2_PREFACE_TIMEOUT)
+            .await
     }
 
-    /// Handles a panic that occurred within a spawned Tokio task.
+    /// Builds the first HTTP/2 frame for a request that asks to upgrade to h2c.
     ///
-    /// It extracts the panic information from the `JoinError` and processes it.
+    /// Only plain connections can upgrade, and the request must carry the
+    /// `HTTP2-Settings` header and no body.
     ///
     /// # Arguments
     ///
-    /// - `&Context` - The context associated with the task.
-    /// - `JoinError` - The `JoinError` returned from the panicked task.
-    async fn handle_task_panic(&self, ctx: &Context, join_error: JoinError) {
-        let panic: Panic = Panic::from_join_error(join_error);
-        self.handle_panic_with_context(&ctx, &panic).await;
+    /// - `&Request` - The first request of the connection.
+    /// - `&str` - The request target exactly as sent by the client.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionVecU8` - The frame that opens stream 1, or None if the connection stays on HTTP/1.1.
+    #[cfg(feature = "http2")]
+    async fn get_h2c_upgrade_frame(
+        &self,
+        request: &Request,
+        target: &str,
+        connection: &ConnectionInfo,
+    ) -> OptionVecU8 {
+        if self.get_read().await.get_config().get_http2().is_none()
+            || connection.get_tls().is_some()
+            || !request.is_h2c()
+            || !request.has_header(HTTP2_SETTINGS)
+            || !request.get_body().is_empty()
+        {
+            return None;
+        }
+        encode_h2c_upgrade_frame(request, target)
     }
 
-    /// Executes a given hook function within a spawned task and manages the request lifecycle.
+    /// Switches a connection to HTTP/2 after an `Upgrade: h2c` request.
     ///
-    /// This function also handles panics that may occur within the hook's execution.
+    /// The upgraded request is answered as stream 1 of the new connection. The client
+    /// has to send its connection preface within `HTTP2_PREFACE_TIMEOUT`. The future is
+    /// boxed because `handle_connection` awaits it.
     ///
     /// # Arguments
     ///
-    /// - `&Context` - The request context.
-    /// - `&mut Lifecycle` - A mutable reference to the current `Lifecycle` state.
-    /// - `F` - The hook function to execute.
-    async fn run_hook_with_lifecycle<F>(
+    /// - `ConnectionStream` - The connection that sent the upgrade request.
+    /// - `Vec<u8>` - The frame that opens stream 1.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    ///
+    /// # Returns
+    ///
+    /// - `PinBoxFutureSendStatic` - The future serving the upgraded connection.
+    #[cfg(feature = "http2")]
+    fn upgrade_to_http2(
         &self,
-        ctx: &Context,
-        lifecycle: &mut Lifecycle,
-        hook_func: F,
-    ) where
-        F: Fn(Context) -> PinBoxFutureSendStatic,
-    {
-        let result: ResultJoinError<()> = tokio::spawn(hook_func(ctx.clone())).await;
-        ctx.update_lifecycle_status(lifecycle).await;
-        if let Err(join_error) = result {
-            if join_error.is_panic() {
-                self.handle_task_panic(&ctx, join_error).await;
+        stream: ConnectionStream,
+        frame: Vec<u8>,
+        connection: &ConnectionInfo,
+    ) -> PinBoxFutureSendStatic {
+        let server: Server = self.clone();
+        let connection: ConnectionInfo = connection.clone();
+        Box::pin(async move {
+            let Some((buffer, reader, mut writer)) = stream.detach().await else {
+                return;
+            };
+            if writer.write_all(H2C_SWITCHING_PROTOCOLS).await.is_err()
+                || writer.flush().await.is_err()
+            {
+                return;
             }
-        }
+            let mut reader = Cursor::new(buffer).chain(reader);
+            let preface: io::Result<Vec<u8>> = tokio::time::timeout(
+                HTTP2_PREFACE_TIMEOUT,
+                read_http2_client_preface(&mut reader),
+            )
+            .await
+            .unwrap_or_else(|_| Err(io::Error::from(io::ErrorKind::TimedOut)));
+            let Ok(mut prefix) = preface else {
+                return;
+            };
+            prefix.extend_from_slice(&frame);
+            let io = join_io(Cursor::new(prefix).chain(reader), writer);
+            server.serve_http2(io, &connection).await;
+        })
     }
 
-    /// Creates and binds a `TcpListener` based on the server's configuration.
+    /// Serves the streams of an HTTP/2 connection until the client goes away.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// Returns a `ServerResult` containing the bound `TcpListener` on success,
-    /// or a `ServerError` on failure.
-    async fn create_tcp_listener(&self) -> ServerResult<TcpListener> {
-        let config: ServerConfig = self.get_read().await.get_config().clone();
-        let host: &str = config.get_host();
-        let port: usize = *config.get_port();
-        let addr: String = Self::format_host_port(host, &port);
-        TcpListener::bind(&addr)
-            .await
-            .map_err(|err| ServerError::TcpBind(err.to_string()))
+    /// - `T` - The connection, positioned at the client connection preface.
+    /// - `&ConnectionInfo` - The details gathered when the connection was accepted.
+    #[cfg(feature = "http2")]
+    async fn serve_http2<T>(&self, io: T, connection: &ConnectionInfo)
+    where
+        T: AsyncRead + AsyncWrite + Unpin + Send,
+    {
+        let settings: Http2Settings = self.get_config().await.get_http2().unwrap_or_default();
+        let Ok(mut h2) = settings.builder().handshake::<T, Bytes>(io).await else {
+            return;
+        };
+        let mut connection: ConnectionInfo = connection.clone();
+        connection.set_http2(true);
+        while let Some(Ok((request, respond))) = h2.accept().await {
+            let server: Server = self.clone();
+            let connection: ConnectionInfo = connection.clone();
+            tokio::spawn(async move {
+                server
+                    .handle_http2_stream(request, respond, &connection)
+                    .await;
+            });
+        }
     }
 
-    /// Enters a loop to accept incoming TCP connections and spawn handlers for them.
+    /// Runs one HTTP/2 stream through the middleware and routes.
+    ///
+    /// The body is read up to the body size limit and the trailer fields are added to
+    /// the request headers. The stream gets its own `Context`, whose responses are sent
+    /// as HTTP/2 frames on the stream.
     ///
     /// # Arguments
     ///
-    /// - `&TcpListener` - A reference to the `TcpListener` to accept connections from.
+    /// - `Http2Request` - The request received on the stream.
+    /// - `Http2SendResponse` - The handle that answers the request.
+    /// - `&ConnectionInfo` - The details of the HTTP/2 connection.
+    #[cfg(feature = "http2")]
+    async fn handle_http2_stream(
+        &self,
+        request: Http2Request,
+        mut respond: Http2SendResponse,
+        connection: &ConnectionInfo,
+    ) {
+        let config: ServerConfig = self.get_config().await;
+        let (parts, mut body_stream) = request.into_parts();
+        let body: Vec<u8> =
+            match read_http2_body(&parts, &mut body_stream, *config.get_http_max_body_size()).await
+            {
+                Ok(body) => body,
+                Err(RequestReadError::Reject(status_code)) => {
+                    Self::reject_http2_stream(&mut respond, status_code);
+                    return;
+                }
+                Err(RequestReadError::Read(_)) => {
+                    respond.send_reset(H2Reason::INTERNAL_ERROR);
+                    return;
+                }
+            };
+        let Ok(trailers) = body_stream.trailers().await else {
+            respond.send_reset(H2Reason::INTERNAL_ERROR);
+            return;
+        };
+        let request: Request = http2_request(&parts, &trailers, body);
+        let stream: ConnectionStream =
+            ConnectionStream::http2(respond, parts.method == Http2Method::HEAD);
+        let target: String = parts
+            .uri
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str())
+            .unwrap_or(DEFAULT_HTTP_PATH)
+            .to_owned();
+        let ctx: Context = Context::create_context(&stream, &request, self, connection);
+        ctx.set_request_target(target).await;
+        let handler: HandlerState = HandlerState::new(&stream, &ctx);
+        self.http2_hook(&handler, &request).await;
+        stream.finish_http2().await;
+    }
+
+    /// Answers an HTTP/2 request that exceeds a size limit.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// - `ServerResult<()>` - A `ServerResult` which is typically `Ok(())` unless an unrecoverable
-    /// error occurs.
-    async fn accept_connections(&self, tcp_listener: &TcpListener) -> ServerResult<()> {
-        while let Ok((stream, _socket_addr)) = tcp_listener.accept().await {
-            self.configure_stream(&stream).await;
-            let stream: ArcRwLockStream = ArcRwLockStream::from_stream(stream);
-            self.spawn_connection_handler(stream).await;
+    /// - `&mut Http2SendResponse` - The handle that answers the request.
+    /// - `usize` - The status code to respond with.
+    #[cfg(feature = "http2")]
+    fn reject_http2_stream(respond: &mut Http2SendResponse, status_code: usize) {
+        let mut response: Http2Response = Http2Response::new(());
+        if let Some(status_code) = u16::try_from(status_code)
+            .ok()
+            .and_then(|status_code: u16| Http2StatusCode::from_u16(status_code).ok())
+        {
+            *response.status_mut() = status_code;
         }
-        Ok(())
+        let _ = respond.send_response(response, true);
     }
 
-    /// Configures socket options for a newly accepted `TcpStream`.
+    /// Finds the handler and parameters for a host and an already normalized path.
     ///
-    /// This applies settings like `SO_LINGER`, `TCP_NODELAY`, and `IP_TTL` from the server's configuration.
+    /// Virtual host routes whose host pattern matches the request host are tried first,
+    /// in registration order, before falling back to the host-agnostic routes.
     ///
     /// # Arguments
     ///
-    /// - `&TcpStream` - A reference to the `TcpStream` to configure.
-    async fn configure_stream(&self, stream: &TcpStream) {
-        let config: ServerConfig = self.get_read().await.get_config().clone();
-        let nodelay_opt: OptionBool = *config.get_nodelay();
-        let linger_opt: OptionDuration = *config.get_linger();
-        let ttl_opt: OptionU32 = *config.get_ttl();
-        let _ = stream.set_linger(linger_opt);
-        if let Some(nodelay) = nodelay_opt {
-            let _ = stream.set_nodelay(nodelay);
-        }
-        if let Some(ttl) = ttl_opt {
-            let _ = stream.set_ttl(ttl);
+    /// - `&ServerInner` - The server state holding the routes.
+    /// - `&Request` - The request being resolved.
+    /// - `&str` - The normalized request path.
+    /// - `&mut OptionUsize` - The status code of the first guard rejection, kept if already set.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionArcFnPinBoxSendSyncRouteParams` - Some handler with its parameters if matched, None otherwise.
+    fn find_route(
+        server: &ServerInner,
+        request: &Request,
+        path: &str,
+        rejected_status_code: &mut OptionUsize,
+    ) -> OptionArcFnPinBoxSendSyncRouteParams {
+        let case_sensitive: bool = *server.get_config().get_case_sensitive();
+        let empty_segments: bool = server.get_config().get_trailing_slash().is_some();
+        for (host_pattern, route) in server.get_host_route().iter() {
+            let Some(mut params) = host_pattern.match_host(request.get_host()) else {
+                continue;
+            };
+            if let Some((handler, route_params)) = route.find_route(
+                request,
+                path,
+                case_sensitive,
+                empty_segments,
+                rejected_status_code,
+            ) {
+                params.extend(route_params);
+                return Some((handler, params));
+            }
         }
+        server.get_route().find_route(
+            request,
+            path,
+            case_sensitive,
+            empty_segments,
+            rejected_status_code,
+        )
     }
 
-    /// Spawns a new asynchronous task to handle a single client connection.
+    /// Creates a handler that redirects the client to another path with `308 Permanent Redirect`.
     ///
     /// # Arguments
     ///
-    /// - `ArcRwLockStream` - The thread-safe stream representing the client connection.
-    async fn spawn_connection_handler(&self, stream: ArcRwLockStream) {
-        let server: Server = self.clone();
-        let http_buffer: usize = *self.get_read().await.get_config().get_http_buffer();
-        tokio::spawn(async move {
-            server.handle_connection(stream, http_buffer).await;
-        });
+    /// - `&str` - The target path.
+    /// - `&str` - The request target as sent by the client, whose query string is kept verbatim.
+    ///
+    /// # Returns
+    ///
+    /// - `ArcFnPinBoxSendSync` - The redirect handler.
+    fn redirect_handler(path: &str, target: &str) -> ArcFnPinBoxSendSync {
+        let location: String = match target.split_once(QUERY) {
+            Some((_, query)) => format!("{}{}{}", path, QUERY, query),
+            None => path.to_owned(),
+        };
+        Arc::new(move |ctx: Context| {
+            let location: String = location.clone();
+            Box::pin(async move {
+                ctx.set_response_status_code(308)
+                    .await
+                    .replace_response_header(LOCATION, location)
+                    .await
+                    .set_response_body(Vec::new())
+                    .await;
+            })
+        })
     }
 
-    /// Handles a single client connection, determining whether it's an HTTP or WebSocket request.
+    /// Resolves the handler for a request and stores the captured route parameters.
     ///
-    /// It reads the initial request from the stream and dispatches it to the appropriate handler.
+    /// The request path is normalized according to the server configuration first.
+    /// If nothing matches, the trailing slash policy decides whether the path is
+    /// retried with the trailing slash toggled, and whether the client is redirected.
+    /// When no route accepts the request but a guard rejected it, the status code of
+    /// the first rejecting guard is responded.
     ///
     /// # Arguments
     ///
-    /// - `ArcRwLockStream` - The stream for the client connection.
-    /// - `usize` - The buffer size to use for reading the initial HTTP request.
-    async fn handle_connection(&self, stream: ArcRwLockStream, http_buffer: usize) {
-        if let Ok(mut request) = Request::http_from_stream(&stream, http_buffer).await {
-            let ctx: Context = Context::create_context(&stream, &request);
-            let handler: HandlerState = HandlerState::new(&stream, &ctx);
-            if request.is_ws() {
-                self.ws_hook(&handler, &mut request).await;
-            } else {
-                self.http_hook(&handler, &request).await;
+    /// - `&Context` - The request context.
+    /// - `&Request` - The request to resolve.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionArcFnPinBoxSendSync` - Some handler if a route matched, None otherwise.
+    async fn resolve_route(&self, ctx: &Context, request: &Request) -> OptionArcFnPinBoxSendSync {
+        let server: RwLockReadGuardServerInner = self.get_read().await;
+        let config: &ServerConfig = server.get_config();
+        let path: String = normalize_path(
+            request.get_path(),
+            *config.get_merge_slashes(),
+            *config.get_remove_dot_segments(),
+        );
+        let mut rejected_status_code: OptionUsize = None;
+        let mut found: OptionArcFnPinBoxSendSyncRouteParams =
+            Self::find_route(&server, request, &path, &mut rejected_status_code);
+        let mut route_path: String = path;
+        let trailing_slash: TrailingSlash = config.get_trailing_slash().unwrap_or_default();
+        if found.is_none() && trailing_slash != TrailingSlash::Strict {
+            if let Some(alternate_path) = toggle_trailing_slash(&route_path) {
+                found =
+                    Self::find_route(&server, request, &alternate_path, &mut rejected_status_code);
+                if found.is_some() && trailing_slash == TrailingSlash::Redirect {
+                    ctx.set_route_params(RouteParams::default()).await;
+                    let target: String = ctx.get_request_target().await;
+                    return Some(Self::redirect_handler(&alternate_path, &target));
+                }
+                if found.is_some() {
+                    route_path = alternate_path;
+                }
             }
         }
+        ctx.set_route_path(route_path).await;
+        let Some((handler, mut params)) = found else {
+            ctx.set_route_params(RouteParams::default()).await;
+            return rejected_status_code.map(status_handler);
+        };
+        if *config.get_decode_route_params() {
+            params
+                .values_mut()
+                .for_each(|value: &mut String| *value = decode_route_param(value));
+        }
+        ctx.set_route_params(params).await;
+        Some(handler)
     }
 
     /// Executes all registered pre-upgrade hooks for a WebSocket connection.
@@ -681,6 +2856,19 @@ impl Server {
         }
     }
 
+    /// Executes all registered WebSocket closed hooks in sequence.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Context` - The connection context.
+    /// - `&mut Lifecycle` - A mutable reference to the request lifecycle state.
+    async fn run_ws_closed_hook(&self, ctx: &Context, lifecycle: &mut Lifecycle) {
+        for func in self.get_read().await.get_ws_closed_hook().iter() {
+            self.run_hook_with_lifecycle(ctx, lifecycle, move |ctx: Context| func(ctx))
+                .await;
+        }
+    }
+
     /// Executes all registered request middleware in sequence.
     ///
     /// # Arguments
@@ -740,16 +2928,25 @@ impl Server {
     ///
     /// - `bool` - A boolean indicating whether the connection should be kept alive.
     async fn request_hook<'a>(&self, state: &HandlerState<'a>, request: &Request) -> bool {
-        let route: &str = request.get_path();
+        let keep_alive: bool = self.run_request_pipeline(state, request).await;
+        !state.ctx.sse_finish().await && keep_alive
+    }
+
+    /// Runs the middleware and route handler of a single request.
+    ///
+    /// # Arguments
+    ///
+    /// - `&HandlerState<'a>` - The `HandlerState` for the current connection.
+    /// - `&Request` - The incoming request to be processed.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether the connection should be kept alive.
+    async fn run_request_pipeline<'a>(&self, state: &HandlerState<'a>, request: &Request) -> bool {
         let ctx: &Context = state.ctx;
         ctx.set_request(request).await;
         let mut lifecycle: Lifecycle = Lifecycle::new_continue(request.is_enable_keep_alive());
-        let route_hook: OptionArcFnPinBoxSendSync = self
-            .get_read()
-            .await
-            .get_route()
-            .resolve_route(ctx, route)
-            .await;
+        let route_hook: OptionArcFnPinBoxSendSync = self.resolve_route(ctx, request).await;
         self.run_request_middleware(ctx, &mut lifecycle).await;
         if lifecycle.is_abort() {
             return lifecycle.keep_alive();
@@ -771,13 +2968,25 @@ impl Server {
     async fn handle_http_requests<'a>(&self, state: &HandlerState<'a>, request: &Request) {
         let route: &String = request.get_path();
         let contains_disable_http_hook: bool = self.contains_disable_http_hook(route).await;
-        let buffer: usize = *self.get_read().await.get_config().get_http_buffer();
+        let config: ServerConfig = self.get_config().await;
+        let buffer: usize = *config.get_http_buffer();
+        let max_body_size: usize = *config.get_http_max_body_size();
         if contains_disable_http_hook {
             while self.request_hook(state, request).await {}
             return;
         }
-        while let Ok(new_request) = &Request::http_from_stream(state.stream, buffer).await {
-            if !self.request_hook(state, new_request).await {
+        loop {
+            let (new_request, target) = match state.stream.read_request(buffer, max_body_size).await
+            {
+                Ok(received) => received.into_parts(),
+                Err(RequestReadError::Reject(status_code)) => {
+                    Self::reject_request(state.stream, status_code).await;
+                    return;
+                }
+                Err(RequestReadError::Read(_)) => return,
+            };
+            state.ctx.set_request_target(target).await;
+            if !self.request_hook(state, &new_request).await {
                 return;
             }
         }
@@ -804,13 +3013,32 @@ impl Server {
         self.handle_http_requests(state, request).await;
     }
 
+    /// The entry point for handling a request received on an HTTP/2 stream.
+    ///
+    /// It runs connected hooks and then the request, as every stream carries a single request.
+    ///
+    /// # Arguments
+    ///
+    /// - `&HandlerState<'a>` - The `HandlerState` for the stream.
+    /// - `&Request` - The request received on the stream.
+    #[cfg(feature = "http2")]
+    async fn http2_hook<'a>(&self, state: &HandlerState<'a>, request: &Request) {
+        let ctx: &Context = state.ctx;
+        let mut lifecycle: Lifecycle = Lifecycle::new();
+        self.run_connected_hook(ctx, &mut lifecycle).await;
+        if lifecycle.is_abort() {
+            return;
+        }
+        self.request_hook(state, request).await;
+    }
+
     /// Handles the stream of incoming WebSocket frames after a connection is established.
     ///
     /// # Arguments
     ///
     /// - `&HandlerState<'a>` - The `HandlerState` for the current connection.
     /// - `&mut Request` - The mutable request object, which will be updated with each new frame.
-    /// - `&str` - The route path that the WebSocket connection was established on.
+    /// - `&str` - The normalized path the WebSocket route was matched against.
     async fn handle_ws_requests<'a>(
         &self,
         state: &HandlerState<'a>,
@@ -821,11 +3049,35 @@ impl Server {
         let buffer: usize = *self.get_read().await.get_config().get_ws_buffer();
         if disable_ws_hook_contains {
             while self.request_hook(state, request).await {}
+            state.ctx.ws_leave_all().await;
             return;
         }
-        while let Ok(new_request) = &Request::ws_from_stream(state.stream, buffer, request).await {
-            let _ = self.request_hook(state, new_request).await;
+        let heartbeat: WebSocketHeartbeat = self.get_ws_heartbeat(route).await;
+        if !heartbeat.is_enabled() {
+            while state.ctx.ws_read_message(buffer).await.is_ok() {
+                let _ = self.request_hook(state, request).await;
+            }
+        } else {
+            state.ctx.ws_touch().await;
+            loop {
+                let deadline: OptionInstant = state.ctx.ws_next_heartbeat(&heartbeat).await;
+                match state.ctx.ws_read_message_until(buffer, deadline).await {
+                    Ok(Some(_)) => {
+                        let _ = self.request_hook(state, request).await;
+                    }
+                    Err(_) => break,
+                    Ok(None) => {
+                        if !state.ctx.ws_heartbeat_tick(&heartbeat).await {
+                            break;
+                        }
+                    }
+                }
+            }
         }
+        state.ctx.ws_finish_close().await;
+        state.ctx.ws_leave_all().await;
+        self.run_ws_closed_hook(state.ctx, &mut Lifecycle::new())
+            .await;
     }
 
     /// The main entry point for handling a WebSocket connection.
@@ -838,14 +3090,23 @@ impl Server {
     /// - `&HandlerState<'a>` - The `HandlerState` for the current connection.
     /// - `&mut Request` - The mutable HTTP request that initiated the WebSocket upgrade.
     async fn ws_hook<'a>(&self, state: &HandlerState<'a>, request: &mut Request) {
-        let route: String = request.get_path().clone();
         let ctx: &Context = state.ctx;
         let mut lifecycle: Lifecycle = Lifecycle::new();
-        self.get_read()
-            .await
-            .get_route()
-            .resolve_route(ctx, &route)
-            .await;
+        self.resolve_route(ctx, request).await;
+        let route: String = ctx.get_route_path().await;
+        let policy: WebSocketPolicy = self.get_ws_policy(&route).await;
+        if !ctx.apply_ws_policy(&policy).await {
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(403)
+                .await
+                .set_response_body(Vec::new())
+                .await
+                .send_once()
+                .await;
+            return;
+        }
         self.run_pre_upgrade_hook(ctx, &mut lifecycle).await;
         if lifecycle.is_abort() {
             return;
@@ -863,14 +3124,266 @@ impl Server {
     /// Starts the server, binds to the configured address, and begins listening for connections.
     ///
     /// This is the main entry point to launch the server. It will initialize the panic hook,
-    /// create a TCP listener, and then enter the connection acceptance loop.
+    /// bind every configured listener, and then accept connections on all of them until
+    /// they stop.
     ///
     /// # Returns
     ///
     /// Returns a `ServerResult` which will be an error if the server fails to start.
     pub async fn run(&self) -> ServerResult<()> {
         self.init_panic_hook().await;
-        let tcp_listener: TcpListener = self.create_tcp_listener().await?;
-        self.accept_connections(&tcp_listener).await
+        let mut accept_tasks: JoinSetServerResult = JoinSet::new();
+        let listeners: VecServerListener = self.create_listeners().await?;
+        #[cfg(unix)]
+        let mut socket_file: OptionUnixSocketFile = match self.get_config().await.get_unix_socket()
+        {
+            Some(path) => UnixSocketFile::new(path).await.ok(),
+            None => None,
+        };
+        #[cfg(unix)]
+        self.get_write().await.set_listener_fds(
+            listeners
+                .iter()
+                .map(ServerListener::get_fd_and_tag)
+                .collect(),
+        );
+        for listener in listeners {
+            let server: Server = self.clone();
+            accept_tasks.spawn(async move { server.accept_listener(listener).await });
+        }
+        #[cfg(unix)]
+        if *self.get_read().await.get_listen_inherited() {
+            notify_upgrade_ready();
+        }
+        #[cfg(unix)]
+        {
+            let result: ServerResult<()> = self
+                .serve_until_upgrade(&mut accept_tasks, &mut socket_file)
+                .await;
+            self.get_write().await.get_mut_listener_fds().clear();
+            result
+        }
+        #[cfg(not(unix))]
+        Self::join_accept_tasks(&mut accept_tasks).await
+    }
+
+    /// Waits for every accept task to stop.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut JoinSetServerResult` - The accept tasks.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<()>` - The first error an accept task stopped with, if any.
+    async fn join_accept_tasks(accept_tasks: &mut JoinSetServerResult) -> ServerResult<()> {
+        while let Some(result) = accept_tasks.join_next().await {
+            result.map_err(|err| ServerError::Unknown(err.to_string()))??;
+        }
+        Ok(())
+    }
+
+    /// Serves until the accept tasks stop or the listeners were handed over to a new process.
+    ///
+    /// After an upgrade the accept tasks are stopped and the in-flight connections are
+    /// drained, bounded by the drain timeout.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut JoinSetServerResult` - The accept tasks.
+    /// - `&mut OptionUnixSocketFile` - The socket file, which is kept for the new process
+    ///   after an upgrade.
+    ///
+    /// # Returns
+    ///
+    /// - `ServerResult<()>` - The first error an accept task stopped with, if any.
+    #[cfg(unix)]
+    async fn serve_until_upgrade(
+        &self,
+        accept_tasks: &mut JoinSetServerResult,
+        socket_file: &mut OptionUnixSocketFile,
+    ) -> ServerResult<()> {
+        let config: ServerConfig = self.get_config().await;
+        let upgrade_notify: ArcNotify = self.get_read().await.get_upgrade_notify().clone();
+        let mut signal_tasks: JoinSet<()> = JoinSet::new();
+        if *config.get_upgrade_signal() {
+            let mut upgrade_signal: Signal = unix_signal(SignalKind::user_defined2())
+                .map_err(|err| ServerError::Upgrade(err.to_string()))?;
+            let server: Server = self.clone();
+            signal_tasks.spawn(async move {
+                while upgrade_signal.recv().await.is_some() {
+                    let _ = server.upgrade().await;
+                }
+            });
+        }
+        tokio::select! {
+            result = Self::join_accept_tasks(accept_tasks) => result,
+            _ = upgrade_notify.notified() => {
+                if let Some(socket_file) = socket_file {
+                    socket_file.keep();
+                }
+                accept_tasks.shutdown().await;
+                signal_tasks.shutdown().await;
+                self.drain_connections(*config.get_upgrade_drain_timeout()).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Waits until no connection is in flight anymore.
+    ///
+    /// The waiter is registered before the counter is checked, so a connection that
+    /// ends in between still wakes it.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The longest time to wait.
+    #[cfg(unix)]
+    async fn drain_connections(&self, timeout: Duration) {
+        let (connections, drained): (ArcAtomicUsize, ArcNotify) = {
+            let inner: RwLockReadGuardServerInner = self.get_read().await;
+            (
+                inner.get_connections().clone(),
+                inner.get_connections_drained().clone(),
+            )
+        };
+        let _ = tokio::time::timeout(timeout, async {
+            loop {
+                let notified: Notified<'_> = drained.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if connections.load(Ordering::Acquire) == 0 {
+                    break;
+                }
+                notified.await;
+            }
+        })
+        .await;
+    }
+}
+
+/// Implementation of methods for `InheritedListener` structure.
+impl InheritedListener {
+    /// Checks whether the listener is bound to an address.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The address, which only matches if it is a literal socket address.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the listener is a TCP listener bound to the address.
+    pub(crate) fn is_bound_to_addr(&self, addr: &str) -> bool {
+        let Ok(addr) = addr.parse::<SocketAddr>() else {
+            return false;
+        };
+        match self.socket.as_ref() {
+            InheritedSocket::Tcp(listener) => listener.local_addr().ok() == Some(addr),
+            #[cfg(unix)]
+            InheritedSocket::Unix(_) => false,
+        }
+    }
+
+    /// Checks whether the listener is bound to a socket file.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path` - The path of the socket file.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the listener is a Unix domain socket listener bound to the path.
+    #[cfg(unix)]
+    pub(crate) fn is_bound_to_path(&self, path: &Path) -> bool {
+        match self.socket.as_ref() {
+            InheritedSocket::Unix(listener) => listener
+                .local_addr()
+                .ok()
+                .and_then(|addr| addr.as_pathname().map(|bound: &Path| bound == path))
+                .unwrap_or_default(),
+            InheritedSocket::Tcp(_) => false,
+        }
+    }
+
+    /// Registers a duplicate of the listener with the runtime.
+    ///
+    /// # Arguments
+    ///
+    /// - `OptionString` - The tag of the listener.
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<ServerListener>` - The listener, or the error that prevented duplicating it.
+    pub(crate) fn to_server_listener(&self, tag: OptionString) -> io::Result<ServerListener> {
+        match self.socket.as_ref() {
+            InheritedSocket::Tcp(listener) => {
+                let listener: StdTcpListener = listener.try_clone()?;
+                listener.set_nonblocking(true)?;
+                Ok(ServerListener::Tcp(TcpListener::from_std(listener)?, tag))
+            }
+            #[cfg(unix)]
+            InheritedSocket::Unix(listener) => {
+                let listener: StdUnixListener = listener.try_clone()?;
+                listener.set_nonblocking(true)?;
+                let path: PathBuf = listener
+                    .local_addr()?
+                    .as_pathname()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default();
+                Ok(ServerListener::Unix(
+                    UnixListener::from_std(listener)?,
+                    path,
+                ))
+            }
+        }
+    }
+}
+
+/// Implementation of methods for `ServerListener` enum.
+#[cfg(unix)]
+impl ServerListener {
+    /// Retrieves the file descriptor and the tag of the listener.
+    ///
+    /// # Returns
+    ///
+    /// - `(RawFd, OptionString)` - The file descriptor and the tag, if any.
+    pub(crate) fn get_fd_and_tag(&self) -> (RawFd, OptionString) {
+        match self {
+            Self::Tcp(listener, tag) => (listener.as_raw_fd(), tag.clone()),
+            Self::Unix(listener, _) => (listener.as_raw_fd(), None),
+        }
+    }
+}
+
+/// Implementation of methods for `ConnectionGuard` structure.
+#[cfg(unix)]
+impl ConnectionGuard {
+    /// Counts a new connection as in flight.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ArcAtomicUsize` - The connection counter of the server.
+    /// - `&ArcNotify` - Woken when the last connection ends.
+    ///
+    /// # Returns
+    ///
+    /// - `ConnectionGuard` - The guard that stops counting the connection when dropped.
+    pub(crate) fn new(connections: &ArcAtomicUsize, drained: &ArcNotify) -> Self {
+        connections.fetch_add(1, Ordering::AcqRel);
+        Self {
+            connections: connections.clone(),
+            drained: drained.clone(),
+        }
+    }
+}
+
+/// Implementation of `Drop` trait for `ConnectionGuard`.
+#[cfg(unix)]
+impl Drop for ConnectionGuard {
+    /// Stops counting the connection as in flight and wakes a draining server if it was the last one.
+    fn drop(&mut self) {
+        if self.connections.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.drained.notify_waiters();
+        }
     }
 }
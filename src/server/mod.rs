@@ -1,6 +1,17 @@
+pub(crate) mod r#const;
+pub(crate) mod r#enum;
+#[cfg(any(unix, feature = "tls", feature = "http2"))]
+pub(crate) mod r#fn;
 pub(crate) mod r#impl;
 pub(crate) mod r#struct;
 pub(crate) mod r#type;
 
 pub use r#struct::*;
 pub use r#type::*;
+
+pub(crate) use r#enum::*;
+
+#[cfg(any(unix, feature = "tls", feature = "http2"))]
+pub(crate) use r#const::*;
+#[cfg(any(unix, feature = "tls", feature = "http2"))]
+pub(crate) use r#fn::*;
@@ -18,6 +18,12 @@ pub struct ServerInner {
     #[get_mut(pub(super))]
     #[set(pub(super))]
     pub(super) route: RouteMatcher,
+    /// The routing components for virtual hosts, each bound to a host pattern.
+    /// They are consulted in registration order before the host-agnostic routes.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) host_route: VecHostPatternRouteMatcher,
     /// A collection of middleware functions that are executed for every incoming request
     /// before it is passed to the corresponding route handler.
     #[debug(skip)]
@@ -45,6 +51,87 @@ pub struct ServerInner {
     #[get_mut(pub(super))]
     #[set(pub(super))]
     pub(super) connected_hook: VecArcFnPinBoxSendSync,
+    /// A collection of hooks executed after a WebSocket connection has been closed.
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) ws_closed_hook: VecArcFnPinBoxSendSync,
+    /// Heartbeat settings that override the server-level setting for matching WebSocket routes.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) ws_route_heartbeat: VecRoutePatternWebSocketHeartbeat,
+    /// Upgrade policies that override the server-level policy for matching WebSocket routes.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) ws_route_policy: VecRoutePatternWebSocketPolicy,
+    /// The broadcast hub shared by all WebSocket connections of the server.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) ws_hub: WebSocketHub,
+    /// The acceptor that performs the TLS handshake of accepted connections, if TLS is enabled.
+    #[cfg(feature = "tls")]
+    #[debug(skip)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) tls: OptionTlsAcceptor,
+    /// The resolver that selects the certificate of each TLS handshake, if TLS is enabled.
+    #[cfg(feature = "tls")]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) tls_resolver: OptionTlsCertResolver,
+    /// The client certificate verification of TLS handshakes, if enabled.
+    #[cfg(feature = "tls")]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) tls_client_auth: OptionTlsClientAuth,
+    /// The pre-opened listeners the server accepts connections on, such as ones passed by a parent process.
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) inherited_listeners: VecInheritedListener,
+    /// The file descriptors and tags of the listeners while the server runs.
+    #[cfg(unix)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) listener_fds: VecRawFdOptionString,
+    /// Whether a new process is being started to take over the listeners.
+    #[cfg(unix)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) upgrading: bool,
+    /// Whether the server serves the listeners passed through the environment.
+    #[cfg(unix)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) listen_inherited: bool,
+    /// The number of connections being served.
+    #[cfg(unix)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) connections: ArcAtomicUsize,
+    /// Wakes a draining server once the last connection being served has ended.
+    #[cfg(unix)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) connections_drained: ArcNotify,
+    /// Wakes the running server once a new process has taken over its listeners.
+    #[cfg(unix)]
+    #[get(pub(super))]
+    #[get_mut(pub(super))]
+    #[set(pub(super))]
+    pub(super) upgrade_notify: ArcNotify,
     /// A route matcher used to specify routes for which the default HTTP hook should be disabled.
     #[get(pub(super))]
     #[get_mut(pub(super))]
@@ -84,9 +171,57 @@ pub struct Server(
 #[derive(Clone, CustomDebug, DisplayDebug)]
 pub(crate) struct HandlerState<'a> {
     /// A reference to the underlying network stream for the connection.
-    /// This provides access to the raw TCP stream for reading and writing data.
-    pub(super) stream: &'a ArcRwLockStream,
+    /// This provides access to the connection for reading and writing data.
+    pub(super) stream: &'a ConnectionStream,
     /// A reference to the context of the current request.
     /// This contains request-specific information, such as headers, method, and URI.
     pub(super) ctx: &'a Context,
 }
+
+/// An address the server listens on, optionally tagged to tell listeners apart.
+///
+/// The tag of the listener that accepted a connection is available through
+/// `Context::get_listener_tag`.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct ListenAddr {
+    /// The address to bind, such as `0.0.0.0:80` or `[::1]:8080`.
+    #[get(pub)]
+    pub(super) addr: String,
+    /// The tag of the listener, such as `admin`.
+    #[get(pub)]
+    pub(super) tag: OptionString,
+}
+
+/// A pre-opened TCP listener handed to the server instead of an address to bind.
+#[derive(Clone, CustomDebug, Data, DisplayDebug)]
+pub(crate) struct InheritedListener {
+    /// The listening socket, which is duplicated every time the server starts.
+    pub(super) socket: ArcInheritedSocket,
+    /// The tag of the listener, such as the name systemd passes in `LISTEN_FDNAMES`.
+    pub(super) tag: OptionString,
+}
+
+/// The listeners passed to this process through its environment.
+#[cfg(unix)]
+#[derive(CustomDebug, Getter, DisplayDebug)]
+pub(crate) struct InheritedEnv {
+    /// The listeners of systemd socket activation, until the first server claims them.
+    #[get(pub(crate))]
+    pub(super) activated: StdMutexVecInheritedListener,
+    /// The listeners handed over by an upgrade, tagged with the names passed along with them.
+    #[get(pub(crate))]
+    pub(super) upgraded: VecInheritedListener,
+    /// The write end of the readiness pipe, if this process was started by an upgrade.
+    #[get(pub(crate))]
+    pub(super) ready: StdMutexOptionStdFile,
+}
+
+/// Counts a connection as in flight for as long as it is alive.
+#[cfg(unix)]
+#[derive(CustomDebug)]
+pub(crate) struct ConnectionGuard {
+    /// The connection counter of the server.
+    pub(super) connections: ArcAtomicUsize,
+    /// Woken when the counter drops to zero.
+    pub(super) drained: ArcNotify,
+}
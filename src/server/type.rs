@@ -4,6 +4,10 @@ use crate::*;
 /// This is commonly used throughout the server's public-facing API.
 pub type ServerResult<T> = Result<T, ServerError>;
 
+/// A type alias for an optional `Server`.
+/// This is used by contexts that may or may not belong to a running server.
+pub type OptionServer = Option<Server>;
+
 /// A type alias for a thread-safe, reference-counted read-write lock over `ServerInner`.
 /// This is the core mechanism for sharing server state across threads.
 pub type ArcRwLockServerInner = ArcRwLock<ServerInner>;
@@ -19,3 +23,55 @@ pub type RwLockWriteGuardServerInner<'a> = RwLockWriteGuard<'a, ServerInner>;
 /// A type alias for a `Result` that returns a `JoinError` on failure.
 /// This is used when waiting for asynchronous tasks to complete.
 pub type ResultJoinError<T> = Result<T, JoinError>;
+
+/// A type alias for a list of `ListenAddr`.
+/// This is used to hold the addresses the server listens on.
+pub type VecListenAddr = Vec<ListenAddr>;
+
+/// A type alias for a list of bound `ServerListener`s.
+/// This is used while the server accepts connections.
+pub(crate) type VecServerListener = Vec<ServerListener>;
+
+/// A type alias for a set of tasks that each accept connections on one listener.
+pub(crate) type JoinSetServerResult = JoinSet<ServerResult<()>>;
+
+/// A type alias for a shared `InheritedSocket`.
+/// This is used to keep a pre-opened listener that can be duplicated on every start.
+pub(crate) type ArcInheritedSocket = Arc<InheritedSocket>;
+
+/// A type alias for a thread-safe, shared connection counter.
+/// This is used to wait for in-flight connections before an upgraded server returns.
+#[cfg(unix)]
+pub(crate) type ArcAtomicUsize = Arc<AtomicUsize>;
+
+/// A type alias for a shared `Notify`.
+/// This is used to tell a running server that it has been upgraded.
+#[cfg(unix)]
+pub(crate) type ArcNotify = Arc<Notify>;
+
+/// A type alias for a list of `InheritedListener`s.
+/// This is used to hold the pre-opened listeners of the server.
+pub(crate) type VecInheritedListener = Vec<InheritedListener>;
+
+/// A type alias for a list of file descriptors.
+/// This is used while reading the listeners passed through the environment.
+#[cfg(unix)]
+pub(crate) type VecRawFd = Vec<RawFd>;
+
+/// A type alias for a list of file descriptors and the names passed with them.
+/// This is used while reading the listeners passed through systemd socket activation.
+#[cfg(unix)]
+pub(crate) type VecRawFdOptionString = Vec<(RawFd, OptionString)>;
+
+/// A type alias for an optional file.
+/// This is used for the readiness pipe of an upgrade.
+#[cfg(unix)]
+pub(crate) type OptionStdFile = Option<StdFile>;
+
+/// A type alias for a lock around the readiness pipe of an upgrade.
+#[cfg(unix)]
+pub(crate) type StdMutexOptionStdFile = StdMutex<OptionStdFile>;
+
+/// A type alias for the listeners of systemd socket activation not claimed by a server yet.
+#[cfg(unix)]
+pub(crate) type StdMutexVecInheritedListener = StdMutex<VecInheritedListener>;
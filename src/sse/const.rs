@@ -0,0 +1,34 @@
+use crate::*;
+
+/// The field carrying the event id.
+pub(crate) const SSE_FIELD_ID: &str = "id";
+
+/// The field carrying the event type.
+pub(crate) const SSE_FIELD_EVENT: &str = "event";
+
+/// The field carrying one line of event data.
+pub(crate) const SSE_FIELD_DATA: &str = "data";
+
+/// The field carrying the reconnection delay in milliseconds.
+pub(crate) const SSE_FIELD_RETRY: &str = "retry";
+
+/// The separator between a field name and its value.
+pub(crate) const SSE_FIELD_SEPARATOR: &str = ": ";
+
+/// The line terminator used in the event stream.
+pub(crate) const SSE_LINE_END: &str = "\n";
+
+/// The text of the comment sent to keep idle streams open.
+pub(crate) const SSE_KEEP_ALIVE_COMMENT: &str = "keep-alive";
+
+/// The request header carrying the id of the last event seen by a reconnecting client.
+pub(crate) const LAST_EVENT_ID: &str = "last-event-id";
+
+/// The response header asking reverse proxies not to buffer the stream.
+pub(crate) const X_ACCEL_BUFFERING: &str = "x-accel-buffering";
+
+/// The value disabling proxy buffering.
+pub(crate) const X_ACCEL_BUFFERING_NO: &str = "no";
+
+/// The default interval between keep-alive comments on an idle stream.
+pub(crate) const DEFAULT_SSE_KEEP_ALIVE: Duration = Duration::from_secs(15);
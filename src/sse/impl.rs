@@ -0,0 +1,135 @@
+use crate::*;
+
+impl SseEvent {
+    /// Creates an empty event.
+    ///
+    /// # Returns
+    ///
+    /// - `SseEvent` - The event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the event id.
+    ///
+    /// # Arguments
+    ///
+    /// - `I` - The id implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `SseEvent` - The modified event.
+    pub fn with_id<I: ToString>(mut self, id: I) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Sets the event type.
+    ///
+    /// # Arguments
+    ///
+    /// - `E` - The event type implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `SseEvent` - The modified event.
+    pub fn with_event<E: ToString>(mut self, event: E) -> Self {
+        self.event = Some(event.to_string());
+        self
+    }
+
+    /// Sets the event data, which may span several lines.
+    ///
+    /// # Arguments
+    ///
+    /// - `D` - The data implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `SseEvent` - The modified event.
+    pub fn with_data<D: ToString>(mut self, data: D) -> Self {
+        self.data = Some(data.to_string());
+        self
+    }
+
+    /// Sets the reconnection delay the client should use.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The delay, sent in whole milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// - `SseEvent` - The modified event.
+    pub fn with_retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets a comment, which clients ignore.
+    ///
+    /// # Arguments
+    ///
+    /// - `C` - The comment implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `SseEvent` - The modified event.
+    pub fn with_comment<C: ToString>(mut self, comment: C) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Appends one field line to the encoded event.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut String` - The encoded event.
+    /// - `&str` - The field name, empty for a comment.
+    /// - `&str` - The field value, which must not contain line breaks.
+    fn push_field(output: &mut String, name: &str, value: &str) {
+        output.push_str(name);
+        output.push_str(SSE_FIELD_SEPARATOR);
+        output.push_str(value);
+        output.push_str(SSE_LINE_END);
+    }
+
+    /// Encodes the event in the `text/event-stream` format.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The encoded event, terminated by an empty line.
+    pub fn encode(&self) -> String {
+        let single_line = |value: &str| -> String {
+            value
+                .chars()
+                .filter(|ch: &char| !matches!(ch, '\r' | '\n' | '\0'))
+                .collect()
+        };
+        let mut output: String = String::new();
+        if let Some(comment) = &self.comment {
+            for line in comment.lines() {
+                Self::push_field(&mut output, EMPTY_STR, line);
+            }
+        }
+        if let Some(id) = &self.id {
+            Self::push_field(&mut output, SSE_FIELD_ID, &single_line(id));
+        }
+        if let Some(event) = &self.event {
+            Self::push_field(&mut output, SSE_FIELD_EVENT, &single_line(event));
+        }
+        if let Some(retry) = &self.retry {
+            Self::push_field(&mut output, SSE_FIELD_RETRY, &retry.as_millis().to_string());
+        }
+        if let Some(data) = &self.data {
+            let data: String = data
+                .replace("\r\n", SSE_LINE_END)
+                .replace('\r', SSE_LINE_END);
+            for line in data.split(SSE_LINE_END) {
+                Self::push_field(&mut output, SSE_FIELD_DATA, line);
+            }
+        }
+        output.push_str(SSE_LINE_END);
+        output
+    }
+}
@@ -0,0 +1,35 @@
+use crate::*;
+
+/// A single Server-Sent Events message.
+///
+/// Every field is optional. Multi-line data is split into several `data` lines, and
+/// line breaks are removed from the id and event type so they cannot start a new field.
+#[derive(Clone, Default, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The event id, reported back by reconnecting clients in `Last-Event-ID`.
+    #[get(pub)]
+    pub(super) id: OptionString,
+    /// The event type dispatched by the client.
+    #[get(pub)]
+    pub(super) event: OptionString,
+    /// The event data.
+    #[get(pub)]
+    pub(super) data: OptionString,
+    /// The reconnection delay the client should use.
+    #[get(pub)]
+    pub(super) retry: OptionDuration,
+    /// A comment, which clients ignore.
+    #[get(pub)]
+    pub(super) comment: OptionString,
+}
+
+/// The Server-Sent Events state of a connection.
+#[derive(Clone, Default, CustomDebug, Data, DisplayDebug)]
+pub(crate) struct SseState {
+    /// Whether the event stream has been started.
+    pub(super) started: bool,
+    /// When the last event or comment was written.
+    pub(super) last_sent: OptionInstant,
+    /// The task sending keep-alive comments, if any.
+    pub(super) keep_alive: OptionAbortHandle,
+}
@@ -0,0 +1,4 @@
+use crate::*;
+
+/// A handle to a running keep-alive task.
+pub(crate) type OptionAbortHandle = Option<AbortHandle>;
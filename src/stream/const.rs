@@ -0,0 +1,17 @@
+/// The size limit of a request line and its header fields.
+pub(crate) const HTTP_MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// The status code responded to a request whose head exceeds `HTTP_MAX_HEAD_SIZE`.
+pub(crate) const HTTP_HEAD_TOO_LARGE_STATUS_CODE: usize = 431;
+
+/// The status code responded to a request whose body length is ambiguous.
+pub(crate) const HTTP_BAD_REQUEST_STATUS_CODE: usize = 400;
+
+/// The number of parts of a valid request line.
+pub(crate) const HTTP_REQUEST_LINE_PARTS: usize = 3;
+
+/// The default size limit of a request body.
+pub(crate) const DEFAULT_HTTP_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The status code responded to a request whose body exceeds the body size limit.
+pub(crate) const HTTP_BODY_TOO_LARGE_STATUS_CODE: usize = 413;
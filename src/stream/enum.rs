@@ -0,0 +1,10 @@
+use crate::*;
+
+/// The reasons reading a request from a connection fails.
+#[derive(Clone, CustomDebug, DisplayDebug)]
+pub(crate) enum RequestReadError {
+    /// The request could not be read or parsed.
+    Read(RequestError),
+    /// The request exceeds a size limit or is malformed and is answered with the given status code.
+    Reject(usize),
+}
@@ -0,0 +1,116 @@
+use crate::*;
+
+/// Finds the end of a request head.
+///
+/// The head ends with the first blank line after the request line, the same way
+/// `Request` reads header fields.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The bytes received so far.
+///
+/// # Returns
+///
+/// - `OptionUsize` - The length of the head including the blank line, or None if it is incomplete.
+pub(crate) fn find_head_end(buffer: &[u8]) -> OptionUsize {
+    let mut start: usize = 0;
+    while let Some(index) = buffer[start..].iter().position(|byte: &u8| *byte == b'\n') {
+        let end: usize = start + index + 1;
+        if start > 0 && buffer[start..end].trim_ascii().is_empty() {
+            return Some(end);
+        }
+        start = end;
+    }
+    None
+}
+
+/// Reads the announced body length of a request head.
+///
+/// Header fields are split the same way `Request` splits them, so both agree on
+/// where the body ends. Chunked bodies are not supported, so any `Transfer-Encoding`
+/// makes the length ambiguous, as does a `Content-Length` that is not a plain decimal
+/// number or that differs from an earlier one.
+///
+/// # Arguments
+///
+/// - `&str` - The header lines of the request head.
+///
+/// # Returns
+///
+/// - `OptionUsize` - The body length, 0 if none is announced, or None if it is ambiguous.
+pub(crate) fn head_content_length(headers: &str) -> OptionUsize {
+    let mut content_length: OptionUsize = None;
+    for line in headers.lines() {
+        let Some((key, value)) = line.trim().split_once(COLON_SPACE_SYMBOL) else {
+            continue;
+        };
+        let key: &str = key.trim();
+        if key.eq_ignore_ascii_case(TRANSFER_ENCODING) {
+            return None;
+        }
+        if !key.eq_ignore_ascii_case(CONTENT_LENGTH) {
+            continue;
+        }
+        let value: &str = value.trim();
+        if value.is_empty() || !value.bytes().all(|byte: u8| byte.is_ascii_digit()) {
+            return None;
+        }
+        let length: usize = value.parse().ok()?;
+        if content_length.is_some_and(|content_length: usize| content_length != length) {
+            return None;
+        }
+        content_length = Some(length);
+    }
+    Some(content_length.unwrap_or_default())
+}
+
+/// Decodes a request whose head and body have already been received.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The request head followed by its body.
+/// - `usize` - The buffer size for reading.
+///
+/// # Returns
+///
+/// - `RequestReaderHandleResult` - The parsed request or an error.
+pub(crate) async fn decode_request(
+    mut raw: &[u8],
+    buffer_size: usize,
+) -> RequestReaderHandleResult {
+    Request::http_from_reader(&mut raw, buffer_size).await
+}
+
+/// Converts an error reading from a connection into a request error.
+///
+/// # Arguments
+///
+/// - `io::Error` - The read error.
+///
+/// # Returns
+///
+/// - `RequestError` - `ClientDisconnected` if the peer reset the connection, the error message otherwise.
+pub(crate) fn read_error(err: io::Error) -> RequestError {
+    match err.kind() {
+        io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted => {
+            RequestError::ClientDisconnected
+        }
+        _ => RequestError::Request(err.to_string()),
+    }
+}
+
+/// Converts an error writing to a connection into a response error.
+///
+/// # Arguments
+///
+/// - `io::Error` - The write error.
+///
+/// # Returns
+///
+/// - `ResponseError` - `ConnectionClosed` if the connection accepts no more bytes, the error message otherwise.
+pub(crate) fn write_error(err: io::Error) -> ResponseError {
+    match err.kind() {
+        io::ErrorKind::WriteZero => ResponseError::ConnectionClosed,
+        _ => ResponseError::Response(err.to_string()),
+    }
+}
@@ -0,0 +1,407 @@
+use crate::*;
+
+/// Implementation of `ConnectionIo` trait for every asynchronous byte stream.
+impl<T> ConnectionIo for T where T: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+/// Implementation of methods for `ConnectionReader` structure.
+impl ConnectionReader {
+    /// Reads more bytes into the buffer.
+    ///
+    /// The read is cancel safe: if the future is dropped, no received bytes are lost.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The number of bytes to read at most.
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<usize>` - The number of bytes read, 0 once the peer closed the connection.
+    async fn fill(&mut self, size: usize) -> io::Result<usize> {
+        let Some(io) = self.io.as_mut() else {
+            return Ok(0);
+        };
+        self.buffer.reserve(size.max(1));
+        io.read_buf(&mut self.buffer).await
+    }
+
+    /// Reads the next request.
+    ///
+    /// The head is read up to `HTTP_MAX_HEAD_SIZE` bytes and the body up to its
+    /// announced length. A head whose body length is ambiguous is rejected, so the
+    /// connection never disagrees with a proxy in front of it about where the next
+    /// request starts. Bytes following the request are kept for the next call.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The buffer size for reading.
+    /// - `usize` - The size limit of the body.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultReceivedRequestRequestReadError` - The request and its target, or the reason reading failed.
+    async fn read_request(
+        &mut self,
+        buffer_size: usize,
+        max_body_size: usize,
+    ) -> ResultReceivedRequestRequestReadError {
+        let head_len: usize = loop {
+            if let Some(head_len) = find_head_end(&self.buffer) {
+                break head_len;
+            }
+            if self.buffer.len() > HTTP_MAX_HEAD_SIZE {
+                return Err(RequestReadError::Reject(HTTP_HEAD_TOO_LARGE_STATUS_CODE));
+            }
+            self.fill_or_disconnect(buffer_size).await?;
+        };
+        if head_len > HTTP_MAX_HEAD_SIZE {
+            return Err(RequestReadError::Reject(HTTP_HEAD_TOO_LARGE_STATUS_CODE));
+        }
+        let head: String = String::from_utf8_lossy(&self.buffer[..head_len]).into_owned();
+        let (request_line, headers) = head.split_once(BR).unwrap_or((&head, ""));
+        let parts: VecStrRef = request_line.split_whitespace().collect();
+        if parts.len() < HTTP_REQUEST_LINE_PARTS {
+            return Err(RequestReadError::Read(
+                RequestError::InvalidHttpRequestPartsLength(parts.len()),
+            ));
+        }
+        let target: String = parts[1].to_owned();
+        let content_length: usize = head_content_length(headers)
+            .ok_or(RequestReadError::Reject(HTTP_BAD_REQUEST_STATUS_CODE))?;
+        if content_length > max_body_size {
+            return Err(RequestReadError::Reject(HTTP_BODY_TOO_LARGE_STATUS_CODE));
+        }
+        let request_len: usize = head_len + content_length;
+        while self.buffer.len() < request_len {
+            self.fill_or_disconnect(buffer_size).await?;
+        }
+        let request: Request = decode_request(&self.buffer[..request_len], buffer_size)
+            .await
+            .map_err(RequestReadError::Read)?;
+        self.buffer.drain(..request_len);
+        Ok(ReceivedRequest { request, target })
+    }
+
+    /// Reads more bytes into the buffer, failing if the peer closed the connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The number of bytes to read at most.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), RequestReadError>` - Ok once bytes were read, or the reason reading failed.
+    async fn fill_or_disconnect(&mut self, size: usize) -> Result<(), RequestReadError> {
+        match self.fill(size).await {
+            Ok(0) => Err(RequestReadError::Read(RequestError::ClientDisconnected)),
+            Ok(_) => Ok(()),
+            Err(err) => Err(RequestReadError::Read(read_error(err))),
+        }
+    }
+}
+
+/// Implementation of methods for `ConnectionWriter` structure.
+impl ConnectionWriter {
+    /// Writes all bytes and flushes them to the transport.
+    ///
+    /// On an HTTP/2 stream the bytes are sent as body data of the response.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The bytes to write.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the write operation.
+    async fn write_all(&mut self, data: &[u8]) -> ResponseResult {
+        #[cfg(feature = "http2")]
+        if let Some(http2) = self.http2.as_mut() {
+            return http2.send_data(data).await;
+        }
+        let Some(io) = self.io.as_mut() else {
+            return Err(ResponseError::ConnectionClosed);
+        };
+        io.write_all(data).await.map_err(write_error)?;
+        io.flush().await.map_err(write_error)
+    }
+}
+
+/// Implementation of methods for `RequestReadError` enum.
+impl RequestReadError {
+    /// Converts the error into the error type of the public request readers.
+    ///
+    /// # Returns
+    ///
+    /// - `RequestError` - The read error, or a description of the exceeded limit.
+    pub(crate) fn into_request_error(self) -> RequestError {
+        match self {
+            Self::Read(err) => err,
+            Self::Reject(status_code) => RequestError::Request(format!(
+                "request is rejected and answered with {}",
+                status_code
+            )),
+        }
+    }
+}
+
+/// Implementation of methods for `ReceivedRequest` structure.
+impl ReceivedRequest {
+    /// Splits the received request into its parts.
+    ///
+    /// # Returns
+    ///
+    /// - `(Request, String)` - The request and its target as sent by the client.
+    pub(crate) fn into_parts(self) -> (Request, String) {
+        (self.request, self.target)
+    }
+}
+
+/// Implementation of methods for `ConnectionStream` structure.
+impl ConnectionStream {
+    /// Creates the stream that answers a single HTTP/2 request.
+    ///
+    /// Nothing can be read from the stream, as the request was received already.
+    ///
+    /// # Arguments
+    ///
+    /// - `Http2SendResponse` - The handle that answers the request.
+    /// - `bool` - Whether the request is a `HEAD` request.
+    ///
+    /// # Returns
+    ///
+    /// - `ConnectionStream` - The stream.
+    #[cfg(feature = "http2")]
+    pub(crate) fn http2(respond: Http2SendResponse, head_request: bool) -> Self {
+        Self {
+            reader: arc_mutex(ConnectionReader {
+                io: None,
+                buffer: Vec::new(),
+            }),
+            writer: arc_mutex(ConnectionWriter {
+                io: None,
+                http2: Some(Http2ResponseWriter::new(respond, head_request)),
+            }),
+        }
+    }
+
+    /// Creates the stream of a connection whose first bytes have already been read.
+    ///
+    /// # Arguments
+    ///
+    /// - `BoxConnectionIo` - The transport of the connection.
+    /// - `Vec<u8>` - The bytes already read from the transport.
+    ///
+    /// # Returns
+    ///
+    /// - `ConnectionStream` - The stream.
+    pub(crate) fn with_buffer(io: BoxConnectionIo, buffer: Vec<u8>) -> Self {
+        let (reader, writer) = split(io);
+        Self {
+            reader: arc_mutex(ConnectionReader {
+                io: Some(reader),
+                buffer,
+            }),
+            writer: arc_mutex(ConnectionWriter {
+                io: Some(writer),
+                #[cfg(feature = "http2")]
+                http2: None,
+            }),
+        }
+    }
+
+    /// Reads the next HTTP request.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The buffer size for reading.
+    /// - `usize` - The size limit of the body.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultReceivedRequestRequestReadError` - The request and its target, or the reason reading failed.
+    pub(crate) async fn read_request(
+        &self,
+        buffer_size: usize,
+        max_body_size: usize,
+    ) -> ResultReceivedRequestRequestReadError {
+        self.reader
+            .lock()
+            .await
+            .read_request(buffer_size, max_body_size)
+            .await
+    }
+
+    /// Checks whether the connection starts with the given bytes.
+    ///
+    /// The bytes are read into the buffer, so a request can still be read afterwards.
+    /// Waiting for the first byte is not limited, but once it arrived the remaining
+    /// bytes have to follow within the timeout.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The expected first bytes.
+    /// - `Duration` - How long the remaining bytes may take after the first one.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionBool` - Whether the connection starts with the bytes, or None if the
+    ///   connection stalled before it was clear.
+    #[cfg(feature = "http2")]
+    pub(crate) async fn starts_with(&self, prefix: &[u8], timeout: Duration) -> OptionBool {
+        let mut reader: MutexGuard<ConnectionReader> = self.reader.lock().await;
+        let mut deadline: OptionInstant = None;
+        loop {
+            let len: usize = reader.buffer.len().min(prefix.len());
+            if reader.buffer[..len] != prefix[..len] {
+                return Some(false);
+            }
+            if len == prefix.len() {
+                return Some(true);
+            }
+            if len > 0 && deadline.is_none() {
+                deadline = Some(Instant::now() + timeout);
+            }
+            let filled: io::Result<usize> = match deadline {
+                Some(deadline) => {
+                    tokio::time::timeout_at(deadline, reader.fill(prefix.len() - len))
+                        .await
+                        .ok()?
+                }
+                None => reader.fill(prefix.len() - len).await,
+            };
+            if !matches!(filled, Ok(size) if size > 0) {
+                return Some(false);
+            }
+        }
+    }
+
+    /// Moves received bytes into a buffer, reading from the transport if none are pending.
+    ///
+    /// The read is cancel safe: if the future is dropped, no received bytes are lost.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut Vec<u8>` - The buffer to append to.
+    /// - `usize` - The number of bytes to read at most.
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<usize>` - The number of bytes appended, 0 once the peer closed the connection.
+    pub(crate) async fn read_into(&self, target: &mut Vec<u8>, size: usize) -> io::Result<usize> {
+        let mut reader: MutexGuard<ConnectionReader> = self.reader.lock().await;
+        if reader.buffer.is_empty() {
+            reader.fill(size).await?;
+        }
+        let len: usize = reader.buffer.len();
+        target.append(&mut reader.buffer);
+        Ok(len)
+    }
+
+    /// Takes the transport out of the stream, for example to switch to HTTP/2.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionDetachedConnectionIo` - The bytes received but not consumed yet and the halves
+    ///   of the transport, or None if it was taken already.
+    #[cfg(feature = "http2")]
+    pub(crate) async fn detach(&self) -> OptionDetachedConnectionIo {
+        let mut reader: MutexGuard<ConnectionReader> = self.reader.lock().await;
+        let mut writer: MutexGuard<ConnectionWriter> = self.writer.lock().await;
+        if reader.io.is_none() || writer.io.is_none() {
+            return None;
+        }
+        Some((
+            std::mem::take(&mut reader.buffer),
+            reader.io.take()?,
+            writer.io.take()?,
+        ))
+    }
+
+    /// Checks whether the stream answers a single HTTP/2 request.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if responses are sent as HTTP/2 frames, false otherwise.
+    #[cfg(feature = "http2")]
+    pub(crate) async fn is_http2(&self) -> bool {
+        self.writer.lock().await.http2.is_some()
+    }
+
+    /// Sends a response converted for the HTTP/2 stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `Http2ResponseMessage` - The converted response.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    #[cfg(feature = "http2")]
+    pub(crate) async fn send_http2_response(
+        &self,
+        message: Http2ResponseMessage,
+    ) -> ResponseResult {
+        match self.writer.lock().await.http2.as_mut() {
+            Some(http2) => http2.send_response(message).await,
+            None => Err(ResponseError::ConnectionClosed),
+        }
+    }
+
+    /// Ends the HTTP/2 stream once its request was handled.
+    #[cfg(feature = "http2")]
+    pub(crate) async fn finish_http2(&self) {
+        if let Some(http2) = self.writer.lock().await.http2.as_mut() {
+            http2.finish();
+        }
+    }
+
+    /// Writes bytes to the connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The bytes to write.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the write operation.
+    pub(crate) async fn write_all(&self, data: &[u8]) -> ResponseResult {
+        self.writer.lock().await.write_all(data).await
+    }
+
+    /// Sends HTTP response data over the stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ResponseData` - The response data to send.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - Result indicating success or failure.
+    pub async fn send(&self, data: &ResponseData) -> ResponseResult {
+        self.write_all(data).await
+    }
+
+    /// Sends HTTP response body bytes over the stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ResponseBody` - The response body data.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - Result indicating success or failure.
+    pub async fn send_body(&self, body: &ResponseBody) -> ResponseResult {
+        self.write_all(body).await
+    }
+
+    /// Flushes all buffered data to the stream.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub async fn flush(&self) -> &Self {
+        if let Some(io) = self.writer.lock().await.io.as_mut() {
+            let _ = io.flush().await;
+        }
+        self
+    }
+}
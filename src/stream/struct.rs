@@ -0,0 +1,42 @@
+use crate::*;
+
+/// The stream of a client connection.
+///
+/// It carries a TCP connection, a TLS session or a Unix domain socket connection
+/// alike, or the response side of a single HTTP/2 stream. The read and write sides are locked separately, so a task waiting for
+/// incoming data never holds up the tasks writing to the connection.
+#[derive(Clone, CustomDebug, DisplayDebug)]
+pub struct ConnectionStream {
+    /// The read side of the connection.
+    #[debug(skip)]
+    pub(super) reader: ArcMutexConnectionReader,
+    /// The write side of the connection.
+    #[debug(skip)]
+    pub(super) writer: ArcMutexConnectionWriter,
+}
+
+/// The read side of a connection together with the bytes received but not consumed yet.
+pub(crate) struct ConnectionReader {
+    /// The read half of the transport, or None once it was handed over.
+    pub(super) io: OptionConnectionReadHalf,
+    /// The bytes received but not consumed yet.
+    pub(super) buffer: Vec<u8>,
+}
+
+/// The write side of a connection.
+pub(crate) struct ConnectionWriter {
+    /// The write half of the transport, or None once it was handed over.
+    pub(super) io: OptionConnectionWriteHalf,
+    /// The response side of the HTTP/2 stream the request arrived on, if any.
+    #[cfg(feature = "http2")]
+    pub(super) http2: OptionHttp2ResponseWriter,
+}
+
+/// A request read from a connection.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug)]
+pub(crate) struct ReceivedRequest {
+    /// The parsed request.
+    pub(super) request: Request,
+    /// The request target exactly as sent by the client, including the query string.
+    pub(super) target: String,
+}
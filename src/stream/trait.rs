@@ -0,0 +1,7 @@
+use crate::*;
+
+/// A transport a client connection is served on.
+///
+/// It is implemented for every asynchronous byte stream, such as a TCP connection,
+/// a TLS session or a Unix domain socket connection.
+pub(crate) trait ConnectionIo: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
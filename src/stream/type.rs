@@ -0,0 +1,39 @@
+use crate::*;
+
+/// A type alias for an optional connection stream.
+pub type OptionConnectionStream = Option<ConnectionStream>;
+
+/// A type alias for a boxed connection transport.
+pub(crate) type BoxConnectionIo = Box<dyn ConnectionIo>;
+
+/// A type alias for an optional boxed connection transport.
+pub(crate) type OptionBoxConnectionIo = Option<BoxConnectionIo>;
+
+/// A type alias for the read half of a connection transport.
+pub(crate) type ConnectionReadHalf = ReadHalf<BoxConnectionIo>;
+
+/// A type alias for an optional read half of a connection transport.
+pub(crate) type OptionConnectionReadHalf = Option<ConnectionReadHalf>;
+
+/// A type alias for the write half of a connection transport.
+pub(crate) type ConnectionWriteHalf = WriteHalf<BoxConnectionIo>;
+
+/// A type alias for an optional write half of a connection transport.
+pub(crate) type OptionConnectionWriteHalf = Option<ConnectionWriteHalf>;
+
+/// A type alias for the shared read side of a connection.
+pub(crate) type ArcMutexConnectionReader = ArcMutex<ConnectionReader>;
+
+/// A type alias for the shared write side of a connection.
+pub(crate) type ArcMutexConnectionWriter = ArcMutex<ConnectionWriter>;
+
+/// A type alias for a `Result` that returns a received request or a `RequestReadError`.
+pub(crate) type ResultReceivedRequestRequestReadError = Result<ReceivedRequest, RequestReadError>;
+
+/// A type alias for the bytes still buffered and the halves of a detached transport.
+#[cfg(feature = "http2")]
+pub(crate) type DetachedConnectionIo = (Vec<u8>, ConnectionReadHalf, ConnectionWriteHalf);
+
+/// A type alias for an optional detached transport.
+#[cfg(feature = "http2")]
+pub(crate) type OptionDetachedConnectionIo = Option<DetachedConnectionIo>;
@@ -0,0 +1,273 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+async fn get(port: usize, headers: &str) -> String {
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let request: String = format!(
+        "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}\r\n",
+        headers
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_string())
+        .unwrap_or(response)
+}
+
+#[cfg(test)]
+async fn forwarded_server(port: usize, trusted_proxies: &[&str], headers: ForwardedHeaders) {
+    async fn client_route(ctx: Context) {
+        let body: String = format!(
+            "{} {} {}",
+            ctx.get_client_ip()
+                .await
+                .map(|ip| ip.to_string())
+                .unwrap_or_default(),
+            ctx.get_client_scheme().await,
+            ctx.get_client_host().await
+        );
+        let _ = ctx
+            .set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(200)
+            .await
+            .set_response_header(CONNECTION, CLOSE)
+            .await
+            .set_response_body(body)
+            .await
+            .send()
+            .await;
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    for trusted_proxy in trusted_proxies {
+        server.trusted_proxy(trusted_proxy, headers).await;
+    }
+    server.route("/", client_route).await;
+    tokio::spawn(async move {
+        let _ = server.run().await;
+    });
+}
+
+#[cfg(test)]
+fn hop(ip: &str) -> ForwardedHop {
+    ForwardedHop::new(ip.parse().ok(), None, None)
+}
+
+#[test]
+fn test_ip_cidr() {
+    let block: IpCidr = "10.1.2.3/8".parse().unwrap();
+    assert_eq!(*block.get_network(), "10.0.0.0".parse::<IpAddr>().unwrap());
+    assert!(block.contains(&"10.255.0.1".parse().unwrap()));
+    assert!(block.contains(&"::ffff:10.0.0.1".parse().unwrap()));
+    assert!(!block.contains(&"11.0.0.1".parse().unwrap()));
+    let single: IpCidr = "2001:db8::1".parse().unwrap();
+    assert_eq!(*single.get_prefix_len(), 128);
+    assert!(single.contains(&"2001:db8::1".parse().unwrap()));
+    assert!(!single.contains(&"2001:db8::2".parse().unwrap()));
+    let all: IpCidr = "0.0.0.0/0".parse().unwrap();
+    assert!(all.contains(&"192.0.2.1".parse().unwrap()));
+    assert!(!all.contains(&"::2".parse().unwrap()));
+    assert_eq!(
+        "10.0.0.0/33".parse::<IpCidr>(),
+        Err(ServerError::InvalidTrustedProxy("10.0.0.0/33".to_string()))
+    );
+    assert!("proxy.local".parse::<IpCidr>().is_err());
+}
+
+#[test]
+fn test_parse_forwarded() {
+    let hops: VecForwardedHop = parse_forwarded(
+        "for=192.0.2.60;proto=HTTPS;host=example.com, for=\"[2001:db8:cafe::17]:4711\", for=unknown",
+    );
+    assert_eq!(
+        hops,
+        vec![
+            ForwardedHop::new(
+                "192.0.2.60".parse().ok(),
+                Some("https".to_string()),
+                Some("example.com".to_string())
+            ),
+            hop("2001:db8:cafe::17"),
+            hop("unknown"),
+        ]
+    );
+    assert_eq!(
+        parse_forwarded_node("192.0.2.43:47011"),
+        "192.0.2.43".parse().ok()
+    );
+    assert_eq!(
+        parse_forwarded_node("[2001:db8::1]"),
+        "2001:db8::1".parse().ok()
+    );
+    assert_eq!(parse_forwarded_node("_hidden"), None);
+}
+
+#[test]
+fn test_parse_x_forwarded() {
+    let hops: VecForwardedHop = parse_x_forwarded("203.0.113.9, 10.0.0.2", "https", "");
+    assert_eq!(
+        hops,
+        vec![
+            hop("203.0.113.9"),
+            ForwardedHop::new("10.0.0.2".parse().ok(), Some("https".to_string()), None),
+        ]
+    );
+    assert!(parse_x_forwarded("", "https", "example.com").is_empty());
+}
+
+#[test]
+fn test_resolve_forwarded() {
+    let trusted_proxies: VecIpCidr = vec!["10.0.0.0/8".parse().unwrap()];
+    let peer: ForwardedHop = ForwardedHop::new(
+        "10.0.0.1".parse().ok(),
+        Some("http".to_string()),
+        Some("internal".to_string()),
+    );
+    let hops: VecForwardedHop = vec![
+        hop("198.51.100.1"),
+        ForwardedHop::new(
+            "203.0.113.9".parse().ok(),
+            Some("https".to_string()),
+            Some("example.com".to_string()),
+        ),
+        hop("10.0.0.2"),
+    ];
+    let client: ForwardedHop = resolve_forwarded(peer.clone(), &hops, &trusted_proxies);
+    assert_eq!(
+        client,
+        ForwardedHop::new(
+            "203.0.113.9".parse().ok(),
+            Some("https".to_string()),
+            Some("example.com".to_string())
+        )
+    );
+    let untrusted: ForwardedHop = ForwardedHop::new("192.0.2.1".parse().ok(), None, None);
+    assert_eq!(
+        resolve_forwarded(untrusted.clone(), &hops, &trusted_proxies),
+        untrusted
+    );
+    let hidden: VecForwardedHop = vec![hop("203.0.113.9"), hop("unknown"), hop("10.0.0.2")];
+    assert_eq!(
+        *resolve_forwarded(peer.clone(), &hidden, &trusted_proxies).get_ip(),
+        "10.0.0.2".parse().ok()
+    );
+    let unix: ForwardedHop = ForwardedHop::new(None, None, None);
+    assert_eq!(
+        resolve_forwarded(unix.clone(), &hops, &trusted_proxies),
+        unix
+    );
+}
+
+#[test]
+fn test_forwarded_headers_restrict() {
+    let hop: ForwardedHop = ForwardedHop::new(
+        "203.0.113.9".parse().ok(),
+        Some("https".to_string()),
+        Some("example.com".to_string()),
+    );
+    assert_eq!(
+        ForwardedHeaders::x_forwarded().restrict(hop.clone()),
+        ForwardedHop::new("203.0.113.9".parse().ok(), None, None)
+    );
+    assert_eq!(
+        ForwardedHeaders::forwarded()
+            .with_proto()
+            .restrict(hop.clone()),
+        ForwardedHop::new("203.0.113.9".parse().ok(), Some("https".to_string()), None)
+    );
+    assert_eq!(
+        ForwardedHeaders::forwarded()
+            .with_proto()
+            .with_host()
+            .restrict(hop.clone()),
+        hop
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_trusted_proxies() {
+    let port: usize = 60129;
+    forwarded_server(
+        port,
+        &["127.0.0.1", "10.0.0.0/8"],
+        ForwardedHeaders::x_forwarded().with_proto().with_host(),
+    )
+    .await;
+    assert_eq!(get(port, "").await, "127.0.0.1 http localhost");
+    assert_eq!(
+        get(
+            port,
+            "X-Forwarded-For: 1.1.1.1, 203.0.113.9\r\nX-Forwarded-For: 10.0.0.2\r\nX-Forwarded-Proto: https\r\nX-Forwarded-Host: example.com\r\n"
+        )
+        .await,
+        "203.0.113.9 https example.com"
+    );
+    assert_eq!(
+        get(
+            port,
+            "Forwarded: for=203.0.113.7;proto=https;host=api.example.com\r\nX-Forwarded-For: 1.1.1.1\r\n"
+        )
+        .await,
+        "1.1.1.1 http localhost"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_forwarded_only() {
+    let port: usize = 60135;
+    forwarded_server(port, &["127.0.0.1"], ForwardedHeaders::forwarded()).await;
+    assert_eq!(
+        get(
+            port,
+            "Forwarded: for=203.0.113.7;proto=https;host=api.example.com\r\nX-Forwarded-For: 1.1.1.1\r\n"
+        )
+        .await,
+        "203.0.113.7 http localhost"
+    );
+    assert_eq!(
+        get(port, "X-Forwarded-For: 1.1.1.1\r\n").await,
+        "127.0.0.1 http localhost"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_x_forwarded_without_host() {
+    let port: usize = 60136;
+    forwarded_server(port, &["127.0.0.1"], ForwardedHeaders::x_forwarded()).await;
+    assert_eq!(
+        get(
+            port,
+            "X-Forwarded-For: 203.0.113.9\r\nX-Forwarded-Proto: https\r\nX-Forwarded-Host: evil.example\r\n"
+        )
+        .await,
+        "203.0.113.9 http localhost"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_untrusted_peer() {
+    let port: usize = 60130;
+    forwarded_server(port, &["10.0.0.0/8"], ForwardedHeaders::x_forwarded()).await;
+    assert_eq!(
+        get(
+            port,
+            "X-Forwarded-For: 203.0.113.9\r\nX-Forwarded-Proto: https\r\n"
+        )
+        .await,
+        "127.0.0.1 http localhost"
+    );
+}
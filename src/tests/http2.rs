@@ -0,0 +1,260 @@
+use crate::*;
+
+#[cfg(test)]
+use h2::client::{self as h2_client, SendRequest};
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+async fn connect(port: usize) -> TcpStream {
+    let addr: String = format!("127.0.0.1:{}", port);
+    loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[cfg(test)]
+async fn http2_server(port: usize) -> Server {
+    async fn request_middleware(ctx: Context) {
+        ctx.set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(200)
+            .await
+            .set_response_header("x-middleware", "seen")
+            .await;
+    }
+
+    async fn echo_route(ctx: Context) {
+        let body: String = format!(
+            "{} {} {} {} {}",
+            ctx.is_http2().await,
+            ctx.get_request_version().await,
+            ctx.get_request_method().await,
+            ctx.get_request_query("id").await.unwrap_or_default(),
+            ctx.get_request_body_string().await
+        );
+        let _ = ctx.set_response_body(body).await.send().await;
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.http2(Http2Settings::new()).await;
+    server.request_middleware(request_middleware).await;
+    server.route("/echo", echo_route).await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    server
+}
+
+#[cfg(test)]
+async fn h2_request(
+    mut client: SendRequest<Bytes>,
+    method: &str,
+    path: &str,
+    body: &'static str,
+) -> (u16, String, String) {
+    let request: HttpRequest<()> = HttpRequest::builder()
+        .method(method)
+        .uri(format!("http://localhost{}", path))
+        .body(())
+        .unwrap();
+    let (response, mut send) = client.send_request(request, body.is_empty()).unwrap();
+    if !body.is_empty() {
+        send.send_data(Bytes::from_static(body.as_bytes()), true)
+            .unwrap();
+    }
+    let response: HttpResponse<RecvStream> = response.await.unwrap();
+    let status: u16 = response.status().as_u16();
+    let middleware: String = response
+        .headers()
+        .get("x-middleware")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let mut stream: RecvStream = response.into_body();
+    let mut received: Vec<u8> = Vec::new();
+    while let Some(chunk) = stream.data().await {
+        let chunk: Bytes = chunk.unwrap();
+        let _ = stream.flow_control().release_capacity(chunk.len());
+        received.extend_from_slice(&chunk);
+    }
+    (
+        status,
+        middleware,
+        String::from_utf8_lossy(&received).into_owned(),
+    )
+}
+
+#[tokio::test]
+async fn test_http2_prior_knowledge_streams() {
+    let port: usize = 60119;
+    http2_server(port).await;
+    let (client, connection) = h2_client::handshake(connect(port).await).await.unwrap();
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+    let (first, second, third) = tokio::join!(
+        h2_request(client.clone(), "GET", "/echo?id=1", ""),
+        h2_request(client.clone(), "POST", "/echo?id=2", "payload"),
+        h2_request(client.clone(), "GET", "/echo?id=3", ""),
+    );
+    assert_eq!(
+        first,
+        (200, "seen".to_string(), "true HTTP/2 GET 1 ".to_string())
+    );
+    assert_eq!(
+        second,
+        (
+            200,
+            "seen".to_string(),
+            "true HTTP/2 POST 2 payload".to_string()
+        )
+    );
+    assert_eq!(
+        third,
+        (200, "seen".to_string(), "true HTTP/2 GET 3 ".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_http2_h2c_upgrade() {
+    let port: usize = 60120;
+    http2_server(port).await;
+    let mut stream: TcpStream = connect(port).await;
+    stream
+        .write_all(
+            b"GET /echo?id=7 HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade, HTTP2-Settings\r\n\
+              Upgrade: h2c\r\nHTTP2-Settings: AAMAAABkAAQAAP__\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    let mut head: Vec<u8> = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        let mut byte: [u8; 1] = [0];
+        stream.read_exact(&mut byte).await.unwrap();
+        head.push(byte[0]);
+    }
+    let head: String = String::from_utf8_lossy(&head).into_owned();
+    assert!(head.starts_with("HTTP/1.1 101"), "{}", head);
+    stream
+        .write_all(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n\x00\x00\x00\x04\x00\x00\x00\x00\x00")
+        .await
+        .unwrap();
+    let mut body: Vec<u8> = Vec::new();
+    let read_frames = async {
+        loop {
+            let mut header: [u8; 9] = [0; 9];
+            stream.read_exact(&mut header).await.unwrap();
+            let length: usize = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+            let stream_id: u32 =
+                u32::from_be_bytes([header[5], header[6], header[7], header[8]]) & 0x7FFF_FFFF;
+            let mut payload: Vec<u8> = vec![0; length];
+            stream.read_exact(&mut payload).await.unwrap();
+            if header[3] == 0x0 && stream_id == 1 {
+                body.extend_from_slice(&payload);
+                if header[4] & 0x1 != 0 {
+                    break;
+                }
+            }
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), read_frames)
+        .await
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&body), "true HTTP/2 GET 7 ");
+}
+
+#[tokio::test]
+async fn test_http2_trailers_and_body_limit() {
+    let port: usize = 60133;
+    let server: Server = http2_server(port).await;
+    server.http_max_body_size(8).await;
+    server
+        .route("/trailer", |ctx: Context| async move {
+            let body: String = format!(
+                "{} {}",
+                ctx.get_request_body_string().await,
+                ctx.get_request_header_back("x-checksum")
+                    .await
+                    .unwrap_or_default()
+            );
+            let _ = ctx.set_response_body(body).await.send().await;
+        })
+        .await;
+    let (mut client, connection) = h2_client::handshake(connect(port).await).await.unwrap();
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+    let request: HttpRequest<()> = HttpRequest::builder()
+        .method("POST")
+        .uri("http://localhost/trailer")
+        .body(())
+        .unwrap();
+    let (response, mut send) = client.send_request(request, false).unwrap();
+    send.send_data(Bytes::from_static(b"payload"), false)
+        .unwrap();
+    let mut trailers: Http2HeaderMap = Http2HeaderMap::new();
+    trailers.insert("x-checksum", Http2HeaderValue::from_static("abc"));
+    send.send_trailers(trailers).unwrap();
+    let mut body: RecvStream = response.await.unwrap().into_body();
+    let mut received: Vec<u8> = Vec::new();
+    while let Some(chunk) = body.data().await {
+        received.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(String::from_utf8_lossy(&received), "payload abc");
+    let oversized: (u16, String, String) =
+        h2_request(client, "POST", "/echo", "payload that is too long").await;
+    assert_eq!(oversized.0, 413);
+    let mut stream: TcpStream = connect(port).await;
+    stream
+        .write_all(b"POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: 100\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+}
+
+#[cfg(feature = "tls")]
+#[tokio::test]
+async fn test_http2_tls_alpn() {
+    use rustls::{ClientConfig, RootCertStore, pki_types::ServerName};
+    use tokio_rustls::{TlsConnector, client::TlsStream as ClientTlsStream};
+
+    let port: usize = 60121;
+    let certified: rcgen::CertifiedKey<rcgen::KeyPair> =
+        rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert: CertificateDer<'static> = certified.cert.der().clone();
+    let key: PrivateKeyDer<'static> =
+        PrivateKeyDer::try_from(certified.signing_key.serialize_der()).unwrap();
+    let server: Server = http2_server(port).await;
+    server.tls(vec![cert.clone()], key).await;
+    let mut roots: RootCertStore = RootCertStore::empty();
+    roots.add(cert).unwrap();
+    let mut config: ClientConfig = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    let connector: TlsConnector = TlsConnector::from(Arc::new(config));
+    let server_name: ServerName<'static> = ServerName::try_from("localhost").unwrap();
+    let stream: ClientTlsStream<TcpStream> = connector
+        .connect(server_name, connect(port).await)
+        .await
+        .unwrap();
+    assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+    let (client, connection) = h2_client::handshake(stream).await.unwrap();
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+    let response: (u16, String, String) = h2_request(client, "GET", "/echo?id=9", "").await;
+    assert_eq!(
+        response,
+        (200, "seen".to_string(), "true HTTP/2 GET 9 ".to_string())
+    );
+}
@@ -0,0 +1,277 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+async fn get(port: usize, path: &str) -> String {
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let request: String = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        path
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[tokio::test]
+async fn test_listen_tagged() {
+    async fn request_middleware(ctx: Context) {
+        let tag: OptionString = ctx.get_listener_tag().await;
+        let admin: bool = ctx.get_request_path().await.starts_with("/admin");
+        let status: usize = if admin && tag.as_deref() != Some("admin") {
+            403
+        } else {
+            200
+        };
+        ctx.set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(status)
+            .await
+            .set_response_header(CONNECTION, CLOSE)
+            .await
+            .set_response_body(tag.unwrap_or_default())
+            .await;
+        if status == 403 {
+            let _ = ctx.send().await;
+            ctx.aborted().await;
+        }
+    }
+
+    async fn tag_route(ctx: Context) {
+        let _ = ctx.send().await;
+    }
+
+    let server: Server = Server::new();
+    server
+        .listen_tagged("127.0.0.1:60122", "admin")
+        .await
+        .listen("127.0.0.1:60123")
+        .await;
+    server.request_middleware(request_middleware).await;
+    server.route("/admin", tag_route).await;
+    server.route("/public", tag_route).await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let admin: String = get(60122, "/admin").await;
+    assert!(admin.starts_with("HTTP/1.1 200"), "{}", admin);
+    assert!(admin.ends_with("admin"), "{}", admin);
+    let public: String = get(60123, "/public").await;
+    assert!(public.starts_with("HTTP/1.1 200"), "{}", public);
+    assert!(public.ends_with("\r\n\r\n"), "{}", public);
+    let forbidden: String = get(60123, "/admin").await;
+    assert!(forbidden.starts_with("HTTP/1.1 403"), "{}", forbidden);
+}
+
+#[tokio::test]
+async fn test_from_listener() {
+    async fn addr_route(ctx: Context) {
+        let _ = ctx
+            .set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(200)
+            .await
+            .set_response_header(CONNECTION, CLOSE)
+            .await
+            .set_response_body("inherited")
+            .await
+            .send()
+            .await;
+    }
+
+    let listener: StdTcpListener = StdTcpListener::bind("127.0.0.1:60124").unwrap();
+    let server: Server = Server::from_listener(listener);
+    server.route("/", addr_route).await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let response: String = get(60124, "/").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with("inherited"), "{}", response);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_parse_listen_fds() {
+    let pid: u32 = std::process::id();
+    let own_pid: String = pid.to_string();
+    assert!(parse_listen_fds(Some("1"), Some("2"), None, pid).is_empty());
+    assert!(parse_listen_fds(None, Some("2"), None, pid).is_empty());
+    assert_eq!(
+        parse_listen_fds(Some(&own_pid), Some("3"), Some("http::admin"), pid),
+        vec![
+            (3, Some("http".to_string())),
+            (4, None),
+            (5, Some("admin".to_string()))
+        ]
+    );
+    assert_eq!(
+        parse_listen_fds(Some(&own_pid), Some("1"), None, pid),
+        vec![(3, None)]
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_socket_activation() {
+    let port: usize = 60141;
+    let listener: StdTcpListener = StdTcpListener::bind(format!("127.0.0.1:{}", port)).unwrap();
+    let fd: RawFd = listener.as_raw_fd();
+    let mut command: Command = Command::new("sh");
+    command
+        .args([
+            "-c",
+            "LISTEN_PID=$$ exec \"$0\" tests::listen::socket_activation_child --exact --ignored",
+        ])
+        .arg(std::env::current_exe().unwrap())
+        .env(LISTEN_FDS, "1")
+        .env(LISTEN_FDNAMES, "activated")
+        .stdout(std::process::Stdio::null());
+    // SAFETY: only the async-signal-safe `dup2` and `fcntl` run between fork and exec.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::dup2(fd, SD_LISTEN_FDS_START) == -1
+                || libc::fcntl(SD_LISTEN_FDS_START, libc::F_SETFD, 0) == -1
+            {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child: Child = command.spawn().unwrap();
+    drop(listener);
+    let response: String = get(port, "/").await;
+    assert!(response.ends_with("child activated"), "{}", response);
+    assert!(child.wait().unwrap().success());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[ignore = "started by test_socket_activation"]
+async fn socket_activation_child() {
+    if std::env::var(LISTEN_FDS).is_err() {
+        return;
+    }
+    let (served_sender, mut served_receiver) = tokio::sync::mpsc::channel::<()>(1);
+    let server: Server = Server::new();
+    server
+        .route("/", move |ctx: Context| {
+            let served_sender: tokio::sync::mpsc::Sender<()> = served_sender.clone();
+            async move {
+                let tag: String = ctx.get_listener_tag().await.unwrap_or_default();
+                let _ = ctx
+                    .set_response_version(HttpVersion::HTTP1_1)
+                    .await
+                    .set_response_status_code(200)
+                    .await
+                    .set_response_header(CONNECTION, CLOSE)
+                    .await
+                    .set_response_body(format!("child {}", tag))
+                    .await
+                    .send()
+                    .await;
+                let _ = served_sender.send(()).await;
+            }
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let _ = tokio::time::timeout(Duration::from_secs(10), served_receiver.recv()).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+}
+
+#[cfg(unix)]
+#[test]
+fn test_parse_upgrade_fds() {
+    assert!(parse_upgrade_fds(None, None).is_empty());
+    assert_eq!(
+        parse_upgrade_fds(Some("7:9"), Some(":admin")),
+        vec![(7, None), (9, Some("admin".to_string()))]
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_adopt_upgrade_ready() {
+    let (sender, mut receiver) = unix_pipe().unwrap();
+    let ready: StdFile = StdFile::from(sender.into_blocking_fd().unwrap());
+    let token: String = upgrade_token(&ready).unwrap();
+    let fd: String = ready.into_raw_fd().to_string();
+    let other: StdFile = StdFile::open("/dev/null").unwrap();
+    assert!(adopt_upgrade_ready(Some(&upgrade_token(&other).unwrap()), Some(&fd)).is_none());
+    assert!(adopt_upgrade_ready(None, Some(&fd)).is_none());
+    let mut ready: StdFile = adopt_upgrade_ready(Some(&token), Some(&fd)).unwrap();
+    ready.write_all(UPGRADE_READY_SIGNAL).unwrap();
+    drop(ready);
+    let mut signal: Vec<u8> = Vec::new();
+    receiver.read_to_end(&mut signal).await.unwrap();
+    assert_eq!(signal, UPGRADE_READY_SIGNAL);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_adopt_unix_listen_fd() {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("hyperlane-adopt-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener: StdUnixListener = StdUnixListener::bind(&path).unwrap();
+    let InheritedSocket::Unix(adopted) = adopt_listen_fd(listener.into_raw_fd()).unwrap() else {
+        panic!("expected a Unix domain socket listener");
+    };
+    assert_eq!(
+        adopted.local_addr().unwrap().as_pathname(),
+        Some(path.as_path())
+    );
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_adopt_listen_fd() {
+    let listener: StdTcpListener = StdTcpListener::bind("127.0.0.1:60125").unwrap();
+    let fd: RawFd = listener.into_raw_fd();
+    let InheritedSocket::Tcp(adopted) = adopt_listen_fd(fd).unwrap() else {
+        panic!("expected a TCP listener");
+    };
+    assert_eq!(adopted.local_addr().unwrap().port(), 60125);
+    let file: std::fs::File = std::fs::File::open("/dev/null").unwrap();
+    let file_fd: RawFd = file.as_raw_fd();
+    assert!(adopt_listen_fd(file_fd).is_err());
+    assert!(file.metadata().is_ok());
+    let socket: std::net::UdpSocket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    assert!(adopt_listen_fd(socket.as_raw_fd()).is_err());
+    assert!(socket.local_addr().is_ok());
+    let server: Server = Server::from_listener(adopted);
+    server
+        .route("/", |ctx: Context| async move {
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_header(CONNECTION, CLOSE)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let response: String = get(60125, "/").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+}
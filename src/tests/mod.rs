@@ -1,4 +1,18 @@
 mod attribute;
+mod forwarded;
+#[cfg(feature = "http2")]
+mod http2;
+mod listen;
+mod proxy;
 mod route;
 mod send;
 mod server;
+mod sse;
+mod stream;
+#[cfg(feature = "tls")]
+mod tls;
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+mod upgrade;
+mod websocket;
@@ -0,0 +1,198 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+async fn send(port: usize, prefix: &[u8]) -> String {
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let mut request: Vec<u8> = prefix.to_vec();
+    request.extend_from_slice(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    let _ = stream.write_all(&request).await;
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[cfg(test)]
+async fn proxy_server(port: usize, proxy_protocol: ProxyProtocol, sources: &[&str]) {
+    async fn peer_route(ctx: Context) {
+        let authority: String = ctx
+            .get_proxy_header()
+            .await
+            .and_then(|header| header.get_tlv(0x02).map(<[u8]>::to_vec))
+            .map(|value| String::from_utf8_lossy(&value).into_owned())
+            .unwrap_or_default();
+        let body: String = format!(
+            "{} {}",
+            ctx.get_socket_addr_or_default_string().await,
+            authority
+        );
+        let _ = ctx
+            .set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(200)
+            .await
+            .set_response_header(CONNECTION, CLOSE)
+            .await
+            .set_response_body(body)
+            .await
+            .send()
+            .await;
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.proxy_protocol(proxy_protocol).await;
+    for source in sources {
+        server.proxy_protocol_source(source).await;
+    }
+    server.route("/", peer_route).await;
+    tokio::spawn(async move {
+        let _ = server.run().await;
+    });
+}
+
+#[cfg(test)]
+fn proxy_v2_header(command: u8, family: u8, addresses: &[u8], tlvs: &[u8]) -> Vec<u8> {
+    let mut header: Vec<u8> = PROXY_V2_SIGNATURE.to_vec();
+    header.push(0x20 | command);
+    header.push(family);
+    header.extend_from_slice(&((addresses.len() + tlvs.len()) as u16).to_be_bytes());
+    header.extend_from_slice(addresses);
+    header.extend_from_slice(tlvs);
+    header
+}
+
+#[test]
+fn test_parse_proxy_v1() {
+    let header: ProxyHeader =
+        ProxyHeader::parse_v1(b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 443\r\n").unwrap();
+    assert_eq!(*header.get_version(), 1);
+    assert_eq!(*header.get_command(), ProxyCommand::Proxy);
+    assert_eq!(
+        *header.get_source(),
+        Some("192.0.2.1:56324".parse().unwrap())
+    );
+    assert_eq!(
+        *header.get_destination(),
+        Some("198.51.100.2:443".parse().unwrap())
+    );
+    let header: ProxyHeader =
+        ProxyHeader::parse_v1(b"PROXY TCP6 2001:db8::1 2001:db8::2 1 2\r\n").unwrap();
+    assert_eq!(
+        *header.get_source(),
+        Some("[2001:db8::1]:1".parse().unwrap())
+    );
+    let header: ProxyHeader = ProxyHeader::parse_v1(b"PROXY UNKNOWN\r\n").unwrap();
+    assert_eq!(*header.get_source(), None);
+    assert!(ProxyHeader::parse_v1(b"PROXY TCP4 2001:db8::1 192.0.2.1 1 2\r\n").is_none());
+    assert!(ProxyHeader::parse_v1(b"PROXY TCP4 192.0.2.1 192.0.2.2 1 70000\r\n").is_none());
+    assert!(ProxyHeader::parse_v1(b"PROXY TCP4 192.0.2.1 192.0.2.2 1 2 3\r\n").is_none());
+    assert!(ProxyHeader::parse_v1(b"PROXY TCP4 192.0.2.1 192.0.2.2 1 2\n").is_none());
+}
+
+#[test]
+fn test_parse_proxy_v2() {
+    let addresses: [u8; 12] = [192, 0, 2, 1, 198, 51, 100, 2, 0xDC, 0x04, 0x01, 0xBB];
+    let tlvs: [u8; 14] = [
+        0x02, 0x00, 0x0B, b'e', b'x', b'a', b'm', b'p', b'l', b'e', b'.', b'c', b'o', b'm',
+    ];
+    let header: ProxyHeader =
+        ProxyHeader::parse_v2(&proxy_v2_header(0x1, 0x11, &addresses, &tlvs)).unwrap();
+    assert_eq!(*header.get_version(), 2);
+    assert_eq!(*header.get_command(), ProxyCommand::Proxy);
+    assert_eq!(
+        *header.get_source(),
+        Some("192.0.2.1:56324".parse().unwrap())
+    );
+    assert_eq!(
+        *header.get_destination(),
+        Some("198.51.100.2:443".parse().unwrap())
+    );
+    assert_eq!(header.get_tlv(0x02), Some(&b"example.com"[..]));
+    assert_eq!(header.get_tlvs().len(), 1);
+    let local: ProxyHeader =
+        ProxyHeader::parse_v2(&proxy_v2_header(0x0, 0x11, &addresses, &[])).unwrap();
+    assert_eq!(*local.get_command(), ProxyCommand::Local);
+    assert_eq!(*local.get_source(), None);
+    let unspec: ProxyHeader = ProxyHeader::parse_v2(&proxy_v2_header(0x0, 0x00, &[], &[])).unwrap();
+    assert!(unspec.get_tlvs().is_empty());
+    assert!(ProxyHeader::parse_v2(&proxy_v2_header(0x1, 0x11, &addresses[..8], &[])).is_none());
+    assert!(ProxyHeader::parse_v2(&proxy_v2_header(0x1, 0x11, &addresses, &tlvs[..5])).is_none());
+    assert!(ProxyHeader::parse_v2(&proxy_v2_header(0x2, 0x11, &addresses, &[])).is_none());
+}
+
+#[test]
+fn test_detect_proxy_header() {
+    assert_eq!(detect_proxy_header(b"PROXY TCP4"), ProxyHeaderKind::V1);
+    assert_eq!(detect_proxy_header(b"PRO"), ProxyHeaderKind::Incomplete);
+    assert_eq!(detect_proxy_header(PROXY_V2_SIGNATURE), ProxyHeaderKind::V2);
+    assert_eq!(
+        detect_proxy_header(b"\r\n\r\n"),
+        ProxyHeaderKind::Incomplete
+    );
+    assert_eq!(
+        detect_proxy_header(b"POST / HTTP/1.1"),
+        ProxyHeaderKind::Absent
+    );
+    assert_eq!(
+        detect_proxy_header(b"PROPFIND / HTTP/1.1"),
+        ProxyHeaderKind::Absent
+    );
+}
+
+#[tokio::test]
+async fn test_proxy_protocol_required() {
+    let port: usize = 60127;
+    proxy_server(port, ProxyProtocol::Required, &[]).await;
+    let response: String = send(port, b"PROXY TCP4 192.0.2.1 127.0.0.1 56324 60127\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with("192.0.2.1:56324 "), "{}", response);
+    let addresses: [u8; 36] = [
+        0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0x01, 0x30, 0x39, 0xEA, 0xDF,
+    ];
+    let tlvs: [u8; 7] = [0x02, 0x00, 0x04, b'a', b'p', b'i', b'.'];
+    let response: String = send(port, &proxy_v2_header(0x1, 0x21, &addresses, &tlvs)).await;
+    assert!(
+        response.ends_with("[2001:db8::1]:12345 api."),
+        "{}",
+        response
+    );
+    let response: String = send(port, b"").await;
+    assert!(response.is_empty(), "{}", response);
+    let response: String = send(port, b"PROXY TCP4 not-an-address\r\n").await;
+    assert!(response.is_empty(), "{}", response);
+}
+
+#[tokio::test]
+async fn test_proxy_protocol_optional() {
+    let port: usize = 60128;
+    proxy_server(port, ProxyProtocol::Optional, &["127.0.0.0/8"]).await;
+    let response: String = send(port, b"").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.contains("127.0.0.1:"), "{}", response);
+    let response: String = send(port, b"PROXY TCP4 192.0.2.7 127.0.0.1 4000 60128\r\n").await;
+    assert!(response.ends_with("192.0.2.7:4000 "), "{}", response);
+    let local: Vec<u8> = proxy_v2_header(0x0, 0x00, &[], &[]);
+    let response: String = send(port, &local).await;
+    assert!(response.contains("127.0.0.1:"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_proxy_protocol_optional_untrusted() {
+    let port: usize = 60139;
+    proxy_server(port, ProxyProtocol::Optional, &["192.0.2.0/24"]).await;
+    let response: String = send(port, b"").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.contains("127.0.0.1:"), "{}", response);
+    let response: String = send(port, b"PROXY TCP4 192.0.2.7 127.0.0.1 4000 60139\r\n").await;
+    assert!(!response.contains("192.0.2.7"), "{}", response);
+}
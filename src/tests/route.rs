@@ -56,3 +56,508 @@ async fn test_duplicate_route() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn test_url_for_named_route() {
+    let server: Server = Server::new();
+    server
+        .named_route("user_detail", "/users/{id}", |_| async move {})
+        .await
+        .named_route("file", "/files/{path:^.*$}", |_| async move {})
+        .await;
+    let mut params: RouteParams = hash_map_xx_hash3_64();
+    params.insert("id".to_string(), "a b/c".to_string());
+    params.insert("path".to_string(), "docs/read me.md".to_string());
+    assert_eq!(
+        server.url_for("user_detail", &params).await,
+        Ok("/users/a%20b%2Fc".to_string())
+    );
+    assert_eq!(
+        server.url_for("file", &params).await,
+        Ok("/files/docs/read%20me.md".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_url_for_errors() {
+    let server: Server = Server::new();
+    server
+        .named_route("post", "/posts/{id:\\d+}", |_| async move {})
+        .await;
+    let mut params: RouteParams = hash_map_xx_hash3_64();
+    assert_eq!(
+        server.url_for("unknown", &params).await,
+        Err(RouteError::UnknownRouteName("unknown".to_string()))
+    );
+    assert_eq!(
+        server.url_for("post", &params).await,
+        Err(RouteError::MissingRouteParam("id".to_string()))
+    );
+    params.insert("id".to_string(), "abc".to_string());
+    assert!(matches!(
+        server.url_for("post", &params).await,
+        Err(RouteError::InvalidRouteParam(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_duplicate_route_name() {
+    assert_panic_message_contains(
+        || async {
+            let _server: &Server = Server::new()
+                .named_route("root", ROOT_PATH, |_| async move {})
+                .await
+                .named_route("root", "/other", |_| async move {})
+                .await;
+        },
+        &RouteError::DuplicateRouteName("root".to_string()).to_string(),
+    )
+    .await;
+    assert_panic_message_contains(
+        || async {
+            let _server: &Server = Server::new()
+                .named_route("root", ROOT_PATH, |_| async move {})
+                .await
+                .host_named_route("example.com", "root", "/other", |_| async move {})
+                .await;
+        },
+        &RouteError::DuplicateRouteName("root".to_string()).to_string(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_host_pattern_match() {
+    let exact: HostPattern = HostPattern::new("api.example.com").unwrap();
+    assert!(exact.match_host("API.example.com:8080").is_some());
+    assert!(exact.match_host("www.example.com").is_none());
+    let wildcard: HostPattern = HostPattern::new("*.example.com").unwrap();
+    assert!(wildcard.match_host("a.b.example.com").is_some());
+    assert!(wildcard.match_host("example.com").is_none());
+    let captured: HostPattern = HostPattern::new("{tenant}.example.com").unwrap();
+    let params: RouteParams = captured.match_host("acme.example.com").unwrap();
+    assert_eq!(params.get("tenant"), Some(&"acme".to_string()));
+    assert!(captured.match_host("a.b.example.com").is_none());
+    assert_eq!(
+        HostPattern::new("api.*.com"),
+        Err(RouteError::InvalidHostPattern("api.*.com".to_string()))
+    );
+}
+
+#[cfg(test)]
+async fn normalization_server(port: usize) -> Server {
+    async fn static_route(ctx: Context) {
+        ctx.set_response_body("static").await;
+    }
+
+    async fn dynamic_route(ctx: Context) {
+        let id: String = ctx.get_route_param("id").await.unwrap_or_default();
+        ctx.set_response_body(format!("dynamic:{}", id)).await;
+    }
+
+    async fn regex_route(ctx: Context) {
+        let path: String = ctx.get_route_param("path").await.unwrap_or_default();
+        ctx.set_response_body(format!("regex:{}", path)).await;
+    }
+
+    async fn request_middleware(ctx: Context) {
+        ctx.set_response_version(HttpVersion::HTTP1_1).await;
+    }
+
+    async fn response_middleware(ctx: Context) {
+        let _ = ctx.send().await;
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.request_middleware(request_middleware).await;
+    server.response_middleware(response_middleware).await;
+    server.route("/users", static_route).await;
+    server.route("/users/{id}", dynamic_route).await;
+    server.route("/files/{path:^.*$}", regex_route).await;
+    server
+}
+
+#[cfg(test)]
+fn spawn_server(server: &Server) {
+    let server: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = server.run().await;
+    });
+}
+
+#[cfg(test)]
+async fn send_request(port: usize, path: &str) -> String {
+    let request: String = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        path
+    );
+    send_raw_request(port, &request).await
+}
+
+#[cfg(test)]
+async fn send_raw_request(port: usize, request: &str) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[cfg(test)]
+fn response_body(response: &str) -> &str {
+    response.split_once("\r\n\r\n").map_or("", |(_, body)| body)
+}
+
+#[tokio::test]
+async fn test_host_routes() {
+    async fn api_route(ctx: Context) {
+        ctx.set_response_body("api").await;
+    }
+
+    async fn wildcard_route(ctx: Context) {
+        ctx.set_response_body("wildcard").await;
+    }
+
+    async fn tenant_route(ctx: Context) {
+        let tenant: String = ctx.get_route_param("tenant").await.unwrap_or_default();
+        let id: String = ctx.get_route_param("id").await.unwrap_or_default();
+        ctx.set_response_body(format!("tenant:{}:{}", tenant, id))
+            .await;
+    }
+
+    let port: usize = 60131;
+    let server: Server = normalization_server(port).await;
+    server
+        .host_route("api.example.com", "/users", api_route)
+        .await
+        .host_route("*.example.com", "/users", wildcard_route)
+        .await
+        .host_named_route(
+            "{tenant}.tenant.test",
+            "tenant_page",
+            "/page/{id}",
+            tenant_route,
+        )
+        .await;
+    assert_eq!(
+        server
+            .url_for(
+                "tenant_page",
+                &RouteParams::from_iter([("id".to_owned(), "7".to_owned())])
+            )
+            .await,
+        Ok("/page/7".to_owned())
+    );
+    spawn_server(&server);
+    let request = |host: &str, path: &str| {
+        format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            path, host
+        )
+    };
+    let cases: [(&str, &str, &str); 6] = [
+        ("api.example.com:8080", "/users", "api"),
+        ("API.example.com", "/users", "api"),
+        ("www.example.com", "/users", "wildcard"),
+        ("api.example.com", "/users/1", "dynamic:1"),
+        ("other.test", "/users", "static"),
+        ("acme.tenant.test:443", "/page/7", "tenant:acme:7"),
+    ];
+    for (host, path, expected) in cases {
+        let response: String = send_raw_request(port, &request(host, path)).await;
+        assert_eq!(response_body(&response), expected, "host: {}", host);
+    }
+    let response: String = send_raw_request(port, &request("other.test", "/page/7")).await;
+    assert_eq!(response_body(&response), "");
+}
+
+#[tokio::test]
+async fn test_path_normalization_default() {
+    let port: usize = 60101;
+    let server: Server = normalization_server(port).await;
+    let cases: [(&str, &str); 10] = [
+        ("/users", "static"),
+        ("/users/", ""),
+        ("//users", ""),
+        ("/USERS", ""),
+        ("/users/1", "dynamic:1"),
+        ("/users/1/", "dynamic:1"),
+        ("/users//1", "dynamic:1"),
+        ("//users/1", "dynamic:1"),
+        ("/users/a%2Fb", "dynamic:a%2Fb"),
+        ("/files/a//b/", "regex:a/b"),
+    ];
+    spawn_server(&server);
+    for (path, expected) in cases {
+        let response: String = send_request(port, path).await;
+        assert_eq!(response_body(&response), expected, "path: {}", path);
+    }
+}
+
+#[tokio::test]
+async fn test_path_normalization_strict() {
+    let port: usize = 60134;
+    let server: Server = normalization_server(port).await;
+    server.trailing_slash(TrailingSlash::Strict).await;
+    let cases: [(&str, &str); 7] = [
+        ("/users", "static"),
+        ("/users/", ""),
+        ("/users/1", "dynamic:1"),
+        ("/users/1/", ""),
+        ("/users//1", ""),
+        ("//users/1", ""),
+        ("/files/a//b", "regex:a//b"),
+    ];
+    spawn_server(&server);
+    for (path, expected) in cases {
+        let response: String = send_request(port, path).await;
+        assert_eq!(response_body(&response), expected, "path: {}", path);
+    }
+}
+
+#[tokio::test]
+async fn test_path_normalization_enabled() {
+    let port: usize = 60102;
+    let server: Server = normalization_server(port).await;
+    server
+        .trailing_slash(TrailingSlash::Ignore)
+        .await
+        .enable_merge_slashes()
+        .await
+        .enable_remove_dot_segments()
+        .await
+        .enable_decode_route_params()
+        .await
+        .disable_case_sensitive()
+        .await;
+    let cases: [(&str, &str); 12] = [
+        ("/users/", "static"),
+        ("//users", "static"),
+        ("/USERS", "static"),
+        ("/a/../users", "static"),
+        ("/users/1/", "dynamic:1"),
+        ("/users//1", "dynamic:1"),
+        ("/Users/./1", "dynamic:1"),
+        ("/users/a%2Fb", "dynamic:a/b"),
+        ("/users/%+41", "dynamic:%+41"),
+        ("/users/a%4", "dynamic:a%4"),
+        ("/files/a//b/../c%20d", "regex:a/c d"),
+        ("/files/../users", "static"),
+    ];
+    spawn_server(&server);
+    for (path, expected) in cases {
+        let response: String = send_request(port, path).await;
+        assert_eq!(response_body(&response), expected, "path: {}", path);
+    }
+}
+
+#[tokio::test]
+async fn test_path_normalization_redirect() {
+    let port: usize = 60103;
+    let server: Server = normalization_server(port).await;
+    server.trailing_slash(TrailingSlash::Redirect).await;
+    spawn_server(&server);
+    let response: String = send_request(port, "/users/").await;
+    assert!(response.starts_with("HTTP/1.1 308"), "{}", response);
+    assert!(response.contains("location: /users\r\n"), "{}", response);
+    let response: String = send_request(port, "/users/1/?page=2").await;
+    assert!(
+        response.contains("location: /users/1?page=2\r\n"),
+        "{}",
+        response
+    );
+    let response: String = send_request(port, "/users/1/?b=2&a=1&a=%20").await;
+    assert!(
+        response.contains("location: /users/1?b=2&a=1&a=%20\r\n"),
+        "{}",
+        response
+    );
+    let response: String = send_request(port, "/files/a/").await;
+    assert_eq!(response_body(&response), "regex:a/");
+}
+
+#[tokio::test]
+async fn test_route_guards() {
+    async fn json_route(ctx: Context) {
+        ctx.set_response_body("json").await;
+    }
+
+    async fn form_route(ctx: Context) {
+        ctx.set_response_body("form").await;
+    }
+
+    async fn v2_route(ctx: Context) {
+        ctx.set_response_body("v2").await;
+    }
+
+    async fn default_route(ctx: Context) {
+        ctx.set_response_body("default").await;
+    }
+
+    let port: usize = 60104;
+    let server: Server = normalization_server(port).await;
+    server
+        .guarded_route(
+            "/upload",
+            vec![RouteGuard::content_type(APPLICATION_JSON)],
+            json_route,
+        )
+        .await
+        .guarded_route(
+            "/upload",
+            vec![RouteGuard::content_type(
+                "application/x-www-form-urlencoded",
+            )],
+            form_route,
+        )
+        .await
+        .guarded_route("/report", vec![RouteGuard::accept("text/csv")], v2_route)
+        .await
+        .route("/report/", default_route)
+        .await
+        .guarded_route("/export", vec![RouteGuard::accept("text/csv")], v2_route)
+        .await
+        .guarded_route("/items/{id}", vec![RouteGuard::query("v", "2")], v2_route)
+        .await
+        .route("/items/{id}", default_route)
+        .await
+        .guarded_route("/search", vec![RouteGuard::has_query("q")], v2_route)
+        .await
+        .trailing_slash(TrailingSlash::Ignore)
+        .await;
+    spawn_server(&server);
+    let upload = |content_type: &str| {
+        format!(
+            "POST /upload HTTP/1.1\r\nHost: localhost\r\nContent-Type: {}\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+            content_type
+        )
+    };
+    let response: String = send_raw_request(port, &upload("application/json; charset=utf-8")).await;
+    assert_eq!(response_body(&response), "json");
+    let response: String =
+        send_raw_request(port, &upload("application/x-www-form-urlencoded")).await;
+    assert_eq!(response_body(&response), "form");
+    let response: String = send_raw_request(port, &upload("text/plain")).await;
+    assert!(response.starts_with("HTTP/1.1 415"), "{}", response);
+    let report = |path: &str, accept: &str| {
+        format!(
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nAccept: {}\r\nConnection: close\r\n\r\n",
+            path, accept
+        )
+    };
+    let response: String =
+        send_raw_request(port, &report("/report", "text/html, text/*;q=0.8")).await;
+    assert_eq!(response_body(&response), "v2");
+    let response: String = send_raw_request(port, &report("/report", "application/json")).await;
+    assert_eq!(response_body(&response), "default");
+    let response: String = send_raw_request(port, &report("/export", "application/json")).await;
+    assert!(response.starts_with("HTTP/1.1 406"), "{}", response);
+    let response: String = send_raw_request(port, &report("/export", "text/csv;q=0, */*")).await;
+    assert!(response.starts_with("HTTP/1.1 406"), "{}", response);
+    let response: String =
+        send_raw_request(port, &report("/export", "text/*;q=0.5, */*;q=0")).await;
+    assert_eq!(response_body(&response), "v2");
+    let response: String =
+        send_raw_request(port, &report("/export", "text/csv;q=0, text/csv")).await;
+    assert!(response.starts_with("HTTP/1.1 406"), "{}", response);
+    let response: String =
+        send_raw_request(port, &report("/export", "text/csv, text/csv;q=0")).await;
+    assert_eq!(response_body(&response), "v2");
+    let response: String = send_request(port, "/items/1?v=2").await;
+    assert_eq!(response_body(&response), "v2");
+    let response: String = send_request(port, "/items/1?v=1").await;
+    assert_eq!(response_body(&response), "default");
+    let response: String = send_request(port, "/search?q=hyperlane").await;
+    assert_eq!(response_body(&response), "v2");
+    let response: String = send_request(port, "/search").await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_nested_duplicate_regex_route() {
+    assert_panic_message_contains(
+        || async {
+            let files: Server = Server::new();
+            files.route("/{path:^.*$}", |_| async move {}).await;
+            let _server: &Server = Server::new()
+                .route("/files/{path:^.*$}", |_| async move {})
+                .await
+                .nest("/files", &files)
+                .await;
+        },
+        &RouteError::DuplicatePattern("/files/{path:^.*$}".to_string()).to_string(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_nested_server() {
+    async fn invoice_route(ctx: Context) {
+        let body: String = format!(
+            "{}|{}|{}|{}",
+            ctx.get_request_path().await,
+            ctx.get_mount_prefix().await,
+            ctx.get_request_relative_path().await,
+            ctx.get_route_param("id").await.unwrap_or_default()
+        );
+        ctx.set_response_body(body).await;
+    }
+
+    async fn index_route(ctx: Context) {
+        let body: String = ctx.get_request_relative_path().await;
+        ctx.set_response_body(body).await;
+    }
+
+    async fn tag_middleware(ctx: Context) {
+        ctx.set_response_header("x-module", "billing").await;
+    }
+
+    let port: usize = 60105;
+    let server: Server = normalization_server(port).await;
+    let billing: Server = Server::new();
+    billing
+        .request_middleware(tag_middleware)
+        .await
+        .route("/", index_route)
+        .await
+        .named_route("invoice", "/invoices/{id}", invoice_route)
+        .await;
+    server.nest("/orgs/{org}/billing/", &billing).await;
+    assert_eq!(
+        server
+            .url_for(
+                "invoice",
+                &RouteParams::from_iter([
+                    ("org".to_owned(), "acme".to_owned()),
+                    ("id".to_owned(), "7".to_owned()),
+                ])
+            )
+            .await,
+        Ok("/orgs/acme/billing/invoices/7".to_owned())
+    );
+    spawn_server(&server);
+    let response: String = send_request(port, "/orgs/acme/billing/invoices/7").await;
+    assert_eq!(
+        response_body(&response),
+        "/orgs/acme/billing/invoices/7|/orgs/acme/billing|/invoices/7|7"
+    );
+    assert!(response.to_ascii_lowercase().contains("x-module: billing"));
+    let response: String = send_request(port, "/orgs/acme/billing").await;
+    assert_eq!(response_body(&response), "/");
+    server.enable_merge_slashes().await;
+    let response: String = send_request(port, "/orgs/acme//billing/invoices/7").await;
+    assert_eq!(
+        response_body(&response),
+        "/orgs/acme//billing/invoices/7|/orgs/acme/billing|/invoices/7|7"
+    );
+    let response: String = send_request(port, "/users").await;
+    assert!(!response.to_ascii_lowercase().contains("x-module"));
+}
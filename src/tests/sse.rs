@@ -0,0 +1,120 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[test]
+fn test_sse_event_encode() {
+    let event: SseEvent = SseEvent::new()
+        .with_id("7\n8")
+        .with_event("tick\r")
+        .with_retry(Duration::from_secs(3))
+        .with_data("first\r\n second\rthird\n");
+    assert_eq!(
+        event.encode(),
+        "id: 78\nevent: tick\nretry: 3000\ndata: first\ndata:  second\ndata: third\ndata: \n\n"
+    );
+    assert_eq!(SseEvent::new().with_comment("ping").encode(), ": ping\n\n");
+}
+
+#[tokio::test]
+async fn test_sse_stream() {
+    let port: usize = 60115;
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.sse_keep_alive(Duration::from_millis(50)).await;
+    server
+        .route("/events", |ctx: Context| async move {
+            let last_event_id: String = ctx.get_last_event_id().await.unwrap_or_default();
+            let _ = ctx.sse_start().await;
+            let _ = ctx
+                .sse_send(&SseEvent::new().with_id(8).with_data(last_event_id))
+                .await;
+            tokio::time::sleep(Duration::from_millis(120)).await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    stream
+        .write_all(b"GET /events HTTP/1.1\r\nHost: localhost\r\nLast-Event-ID: 7\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+        .await
+        .unwrap()
+        .unwrap();
+    let response: String = String::from_utf8_lossy(&response).to_ascii_lowercase();
+    assert!(response.starts_with("http/1.1 200"), "{}", response);
+    assert!(
+        response.contains("content-type: text/event-stream\r\n"),
+        "{}",
+        response
+    );
+    assert!(
+        response.contains("cache-control: no-cache\r\n"),
+        "{}",
+        response
+    );
+    assert!(!response.contains("content-length"), "{}", response);
+    let (_, events) = response.split_once("\r\n\r\n").unwrap();
+    let keep_alive: &str = events
+        .strip_prefix("id: 8\ndata: 7\n\n")
+        .unwrap_or_else(|| panic!("{}", response));
+    assert!(!keep_alive.is_empty(), "{}", response);
+    assert!(
+        keep_alive
+            .split_terminator("\n\n")
+            .all(|comment: &str| comment == ": keep-alive"),
+        "{}",
+        response
+    );
+}
+
+#[tokio::test]
+async fn test_sse_events_then_closed() {
+    let port: usize = 60143;
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server
+        .route("/events", |ctx: Context| async move {
+            let _ = ctx.sse_start().await;
+            for i in 0..3 {
+                let _ = ctx.sse_send(&SseEvent::new().with_data(i)).await;
+            }
+            let _ = ctx.closed().await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    stream
+        .write_all(b"GET /events HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+        .await
+        .unwrap()
+        .unwrap();
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    let (_, events) = response.split_once("\r\n\r\n").unwrap();
+    assert_eq!(events, "data: 0\n\ndata: 1\n\ndata: 2\n\n", "{}", response);
+}
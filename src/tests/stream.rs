@@ -0,0 +1,97 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+async fn send(port: usize, request: &str) -> String {
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let _ = stream.write_all(request.as_bytes()).await;
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[test]
+fn test_head_content_length() {
+    assert_eq!(head_content_length("Host: localhost\r\n\r\n"), Some(0));
+    assert_eq!(head_content_length("Content-Length: 5\r\n\r\n"), Some(5));
+    assert_eq!(
+        head_content_length("content-length: 5\r\nContent-Length: 5\r\n\r\n"),
+        Some(5)
+    );
+    assert_eq!(
+        head_content_length("Content-Length: 5\r\nContent-Length: 6\r\n\r\n"),
+        None
+    );
+    assert_eq!(head_content_length("Content-Length: +5\r\n\r\n"), None);
+    assert_eq!(head_content_length("Content-Length: 5x\r\n\r\n"), None);
+    assert_eq!(head_content_length("Content-Length:\r\n\r\n"), None);
+    assert_eq!(
+        head_content_length("Content-Length: 99999999999999999999999\r\n\r\n"),
+        None
+    );
+    assert_eq!(
+        head_content_length("Transfer-Encoding: chunked\r\n\r\n"),
+        None
+    );
+    assert_eq!(
+        head_content_length("Content-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_ambiguous_body_length() {
+    async fn echo_route(ctx: Context) {
+        let body: String = ctx.get_request_body_string().await;
+        let _ = ctx
+            .set_response_version(HttpVersion::HTTP1_1)
+            .await
+            .set_response_status_code(200)
+            .await
+            .set_response_body(body)
+            .await
+            .send()
+            .await;
+    }
+
+    let port: usize = 60137;
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.route("/", echo_route).await;
+    tokio::spawn(async move {
+        let _ = server.run().await;
+    });
+    let response: String = send(
+        port,
+        "POST / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: 5\r\n\r\nhello",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with("hello"), "{}", response);
+    let response: String = send(
+        port,
+        "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\nContent-Length: 0\r\n\r\nhello",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+    let response: String = send(
+        port,
+        "POST / HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+    let response: String = send(
+        port,
+        "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: five\r\n\r\nhello",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+}
@@ -0,0 +1,451 @@
+use crate::*;
+
+#[cfg(test)]
+use rustls::{ClientConfig, RootCertStore, pki_types::ServerName};
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+#[cfg(test)]
+use tokio_rustls::{TlsConnector, client::TlsStream as ClientTlsStream};
+
+#[cfg(test)]
+fn self_signed_cert(names: &[&str]) -> (CertificateDer<'static>, PrivateKeyDer<'static>) {
+    let certified: rcgen::CertifiedKey<rcgen::KeyPair> = self_signed_certified(names);
+    let key: PrivateKeyDer<'static> =
+        PrivateKeyDer::try_from(certified.signing_key.serialize_der()).unwrap();
+    (certified.cert.der().clone(), key)
+}
+
+#[cfg(test)]
+fn self_signed_certified(names: &[&str]) -> rcgen::CertifiedKey<rcgen::KeyPair> {
+    let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    rcgen::generate_simple_self_signed(names).unwrap()
+}
+
+#[cfg(test)]
+async fn tls_connect(
+    port: usize,
+    root: &CertificateDer<'static>,
+    server_name: &str,
+) -> io::Result<ClientTlsStream<TcpStream>> {
+    tls_connect_with_identity(port, root, server_name, None).await
+}
+
+#[cfg(test)]
+async fn tls_connect_with_identity(
+    port: usize,
+    root: &CertificateDer<'static>,
+    server_name: &str,
+    identity: Option<(CertificateDer<'static>, PrivateKeyDer<'static>)>,
+) -> io::Result<ClientTlsStream<TcpStream>> {
+    let mut roots: RootCertStore = RootCertStore::empty();
+    roots.add(root.clone()).unwrap();
+    let builder = ClientConfig::builder().with_root_certificates(roots);
+    let mut config: ClientConfig = match identity {
+        Some((cert, key)) => builder.with_client_auth_cert(vec![cert], key).unwrap(),
+        None => builder.with_no_client_auth(),
+    };
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    let connector: TlsConnector = TlsConnector::from(Arc::new(config));
+    let addr: String = format!("127.0.0.1:{}", port);
+    let stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let server_name: ServerName<'static> = ServerName::try_from(server_name.to_owned()).unwrap();
+    connector.connect(server_name, stream).await
+}
+
+#[cfg(test)]
+async fn tls_get(stream: &mut ClientTlsStream<TcpStream>, server_name: &str) -> String {
+    let request: String = format!("GET /sni HTTP/1.1\r\nHost: {}\r\n\r\n", server_name);
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let mut buffer: [u8; 1024] = [0; 1024];
+    while !response.ends_with(server_name.as_bytes()) {
+        let read = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buffer));
+        match read.await {
+            Ok(Ok(size)) if size > 0 => response.extend_from_slice(&buffer[..size]),
+            _ => break,
+        }
+    }
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[tokio::test]
+async fn test_tls_handshake() {
+    let port: usize = 60116;
+    let (cert, key) = self_signed_cert(&["localhost"]);
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.tls(vec![cert.clone()], key).await;
+    server
+        .route("/tls", |ctx: Context| async move {
+            let info: TlsInfo = ctx.get_tls_info().await.unwrap_or_default();
+            let body: String = format!(
+                "{} {} {} {}",
+                info.get_version(),
+                info.get_sni().clone().unwrap_or_default(),
+                info.get_alpn().clone().unwrap_or_default(),
+                ctx.get_socket_addr_or_default().await.ip()
+            );
+            assert!(!info.get_cipher().is_empty());
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_header(CONNECTION, CLOSE)
+                .await
+                .set_response_body(body)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let mut stream: ClientTlsStream<TcpStream> =
+        tls_connect(port, &cert, "localhost").await.unwrap();
+    stream
+        .write_all(b"GET /tls HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(
+        response.ends_with("TLSv1_3 localhost http/1.1 127.0.0.1"),
+        "{}",
+        response
+    );
+}
+
+#[tokio::test]
+async fn test_tls_pipelined_requests() {
+    let port: usize = 60132;
+    let (cert, key) = self_signed_cert(&["localhost"]);
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.tls(vec![cert.clone()], key).await;
+    server
+        .route("/echo", |ctx: Context| async move {
+            let body: String = format!(
+                "{}:{}",
+                ctx.get_request_method().await,
+                ctx.get_request_body_string().await
+            );
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_body(body)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let mut stream: ClientTlsStream<TcpStream> =
+        tls_connect(port, &cert, "localhost").await.unwrap();
+    stream
+        .write_all(
+            b"POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nfirst\
+              GET /echo HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    assert_eq!(response.matches("HTTP/1.1 200").count(), 2, "{}", response);
+    let first: usize = response.find("POST:first").unwrap();
+    let second: usize = response.find("GET:").unwrap();
+    assert!(first < second, "{}", response);
+}
+
+#[tokio::test]
+async fn test_tls_proxy_protocol() {
+    let port: usize = 60140;
+    let (cert, key) = self_signed_cert(&["localhost"]);
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.tls(vec![cert.clone()], key).await;
+    server.proxy_protocol(ProxyProtocol::Required).await;
+    server
+        .route("/peer", |ctx: Context| async move {
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_header(CONNECTION, CLOSE)
+                .await
+                .set_response_body(ctx.get_socket_addr_or_default_string().await)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut tcp: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let (client, mut relay) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        let mut hello: Vec<u8> = vec![0; 16 * 1024];
+        let size: usize = relay.read(&mut hello).await.unwrap();
+        let mut first: Vec<u8> = b"PROXY TCP4 192.0.2.9 127.0.0.1 5000 60140\r\n".to_vec();
+        first.extend_from_slice(&hello[..size]);
+        tcp.write_all(&first).await.unwrap();
+        let _ = tokio::io::copy_bidirectional(&mut relay, &mut tcp).await;
+    });
+    let mut roots: RootCertStore = RootCertStore::empty();
+    roots.add(cert).unwrap();
+    let config: ClientConfig = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let connector: TlsConnector = TlsConnector::from(Arc::new(config));
+    let server_name: ServerName<'static> = ServerName::try_from("localhost").unwrap();
+    let mut stream: ClientTlsStream<DuplexStream> =
+        connector.connect(server_name, client).await.unwrap();
+    stream
+        .write_all(b"GET /peer HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with("192.0.2.9:5000"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_tls_handshake_timeout() {
+    let port: usize = 60142;
+    let (cert, key) = self_signed_cert(&["localhost"]);
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.tls(vec![cert.clone()], key).await;
+    server
+        .tls_handshake_timeout(Duration::from_millis(200))
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut silent: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let mut buffer: [u8; 16] = [0; 16];
+    let read = tokio::time::timeout(Duration::from_secs(5), silent.read(&mut buffer));
+    assert!(matches!(read.await, Ok(Ok(0)) | Ok(Err(_))));
+    assert!(tls_connect(port, &cert, "localhost").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_tls_sni_and_reload() {
+    let port: usize = 60117;
+    let (default_cert, default_key) = self_signed_cert(&["localhost"]);
+    let (a_cert, a_key) = self_signed_cert(&["a.test"]);
+    let (wild_cert, wild_key) = self_signed_cert(&["*.wild.test"]);
+    let resolver: TlsCertResolver = TlsCertResolver::new();
+    resolver
+        .set_default(TlsCertificate::from_der(vec![default_cert.clone()], default_key).unwrap())
+        .insert(
+            "A.test",
+            TlsCertificate::from_der(vec![a_cert.clone()], a_key).unwrap(),
+        )
+        .insert(
+            "*.wild.test",
+            TlsCertificate::from_der(vec![wild_cert.clone()], wild_key).unwrap(),
+        );
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.tls_resolver(resolver.clone()).await;
+    server
+        .route("/sni", |ctx: Context| async move {
+            let info: TlsInfo = ctx.get_tls_info().await.unwrap_or_default();
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_body(info.get_sni().clone().unwrap_or_default())
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let mut a_stream: ClientTlsStream<TcpStream> =
+        tls_connect(port, &a_cert, "a.test").await.unwrap();
+    assert!(
+        tls_get(&mut a_stream, "a.test")
+            .await
+            .starts_with("HTTP/1.1 200")
+    );
+    let mut wild_stream: ClientTlsStream<TcpStream> =
+        tls_connect(port, &wild_cert, "x.wild.test").await.unwrap();
+    assert!(
+        tls_get(&mut wild_stream, "x.wild.test")
+            .await
+            .ends_with("\r\n\r\nx.wild.test")
+    );
+    assert!(tls_connect(port, &default_cert, "localhost").await.is_ok());
+    let dir: PathBuf = std::env::temp_dir().join(format!("hyperlane-tls-{}", port));
+    std::fs::create_dir_all(&dir).unwrap();
+    let renewed: rcgen::CertifiedKey<rcgen::KeyPair> = self_signed_certified(&["a.test"]);
+    std::fs::write(dir.join("a.pem"), renewed.cert.pem()).unwrap();
+    std::fs::write(dir.join("a.key"), renewed.signing_key.serialize_pem()).unwrap();
+    let watcher: AbortHandle = resolver.watch_pem_files(
+        Some("a.test".to_owned()),
+        dir.join("a.pem"),
+        dir.join("a.key"),
+        Duration::from_millis(20),
+    );
+    let renewed_cert: CertificateDer<'static> = renewed.cert.der().clone();
+    let mut renewed_stream: ClientTlsStream<TcpStream> = loop {
+        if let Ok(stream) = tls_connect(port, &renewed_cert, "a.test").await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    };
+    watcher.abort();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(tls_connect(port, &a_cert, "a.test").await.is_err());
+    assert!(
+        tls_get(&mut renewed_stream, "a.test")
+            .await
+            .starts_with("HTTP/1.1 200")
+    );
+    assert!(
+        tls_get(&mut a_stream, "a.test")
+            .await
+            .starts_with("HTTP/1.1 200")
+    );
+    let (next_cert, next_key) = self_signed_cert(&["localhost"]);
+    server
+        .reload_tls(vec![next_cert.clone()], next_key)
+        .await
+        .unwrap();
+    assert!(tls_connect(port, &default_cert, "localhost").await.is_err());
+    assert!(tls_connect(port, &next_cert, "localhost").await.is_ok());
+    assert!(resolver.remove("*.wild.test"));
+    assert!(tls_connect(port, &wild_cert, "x.wild.test").await.is_err());
+}
+
+#[tokio::test]
+async fn test_tls_client_auth() {
+    let port: usize = 60118;
+    let ca_key: rcgen::KeyPair = rcgen::KeyPair::generate().unwrap();
+    let mut ca_params: rcgen::CertificateParams =
+        rcgen::CertificateParams::new(Vec::new()).unwrap();
+    ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    ca_params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, "internal-ca");
+    let ca_cert: rcgen::Certificate = ca_params.self_signed(&ca_key).unwrap();
+    let ca: rcgen::Issuer<'_, rcgen::KeyPair> = rcgen::Issuer::new(ca_params, ca_key);
+    let client_key: rcgen::KeyPair = rcgen::KeyPair::generate().unwrap();
+    let mut client_params: rcgen::CertificateParams =
+        rcgen::CertificateParams::new(vec!["billing.internal".to_owned()]).unwrap();
+    client_params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, "billing");
+    let client_cert: CertificateDer<'static> = client_params
+        .signed_by(&client_key, &ca)
+        .unwrap()
+        .der()
+        .clone();
+    let client_identity = || {
+        (
+            client_cert.clone(),
+            PrivateKeyDer::try_from(client_key.serialize_der()).unwrap(),
+        )
+    };
+    let (cert, key) = self_signed_cert(&["localhost"]);
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.tls(vec![cert.clone()], key).await;
+    let client_auth: TlsClientAuth =
+        TlsClientAuth::from_pem(ca_cert.pem().as_bytes(), true).unwrap();
+    assert!(client_auth.is_required());
+    server.tls_client_auth(client_auth).await;
+    server
+        .route("/sni", |ctx: Context| async move {
+            let info: TlsInfo = ctx.get_tls_info().await.unwrap_or_default();
+            let body: String = format!(
+                "{} {} {:?} localhost",
+                info.get_peer_certificates().len(),
+                info.get_peer_subject().clone().unwrap_or_default(),
+                info.get_peer_sans()
+            );
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_body(body)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let mut stream: ClientTlsStream<TcpStream> =
+        tls_connect_with_identity(port, &cert, "localhost", Some(client_identity()))
+            .await
+            .unwrap();
+    let response: String = tls_get(&mut stream, "localhost").await;
+    assert!(
+        response.ends_with("\r\n\r\n1 CN=billing [Dns(\"billing.internal\")] localhost"),
+        "{}",
+        response
+    );
+    if let Ok(mut stream) = tls_connect(port, &cert, "localhost").await {
+        assert_eq!(tls_get(&mut stream, "localhost").await, "");
+    }
+    server
+        .tls_client_auth(TlsClientAuth::new(vec![ca_cert.der().clone()], false).unwrap())
+        .await;
+    let mut stream: ClientTlsStream<TcpStream> =
+        tls_connect(port, &cert, "localhost").await.unwrap();
+    assert!(
+        tls_get(&mut stream, "localhost")
+            .await
+            .ends_with("\r\n\r\n0  [] localhost")
+    );
+    let (other_cert, other_key) = self_signed_cert(&["billing.internal"]);
+    let other_identity = Some((other_cert, other_key));
+    if let Ok(mut stream) =
+        tls_connect_with_identity(port, &cert, "localhost", other_identity).await
+    {
+        assert_eq!(tls_get(&mut stream, "localhost").await, "");
+    }
+}
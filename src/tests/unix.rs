@@ -0,0 +1,126 @@
+use crate::*;
+
+#[cfg(test)]
+use std::os::unix::fs::MetadataExt;
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn test_unix_socket() {
+    let path: PathBuf = std::env::temp_dir().join(format!("hyperlane-{}.sock", std::process::id()));
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+    let server: Server = Server::new();
+    server
+        .unix_socket(&path)
+        .await
+        .unix_socket_mode(0o600)
+        .await;
+    server
+        .route("/peer", |ctx: Context| async move {
+            let peer: UnixPeer = ctx.get_unix_peer().await.unwrap();
+            let body: String = format!(
+                "{} {} {} {:?} {}",
+                peer.get_uid(),
+                peer.get_gid(),
+                peer.get_pid().unwrap_or_default(),
+                ctx.get_socket_addr().await,
+                ctx.get_socket_addr_string().await.unwrap_or_default()
+            );
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_header(CONNECTION, CLOSE)
+                .await
+                .set_response_body(body)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    let handle: JoinHandle<()> = tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let mut stream: UnixStream = loop {
+        if let Ok(stream) = UnixStream::connect(&path).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    stream
+        .write_all(b"GET /peer HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    let metadata: std::fs::Metadata = std::fs::metadata(&path).unwrap();
+    handle.abort();
+    let _ = handle.await;
+    let removed: bool = !path.exists();
+    let _ = std::fs::remove_file(&path);
+    assert!(removed);
+    assert_eq!(metadata.mode() & 0o777, 0o600);
+    let expected: String = format!(
+        "{} {} {} None unix:{}",
+        metadata.uid(),
+        metadata.gid(),
+        std::process::id(),
+        path.display()
+    );
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with(&expected), "{}", response);
+}
+
+#[tokio::test]
+async fn test_unix_socket_proxy_header() {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("hyperlane-proxy-{}.sock", std::process::id()));
+    let server: Server = Server::new();
+    server
+        .unix_socket(&path)
+        .await
+        .proxy_protocol(ProxyProtocol::Required)
+        .await;
+    server
+        .route("/peer", |ctx: Context| async move {
+            let body: String = format!("{:?}", ctx.get_socket_addr().await);
+            let _ = ctx
+                .set_response_version(HttpVersion::HTTP1_1)
+                .await
+                .set_response_status_code(200)
+                .await
+                .set_response_header(CONNECTION, CLOSE)
+                .await
+                .set_response_body(body)
+                .await
+                .send()
+                .await;
+        })
+        .await;
+    let runner: Server = server.clone();
+    let handle: JoinHandle<()> = tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let mut stream: UnixStream = loop {
+        if let Ok(stream) = UnixStream::connect(&path).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    stream
+        .write_all(b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 443\r\nGET /peer HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response: String = String::from_utf8_lossy(&response).into_owned();
+    handle.abort();
+    let _ = handle.await;
+    let _ = std::fs::remove_file(&path);
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with("Some(192.0.2.1:56324)"), "{}", response);
+}
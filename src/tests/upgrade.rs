@@ -0,0 +1,109 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+const UPGRADE_ADDR: &str = "127.0.0.1:60126";
+
+#[cfg(test)]
+async fn get(path: &str) -> String {
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(UPGRADE_ADDR).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let request: String = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        path
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[cfg(test)]
+async fn respond(ctx: &Context, body: String) {
+    let _ = ctx
+        .set_response_version(HttpVersion::HTTP1_1)
+        .await
+        .set_response_status_code(200)
+        .await
+        .set_response_header(CONNECTION, CLOSE)
+        .await
+        .set_response_body(body)
+        .await
+        .send()
+        .await;
+}
+
+#[tokio::test]
+async fn test_upgrade_handover() {
+    async fn parent_route(ctx: Context) {
+        respond(&ctx, "parent".to_string()).await;
+    }
+
+    let server: Server = Server::new();
+    server.listen_tagged(UPGRADE_ADDR, "public").await;
+    server.upgrade_drain_timeout(Duration::from_secs(5)).await;
+    server.route("/", parent_route).await;
+    assert!(matches!(
+        server.upgrade_with(Command::new("true")).await,
+        Err(ServerError::Upgrade(_))
+    ));
+    let runner: Server = server.clone();
+    let running: JoinHandle<ServerResult<()>> = tokio::spawn(async move { runner.run().await });
+    let response: String = get("/").await;
+    assert!(response.ends_with("parent"), "{}", response);
+    assert!(matches!(
+        server.upgrade_with(Command::new("true")).await,
+        Err(ServerError::Upgrade(_))
+    ));
+    let response: String = get("/").await;
+    assert!(response.ends_with("parent"), "{}", response);
+    let mut command: Command = Command::new(std::env::current_exe().unwrap());
+    command
+        .args(["tests::upgrade::upgrade_child", "--exact", "--ignored"])
+        .stdout(std::process::Stdio::null());
+    assert!(server.upgrade_with(command).await.unwrap() > 0);
+    let result: ServerResult<()> = tokio::time::timeout(Duration::from_secs(10), running)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(result.is_ok());
+    let response: String = get("/").await;
+    assert!(response.ends_with("child public"), "{}", response);
+}
+
+#[tokio::test]
+#[ignore = "started by test_upgrade_handover"]
+async fn upgrade_child() {
+    if std::env::var(UPGRADE_FDS).is_err() {
+        return;
+    }
+    let (served_sender, mut served_receiver) = tokio::sync::mpsc::channel::<()>(1);
+    let server: Server = Server::new();
+    server
+        .listen_tagged(UPGRADE_ADDR, "public")
+        .await
+        .listen_inherited()
+        .await;
+    server
+        .route("/", move |ctx: Context| {
+            let served_sender: tokio::sync::mpsc::Sender<()> = served_sender.clone();
+            async move {
+                let tag: String = ctx.get_listener_tag().await.unwrap_or_default();
+                respond(&ctx, format!("child {}", tag)).await;
+                let _ = served_sender.send(()).await;
+            }
+        })
+        .await;
+    let runner: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = runner.run().await;
+    });
+    let _ = tokio::time::timeout(Duration::from_secs(10), served_receiver.recv()).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+}
@@ -0,0 +1,764 @@
+use crate::*;
+
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+async fn websocket_server(port: usize) -> Server {
+    async fn echo_route(ctx: Context) {
+        let body: RequestBody = ctx.get_request_body().await;
+        if body == b"bye" {
+            let _ = ctx.ws_close(4000, "bye").await;
+            return;
+        }
+        let _ = ctx.set_response_body(body).await.send_body().await;
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.route("/ws", echo_route).await;
+    server
+}
+
+#[cfg(test)]
+fn spawn_server(server: &Server) {
+    let server: Server = server.clone();
+    tokio::spawn(async move {
+        let _ = server.run().await;
+    });
+}
+
+#[cfg(test)]
+async fn ws_connect(port: usize, path: &str) -> TcpStream {
+    let (stream, response) = ws_handshake(port, path, "").await;
+    assert!(response.starts_with("HTTP/1.1 101"), "{}", response);
+    stream
+}
+
+#[cfg(test)]
+async fn ws_handshake(port: usize, path: &str, headers: &str) -> (TcpStream, String) {
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut stream: TcpStream = loop {
+        if let Ok(stream) = TcpStream::connect(&addr).await {
+            break stream;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let request: String = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n{}\r\n",
+        path, headers
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response: Vec<u8> = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        let mut byte: [u8; 1] = [0];
+        if stream.read(&mut byte).await.unwrap() == 0 {
+            break;
+        }
+        response.push(byte[0]);
+    }
+    (stream, String::from_utf8_lossy(&response).into_owned())
+}
+
+#[cfg(test)]
+fn ws_client_frame(fin: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+    ws_client_frame_with_rsv(fin, 0, opcode, payload)
+}
+
+#[cfg(test)]
+fn ws_client_frame_with_rsv(fin: bool, rsv: u8, opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mask_key: [u8; 4] = [0x12, 0x34, 0x56, 0x78];
+    let mut frame: Vec<u8> = vec![if fin { 0x80 } else { 0 } | rsv | opcode];
+    if payload.len() < 126 {
+        frame.push(0x80 | payload.len() as u8);
+    } else {
+        frame.push(0x80 | 126);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    }
+    frame.extend_from_slice(&mask_key);
+    frame.extend(
+        payload
+            .iter()
+            .enumerate()
+            .map(|(index, byte)| byte ^ mask_key[index % 4]),
+    );
+    frame
+}
+
+#[cfg(test)]
+async fn ws_request(stream: &mut TcpStream, body: &str) -> (bool, u8, Vec<u8>) {
+    stream
+        .write_all(&ws_client_frame(true, 0x1, body.as_bytes()))
+        .await
+        .unwrap();
+    ws_read_frame(stream).await
+}
+
+#[cfg(test)]
+async fn ws_read_frame(stream: &mut TcpStream) -> (bool, u8, Vec<u8>) {
+    let mut header: [u8; 2] = [0; 2];
+    stream.read_exact(&mut header).await.unwrap();
+    let len: usize = match header[1] & 0x7F {
+        126 => stream.read_u16().await.unwrap() as usize,
+        127 => stream.read_u64().await.unwrap() as usize,
+        len => len as usize,
+    };
+    let mut payload: Vec<u8> = vec![0; len];
+    stream.read_exact(&mut payload).await.unwrap();
+    (header[0] & 0x80 != 0, header[0] & 0x4F, payload)
+}
+
+#[tokio::test]
+async fn test_ws_reassembly_and_control_frames() {
+    let port: usize = 60106;
+    let server: Server = websocket_server(port).await;
+    spawn_server(&server);
+    let mut stream: TcpStream = ws_connect(port, "/ws").await;
+    let mut data: Vec<u8> = ws_client_frame(false, 0x1, b"hel");
+    data.extend(ws_client_frame(true, 0x9, b"beat"));
+    data.extend(ws_client_frame(false, 0x0, b"lo "));
+    data.extend(ws_client_frame(true, 0x0, b"world"));
+    data.extend(ws_client_frame(true, 0x2, &[0, 159, 146, 150]));
+    stream.write_all(&data).await.unwrap();
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0xA, b"beat".to_vec())
+    );
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x1, b"hello world".to_vec())
+    );
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x2, vec![0, 159, 146, 150])
+    );
+    stream
+        .write_all(&ws_client_frame(true, 0x8, &1000u16.to_be_bytes()))
+        .await
+        .unwrap();
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x8, 1000u16.to_be_bytes().to_vec())
+    );
+    let mut rest: Vec<u8> = Vec::new();
+    let _ = stream.read_to_end(&mut rest).await;
+    assert!(rest.is_empty());
+}
+
+#[tokio::test]
+async fn test_ws_close_handshake() {
+    let port: usize = 60107;
+    let server: Server = websocket_server(port).await;
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<WebSocketClose>();
+    server
+        .ws_close_timeout(Duration::from_millis(200))
+        .await
+        .ws_closed_hook(move |ctx: Context| {
+            let sender: tokio::sync::mpsc::UnboundedSender<WebSocketClose> = sender.clone();
+            async move {
+                let _ = sender.send(ctx.get_ws_close().await.unwrap());
+            }
+        })
+        .await;
+    spawn_server(&server);
+    let mut stream: TcpStream = ws_connect(port, "/ws").await;
+    stream
+        .write_all(&ws_client_frame(true, 0x1, b"bye"))
+        .await
+        .unwrap();
+    let (_, opcode, payload) = ws_read_frame(&mut stream).await;
+    assert_eq!(opcode, 0x8);
+    assert_eq!(payload, b"\x0f\xa0bye".to_vec());
+    stream
+        .write_all(&ws_client_frame(true, 0x8, &payload[..2]))
+        .await
+        .unwrap();
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        WebSocketClose::new(4000, "bye")
+    );
+    let mut stream: TcpStream = ws_connect(port, "/ws").await;
+    let mut payload: Vec<u8> = WS_CLOSE_GOING_AWAY.to_be_bytes().to_vec();
+    payload.extend_from_slice(b"away");
+    stream
+        .write_all(&ws_client_frame(true, 0x8, &payload))
+        .await
+        .unwrap();
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x8, WS_CLOSE_GOING_AWAY.to_be_bytes().to_vec())
+    );
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        WebSocketClose::new(WS_CLOSE_GOING_AWAY, "away")
+    );
+    let stream: TcpStream = ws_connect(port, "/ws").await;
+    drop(stream);
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        WebSocketClose::new(WS_CLOSE_ABNORMAL, "")
+    );
+}
+
+#[tokio::test]
+async fn test_ws_heartbeat() {
+    let port: usize = 60108;
+    let server: Server = websocket_server(port).await;
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<WebSocketClose>();
+    server
+        .route("/idle", |_: Context| async {})
+        .await
+        .ws_heartbeat(
+            WebSocketHeartbeat::new()
+                .with_ping_interval(Duration::from_millis(50))
+                .with_pong_timeout(Duration::from_millis(100)),
+        )
+        .await
+        .ws_route_heartbeat(
+            "/idle",
+            WebSocketHeartbeat::new().with_idle_timeout(Duration::from_millis(100)),
+        )
+        .await
+        .ws_closed_hook(move |ctx: Context| {
+            let sender: tokio::sync::mpsc::UnboundedSender<WebSocketClose> = sender.clone();
+            async move {
+                let _ = sender.send(ctx.get_ws_close().await.unwrap());
+            }
+        })
+        .await;
+    spawn_server(&server);
+    let mut stream: TcpStream = ws_connect(port, "/ws").await;
+    for _ in 0..3 {
+        let (_, opcode, payload) = ws_read_frame(&mut stream).await;
+        assert_eq!(opcode, 0x9);
+        stream
+            .write_all(&ws_client_frame(true, 0xA, &payload))
+            .await
+            .unwrap();
+    }
+    assert_eq!(ws_read_frame(&mut stream).await.1, 0x9);
+    let (_, opcode, payload) = ws_read_frame(&mut stream).await;
+    assert_eq!(opcode, 0x8);
+    assert_eq!(&payload[..2], &WS_CLOSE_GOING_AWAY.to_be_bytes());
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        WebSocketClose::new(WS_CLOSE_GOING_AWAY, "pong timeout")
+    );
+    let mut rest: Vec<u8> = Vec::new();
+    let _ = stream.read_to_end(&mut rest).await;
+    assert!(rest.is_empty());
+    let mut stream: TcpStream = ws_connect(port, "/idle").await;
+    let (_, opcode, payload) = ws_read_frame(&mut stream).await;
+    assert_eq!(opcode, 0x8);
+    assert_eq!(&payload[..2], &WS_CLOSE_GOING_AWAY.to_be_bytes());
+    stream
+        .write_all(&ws_client_frame(true, 0x8, &payload[..2]))
+        .await
+        .unwrap();
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        WebSocketClose::new(WS_CLOSE_GOING_AWAY, "idle timeout")
+    );
+    let mut stream: TcpStream = ws_connect(port, "/ws").await;
+    let frame: Vec<u8> = ws_client_frame(true, 0x1, b"hello");
+    stream.write_all(&frame[..4]).await.unwrap();
+    let (_, opcode, payload) = ws_read_frame(&mut stream).await;
+    assert_eq!(opcode, 0x9);
+    stream.write_all(&frame[4..]).await.unwrap();
+    stream
+        .write_all(&ws_client_frame(true, 0xA, &payload))
+        .await
+        .unwrap();
+    assert_eq!(ws_read_frame(&mut stream).await.2, b"hello");
+}
+
+#[tokio::test]
+async fn test_ws_deflate_negotiation() {
+    let config: WebSocketDeflate = WebSocketDeflate::new().with_client_max_window_bits(12);
+    let agreement: WebSocketDeflateAgreement = negotiate_ws_deflate(
+        "x-webkit-deflate-frame, permessage-deflate; server_max_window_bits=8, permessage-deflate; server_max_window_bits=10; client_max_window_bits",
+        &config,
+    )
+    .unwrap();
+    assert_eq!(
+        agreement.to_header(),
+        "permessage-deflate; server_max_window_bits=10; client_max_window_bits=12"
+    );
+    assert!(negotiate_ws_deflate("permessage-deflate; foo", &config).is_none());
+    assert_eq!(
+        negotiate_ws_deflate(
+            "permessage-deflate; client_max_window_bits=8, permessage-deflate",
+            &config
+        )
+        .unwrap()
+        .to_header(),
+        "permessage-deflate"
+    );
+    assert!(
+        negotiate_ws_deflate(
+            "permessage-deflate; server_no_context_takeover; server_no_context_takeover",
+            &config
+        )
+        .is_none()
+    );
+    assert_eq!(
+        negotiate_ws_deflate("permessage-deflate", &config)
+            .unwrap()
+            .to_header(),
+        "permessage-deflate"
+    );
+}
+
+#[tokio::test]
+async fn test_ws_deflate_messages() {
+    let deflate = |compress: &mut DeflateCompress, data: &[u8]| -> Vec<u8> {
+        let mut output: Vec<u8> = Vec::with_capacity(data.len() + 64);
+        compress
+            .compress_vec(data, &mut output, FlushCompress::Sync)
+            .unwrap();
+        output.truncate(output.len() - 4);
+        output
+    };
+    let inflate = |data: &[u8]| -> Vec<u8> {
+        let mut decompress: DeflateDecompress = DeflateDecompress::new(false);
+        let mut input: Vec<u8> = data.to_vec();
+        input.extend_from_slice(&[0, 0, 0xFF, 0xFF]);
+        let mut output: Vec<u8> = Vec::with_capacity(4096);
+        decompress
+            .decompress_vec(&input, &mut output, FlushDecompress::Sync)
+            .unwrap();
+        output
+    };
+    let port: usize = 60109;
+    let server: Server = websocket_server(port).await;
+    server
+        .enable_ws_deflate(
+            WebSocketDeflate::new()
+                .with_server_no_context_takeover(true)
+                .with_client_max_window_bits(10),
+        )
+        .await;
+    spawn_server(&server);
+    let (mut stream, response) = ws_handshake(
+        port,
+        "/ws",
+        "Sec-WebSocket-Extensions: permessage-deflate; client_max_window_bits\r\n",
+    )
+    .await;
+    assert!(
+        response.to_ascii_lowercase().contains(
+            "sec-websocket-extensions: permessage-deflate; server_no_context_takeover; client_max_window_bits=10"
+        ),
+        "{}",
+        response
+    );
+    let message: Vec<u8> = "{\"series\":[1,2,3,4,5,6,7,8,9]}".repeat(20).into_bytes();
+    let mut compress: DeflateCompress =
+        DeflateCompress::new_with_window_bits(DeflateLevel::default(), false, 10);
+    for _ in 0..2 {
+        let compressed: Vec<u8> = deflate(&mut compress, &message);
+        stream
+            .write_all(&ws_client_frame_with_rsv(true, 0x40, 0x1, &compressed))
+            .await
+            .unwrap();
+        let (fin, opcode, payload) = ws_read_frame(&mut stream).await;
+        assert!(fin);
+        assert_eq!(opcode, 0x41);
+        assert!(payload.len() < message.len());
+        assert_eq!(inflate(&payload), message);
+    }
+    let (mut stream, response) = ws_handshake(port, "/ws", "").await;
+    assert!(
+        !response
+            .to_ascii_lowercase()
+            .contains("sec-websocket-extensions")
+    );
+    stream
+        .write_all(&ws_client_frame(true, 0x1, b"plain"))
+        .await
+        .unwrap();
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x1, b"plain".to_vec())
+    );
+}
+
+#[tokio::test]
+async fn test_ws_upgrade_policy() {
+    let port: usize = 60110;
+    let server: Server = websocket_server(port).await;
+    server
+        .route("/chat", |ctx: Context| async move {
+            let protocol: String = ctx.get_ws_protocol().await.unwrap_or_default();
+            let _ = ctx.set_response_body(protocol).await.send_body().await;
+        })
+        .await;
+    server
+        .ws_policy(WebSocketPolicy::new().with_allowed_origins(["https://example.com"]))
+        .await;
+    server
+        .ws_route_policy(
+            "/chat",
+            WebSocketPolicy::new()
+                .with_protocols(["chat.v2", "chat.v1"])
+                .with_protocol_required(true),
+        )
+        .await;
+    spawn_server(&server);
+    let (_, response) = ws_handshake(port, "/ws", "Origin: https://evil.example\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+    let (_, response) = ws_handshake(port, "/ws", "Origin: HTTPS://EXAMPLE.COM\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 101"), "{}", response);
+    ws_connect(port, "/ws").await;
+    let (mut stream, response) = ws_handshake(
+        port,
+        "/chat",
+        "Sec-WebSocket-Protocol: chat.v3, chat.v1, chat.v2\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 101"), "{}", response);
+    assert!(
+        response
+            .to_ascii_lowercase()
+            .contains("sec-websocket-protocol: chat.v1"),
+        "{}",
+        response
+    );
+    stream
+        .write_all(&ws_client_frame(true, 0x1, b"which"))
+        .await
+        .unwrap();
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x1, b"chat.v1".to_vec())
+    );
+    let (_, response) = ws_handshake(port, "/chat", "Sec-WebSocket-Protocol: chat.v3\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+    let (_, response) = ws_handshake(port, "/chat", "").await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+    let (_, response) = ws_handshake(
+        port,
+        "/chat",
+        "Origin: https://evil.example\r\nSec-WebSocket-Protocol: chat.v1\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+    let (_, response) = ws_handshake(
+        port,
+        "/chat",
+        "Origin: https://example.com\r\nSec-WebSocket-Protocol: chat.v1\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 101"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_ws_policy_trailing_slash() {
+    let port: usize = 60138;
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await.port(port).await;
+    server.trailing_slash(TrailingSlash::Ignore).await;
+    server
+        .route("/chat", |ctx: Context| async move {
+            let _ = ctx.set_response_body("chat").await.send_body().await;
+        })
+        .await;
+    server
+        .ws_route_policy(
+            "/chat",
+            WebSocketPolicy::new().with_allowed_origins(["https://example.com"]),
+        )
+        .await;
+    spawn_server(&server);
+    let (_, response) = ws_handshake(port, "/chat/", "Origin: https://evil.example\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+    let (_, response) = ws_handshake(port, "/chat/", "Origin: https://example.com\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 101"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_ws_hub_broadcast() {
+    let port: usize = 60111;
+    let server: Server = websocket_server(port).await;
+    server
+        .route("/room", |ctx: Context| async move {
+            let body: String = ctx.get_request_body_string().await;
+            if body == "join" {
+                ctx.ws_join("lobby").await.unwrap();
+                let _ = ctx.set_response_body("joined").await.send_body().await;
+                return;
+            }
+            ctx.get_ws_hub()
+                .await
+                .broadcast_text("lobby", body, Some(&ctx))
+                .await;
+        })
+        .await;
+    spawn_server(&server);
+    let mut clients: Vec<TcpStream> = Vec::new();
+    for _ in 0..3 {
+        let mut stream: TcpStream = ws_connect(port, "/room").await;
+        stream
+            .write_all(&ws_client_frame(true, 0x1, b"join"))
+            .await
+            .unwrap();
+        assert_eq!(
+            ws_read_frame(&mut stream).await,
+            (true, 0x1, b"joined".to_vec())
+        );
+        clients.push(stream);
+    }
+    let hub: WebSocketHub = server.get_ws_hub().await;
+    assert_eq!(hub.get_room_size("lobby").await, 3);
+    clients[0]
+        .write_all(&ws_client_frame(true, 0x1, b"hello"))
+        .await
+        .unwrap();
+    for stream in clients.iter_mut().skip(1) {
+        assert_eq!(ws_read_frame(stream).await, (true, 0x1, b"hello".to_vec()));
+    }
+    assert_eq!(
+        hub.broadcast_binary("lobby", vec![0xFF, 0x00], None).await,
+        3
+    );
+    for stream in clients.iter_mut() {
+        assert_eq!(ws_read_frame(stream).await, (true, 0x2, vec![0xFF, 0x00]));
+    }
+    assert_eq!(
+        hub.broadcast_binary("lobby", b"bytes".to_vec(), None).await,
+        3
+    );
+    for stream in clients.iter_mut() {
+        assert_eq!(ws_read_frame(stream).await, (true, 0x2, b"bytes".to_vec()));
+    }
+    drop(clients.pop());
+    for _ in 0..100 {
+        if hub.get_room_size("lobby").await == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(hub.get_room_size("lobby").await, 2);
+    assert_eq!(hub.broadcast_text("empty", "nobody", None).await, 0);
+    assert!(Context::default().ws_join("lobby").await.is_err());
+    assert_eq!(hub.get_room_size("lobby").await, 2);
+}
+
+#[tokio::test]
+async fn test_ws_explicit_frames() {
+    let port: usize = 60112;
+    let server: Server = websocket_server(port).await;
+    server
+        .route("/frames", |ctx: Context| async move {
+            let body: String = ctx.get_request_body_string().await;
+            let _ = match body.as_str() {
+                "text" => ctx.ws_send_text("caf\u{e9}").await,
+                "binary" => ctx.ws_send_binary(b"caf").await,
+                "ping" => match ctx.ws_send_ping(vec![0; 126]).await {
+                    Ok(_) => Ok(()),
+                    Err(_) => ctx.ws_send_ping("p").await,
+                },
+                "stream" => {
+                    let mut writer: WebSocketMessageWriter = ctx.ws_start_text().await.unwrap();
+                    writer.send("ab").await.unwrap();
+                    writer.send("cd").await.unwrap();
+                    writer.finish("ef").await
+                }
+                _ => match ctx.ws_send_close(1005, "reserved").await {
+                    Ok(_) => Ok(()),
+                    Err(_) => ctx.ws_send_close(4001, "done").await,
+                },
+            };
+        })
+        .await;
+    server.enable_ws_deflate(WebSocketDeflate::new()).await;
+    spawn_server(&server);
+    let mut stream: TcpStream = ws_connect(port, "/frames").await;
+    assert_eq!(
+        ws_request(&mut stream, "text").await,
+        (true, 0x1, "caf\u{e9}".as_bytes().to_vec())
+    );
+    assert_eq!(
+        ws_request(&mut stream, "binary").await,
+        (true, 0x2, b"caf".to_vec())
+    );
+    assert_eq!(
+        ws_request(&mut stream, "ping").await,
+        (true, 0x9, b"p".to_vec())
+    );
+    assert_eq!(
+        ws_request(&mut stream, "stream").await,
+        (false, 0x1, b"ab".to_vec())
+    );
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (false, 0x0, b"cd".to_vec())
+    );
+    assert_eq!(
+        ws_read_frame(&mut stream).await,
+        (true, 0x0, b"ef".to_vec())
+    );
+    let (fin, opcode, payload) = ws_request(&mut stream, "close").await;
+    assert_eq!((fin, opcode), (true, 0x8));
+    assert_eq!(payload, WebSocketClose::new(4001, "done").to_payload());
+    let (mut stream, _) = ws_handshake(
+        port,
+        "/frames",
+        "Sec-WebSocket-Extensions: permessage-deflate\r\n",
+    )
+    .await;
+    stream
+        .write_all(&ws_client_frame(true, 0x1, b"stream"))
+        .await
+        .unwrap();
+    let mut compressed: Vec<u8> = Vec::new();
+    for expected in [(false, 0x41), (false, 0x0), (true, 0x0)] {
+        let (fin, opcode, payload) = ws_read_frame(&mut stream).await;
+        assert_eq!((fin, opcode), expected);
+        compressed.extend(payload);
+    }
+    compressed.extend_from_slice(&[0, 0, 0xFF, 0xFF]);
+    let mut output: Vec<u8> = Vec::with_capacity(64);
+    DeflateDecompress::new(false)
+        .decompress_vec(&compressed, &mut output, FlushDecompress::Sync)
+        .unwrap();
+    assert_eq!(output, b"abcdef");
+}
+
+#[tokio::test]
+async fn test_ws_frame_validation() {
+    let port: usize = 60113;
+    let server: Server = websocket_server(port).await;
+    server.ws_max_message_size(16).await;
+    spawn_server(&server);
+    let mut unmasked: Vec<u8> = vec![0x81, 2];
+    unmasked.extend_from_slice(b"hi");
+    let mut announced: Vec<u8> = vec![0x82, 0x80 | 127];
+    announced.extend_from_slice(&(1u64 << 40).to_be_bytes());
+    let mut fragmented: Vec<u8> = ws_client_frame(false, 0x2, &[0; 10]);
+    fragmented.extend(ws_client_frame(true, 0x0, &[0; 10]));
+    let cases: Vec<(Vec<u8>, u16)> = vec![
+        (unmasked, WS_CLOSE_PROTOCOL_ERROR),
+        (ws_client_frame(true, 0x3, b""), WS_CLOSE_PROTOCOL_ERROR),
+        (ws_client_frame(false, 0x9, b""), WS_CLOSE_PROTOCOL_ERROR),
+        (ws_client_frame(true, 0x8, &[0x03]), WS_CLOSE_PROTOCOL_ERROR),
+        (
+            ws_client_frame(true, 0x8, &1005u16.to_be_bytes()),
+            WS_CLOSE_PROTOCOL_ERROR,
+        ),
+        (
+            ws_client_frame_with_rsv(true, 0x20, 0x1, b"hi"),
+            WS_CLOSE_PROTOCOL_ERROR,
+        ),
+        (
+            ws_client_frame(true, 0x1, &[0xC3, 0x28]),
+            WS_CLOSE_INVALID_PAYLOAD,
+        ),
+        (
+            ws_client_frame(true, 0x2, &[0; 17]),
+            WS_CLOSE_MESSAGE_TOO_BIG,
+        ),
+        (fragmented, WS_CLOSE_MESSAGE_TOO_BIG),
+        (announced, WS_CLOSE_MESSAGE_TOO_BIG),
+        (vec![0x82, 126, 0xFF, 0xFF], WS_CLOSE_PROTOCOL_ERROR),
+        (vec![0x83, 0x80 | 126, 0xFF, 0xFF], WS_CLOSE_PROTOCOL_ERROR),
+        (vec![0xA2, 0x80 | 126, 0xFF, 0xFF], WS_CLOSE_PROTOCOL_ERROR),
+    ];
+    for (frames, code) in cases {
+        let mut stream: TcpStream = ws_connect(port, "/ws").await;
+        stream.write_all(&frames).await.unwrap();
+        let (fin, opcode, payload) = ws_read_frame(&mut stream).await;
+        assert_eq!((fin, opcode), (true, 0x8));
+        assert_eq!(u16::from_be_bytes([payload[0], payload[1]]), code);
+    }
+    let mut stream: TcpStream = ws_connect(port, "/ws").await;
+    assert_eq!(
+        ws_request(&mut stream, "sixteen-byte-msg").await,
+        (true, 0x1, b"sixteen-byte-msg".to_vec())
+    );
+}
+
+#[cfg(feature = "websocket-client")]
+#[tokio::test]
+async fn test_ws_client() {
+    let port: usize = 60114;
+    let server: Server = websocket_server(port).await;
+    server
+        .route("/stream", |ctx: Context| async move {
+            let mut writer: WebSocketMessageWriter = ctx.ws_start_binary().await.unwrap();
+            writer.send([1, 2]).await.unwrap();
+            let _ = writer.finish([3]).await;
+            let _ = ctx.ws_send_ping("beat").await;
+        })
+        .await;
+    server
+        .route("/invalid", |ctx: Context| async move {
+            let writer: WebSocketMessageWriter = ctx.ws_start_text().await.unwrap();
+            let _ = writer.finish([0xC3, 0x28]).await;
+        })
+        .await;
+    server
+        .ws_route_policy(
+            "/stream",
+            WebSocketPolicy::new().with_protocols(["stream.v1"]),
+        )
+        .await;
+    spawn_server(&server);
+    let addr: String = format!("127.0.0.1:{}", port);
+    let mut client: WebSocketClient = loop {
+        if let Ok(client) = WebSocketClient::connect(&addr, "/ws").await {
+            break client;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    client.send_text("hello").await.unwrap();
+    assert_eq!(
+        client.read_message().await,
+        Ok(WebSocketMessage::Text("hello".to_owned()))
+    );
+    client.send_binary([0xFF, 0x00]).await.unwrap();
+    assert_eq!(
+        client.read_message().await,
+        Ok(WebSocketMessage::Binary(vec![0xFF, 0x00]))
+    );
+    client.send_text("bye").await.unwrap();
+    assert_eq!(
+        client.read_message().await,
+        Ok(WebSocketMessage::Close(WebSocketClose::new(4000, "bye")))
+    );
+    let mut client: WebSocketClient = WebSocketClient::connect_with_headers(
+        &addr,
+        "/stream",
+        [("Sec-WebSocket-Protocol", "stream.v1")],
+    )
+    .await
+    .unwrap();
+    assert_eq!(client.get_protocol(), Some(&"stream.v1".to_owned()));
+    client.send_text("go").await.unwrap();
+    assert_eq!(
+        client.read_message().await,
+        Ok(WebSocketMessage::Binary(vec![1, 2, 3]))
+    );
+    assert_eq!(
+        client.read_message().await,
+        Ok(WebSocketMessage::Ping(b"beat".to_vec()))
+    );
+    client.send_close(WS_CLOSE_NORMAL, "done").await.unwrap();
+    assert_eq!(
+        client.read_message().await,
+        Ok(WebSocketMessage::Close(WebSocketClose::new(
+            WS_CLOSE_NORMAL,
+            EMPTY_STR
+        )))
+    );
+    let mut client: WebSocketClient = WebSocketClient::connect(&addr, "/invalid").await.unwrap();
+    client.send_text("go").await.unwrap();
+    assert!(matches!(
+        client.read_message().await,
+        Err(WebSocketClientError::Protocol(_))
+    ));
+    assert_eq!(
+        client.send_text("again").await,
+        Err(WebSocketClientError::Closed)
+    );
+}
@@ -0,0 +1,18 @@
+#[cfg(feature = "tls")]
+use crate::*;
+
+/// The ALPN protocol identifier of HTTP/1.1.
+#[cfg(feature = "tls")]
+pub(crate) const ALPN_HTTP1_1: &[u8] = b"http/1.1";
+
+/// The default longest time a client may take to complete the TLS handshake.
+#[cfg(feature = "tls")]
+pub(crate) const DEFAULT_TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The label that starts a wildcard server name.
+#[cfg(feature = "tls")]
+pub(crate) const TLS_WILDCARD_PREFIX: &str = "*.";
+
+/// The separator between the labels of a server name.
+#[cfg(feature = "tls")]
+pub(crate) const TLS_NAME_SEPARATOR: char = '.';
@@ -0,0 +1,14 @@
+use crate::*;
+
+/// A subject alternative name of a peer certificate.
+#[derive(Clone, CustomDebug, DisplayDebug, PartialEq, Eq)]
+pub enum TlsSubjectAltName {
+    /// A DNS name, such as `service.internal`.
+    Dns(String),
+    /// An IP address.
+    Ip(IpAddr),
+    /// An e-mail address.
+    Email(String),
+    /// A uniform resource identifier, such as a SPIFFE id.
+    Uri(String),
+}
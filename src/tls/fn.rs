@@ -0,0 +1,109 @@
+use crate::*;
+
+/// Builds the TLS acceptor that selects certificates through a resolver.
+///
+/// # Arguments
+///
+/// - `&TlsCertResolver` - The resolver consulted on every handshake.
+/// - `Option<&TlsClientAuth>` - The client certificate verification, if any.
+/// - `VecVecU8` - The protocols advertised through ALPN, most preferred first.
+///
+/// # Returns
+///
+/// - `TlsAcceptor` - The acceptor.
+pub(crate) fn create_tls_acceptor(
+    resolver: &TlsCertResolver,
+    client_auth: Option<&TlsClientAuth>,
+    alpn_protocols: VecVecU8,
+) -> TlsAcceptor {
+    let builder = TlsServerConfig::builder();
+    let builder = match client_auth {
+        Some(client_auth) => builder.with_client_cert_verifier(client_auth.get_verifier()),
+        None => builder.with_no_client_auth(),
+    };
+    let mut config: TlsServerConfig = builder.with_cert_resolver(Arc::new(resolver.clone()));
+    config.alpn_protocols = alpn_protocols;
+    TlsAcceptor::from(Arc::new(config))
+}
+
+/// Retrieves the cryptographic provider used to load private keys.
+///
+/// # Returns
+///
+/// - `ArcCryptoProvider` - The process-wide default provider, or the ring provider if none is installed.
+pub(crate) fn default_crypto_provider() -> ArcCryptoProvider {
+    CryptoProvider::get_default()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(ring_default_provider()))
+}
+
+/// Retrieves the latest modification time of a pair of files.
+///
+/// # Arguments
+///
+/// - `&Path` - The first file.
+/// - `&Path` - The second file.
+///
+/// # Returns
+///
+/// - `OptionSystemTime` - The later modification time, or None if either file cannot be inspected.
+pub(crate) async fn latest_modified(first: &Path, second: &Path) -> OptionSystemTime {
+    let first: SystemTime = tokio::fs::metadata(first).await.ok()?.modified().ok()?;
+    let second: SystemTime = tokio::fs::metadata(second).await.ok()?.modified().ok()?;
+    Some(first.max(second))
+}
+
+/// Reads the subject and the subject alternative names of a certificate.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The DER encoded certificate.
+///
+/// # Returns
+///
+/// - `(OptionString, VecTlsSubjectAltName)` - The subject and the supported alternative names,
+///   or nothing if the certificate cannot be parsed.
+pub(crate) fn parse_peer_identity(certificate: &[u8]) -> (OptionString, VecTlsSubjectAltName) {
+    let Ok((_, certificate)) = X509Certificate::from_der(certificate) else {
+        return (None, Vec::new());
+    };
+    let subject: String = certificate.subject().to_string();
+    let sans: VecTlsSubjectAltName = certificate
+        .subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|extension| {
+            extension
+                .value
+                .general_names
+                .iter()
+                .filter_map(|name: &GeneralName| match name {
+                    GeneralName::DNSName(dns) => Some(TlsSubjectAltName::Dns(dns.to_string())),
+                    GeneralName::RFC822Name(email) => {
+                        Some(TlsSubjectAltName::Email(email.to_string()))
+                    }
+                    GeneralName::URI(uri) => Some(TlsSubjectAltName::Uri(uri.to_string())),
+                    GeneralName::IPAddress(bytes) => parse_san_ip(bytes).map(TlsSubjectAltName::Ip),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (Some(subject), sans)
+}
+
+/// Decodes the IP address of a subject alternative name.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The four or sixteen address bytes.
+///
+/// # Returns
+///
+/// - `OptionIpAddr` - The address, or None if the length is invalid.
+fn parse_san_ip(bytes: &[u8]) -> OptionIpAddr {
+    if let Ok(octets) = <[u8; 4]>::try_from(bytes) {
+        return Some(IpAddr::from(octets));
+    }
+    <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from)
+}
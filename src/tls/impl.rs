@@ -0,0 +1,360 @@
+use crate::*;
+
+/// Implementation of the handshake details for `TlsInfo`.
+impl TlsInfo {
+    /// Collects the negotiated parameters of an established TLS session.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ServerConnection` - The server side of the TLS session.
+    ///
+    /// # Returns
+    ///
+    /// - `TlsInfo` - The negotiated parameters.
+    pub(crate) fn from_connection(connection: &ServerConnection) -> Self {
+        let peer_certificates: VecVecU8 = connection
+            .peer_certificates()
+            .unwrap_or_default()
+            .iter()
+            .map(|certificate: &CertificateDer<'static>| certificate.to_vec())
+            .collect();
+        let (peer_subject, peer_sans) = peer_certificates
+            .first()
+            .map(|leaf: &Vec<u8>| parse_peer_identity(leaf))
+            .unwrap_or_default();
+        Self {
+            version: connection
+                .protocol_version()
+                .and_then(|version| version.as_str())
+                .unwrap_or_default()
+                .to_owned(),
+            cipher: connection
+                .negotiated_cipher_suite()
+                .and_then(|suite| suite.suite().as_str())
+                .unwrap_or_default()
+                .to_owned(),
+            sni: connection.server_name().map(str::to_owned),
+            alpn: connection
+                .alpn_protocol()
+                .map(|protocol| String::from_utf8_lossy(protocol).into_owned()),
+            peer_certificates,
+            peer_subject,
+            peer_sans,
+        }
+    }
+}
+
+/// Implementation of methods for `TlsCertificate` structure.
+impl TlsCertificate {
+    /// Creates a certificate from a DER encoded chain and private key.
+    ///
+    /// # Arguments
+    ///
+    /// - `VecCertificateDer` - The certificate chain, leaf certificate first.
+    /// - `PrivateKeyDer<'static>` - The private key of the leaf certificate.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultTlsCertificateTlsError` - The certificate, or a `TlsError` if the key does not fit the chain.
+    pub fn from_der(
+        cert_chain: VecCertificateDer,
+        key: PrivateKeyDer<'static>,
+    ) -> ResultTlsCertificateTlsError {
+        if cert_chain.is_empty() {
+            return Err(TlsError::EmptyCertificateChain);
+        }
+        let provider: ArcCryptoProvider = default_crypto_provider();
+        CertifiedKey::from_der(cert_chain, key, &provider)
+            .map(|certified_key: CertifiedKey| Self(Arc::new(certified_key)))
+            .map_err(|err| TlsError::InvalidCertificate(err.to_string()))
+    }
+
+    /// Creates a certificate from a PEM encoded chain and private key.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The PEM encoded certificate chain, leaf certificate first.
+    /// - `&[u8]` - The PEM encoded private key of the leaf certificate.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultTlsCertificateTlsError` - The certificate, or a `TlsError` if either input is invalid.
+    pub fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> ResultTlsCertificateTlsError {
+        let cert_chain: VecCertificateDer = CertificateDer::pem_slice_iter(cert_pem)
+            .collect::<Result<VecCertificateDer, _>>()
+            .map_err(|err| TlsError::InvalidCertificate(err.to_string()))?;
+        let key: PrivateKeyDer<'static> = PrivateKeyDer::from_pem_slice(key_pem)
+            .map_err(|err| TlsError::InvalidCertificate(err.to_string()))?;
+        Self::from_der(cert_chain, key)
+    }
+
+    /// Reads a certificate from a PEM encoded chain file and private key file.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path` - The path of the certificate chain file.
+    /// - `&Path` - The path of the private key file.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultTlsCertificateTlsError` - The certificate, or a `TlsError` if either file is invalid.
+    pub async fn from_pem_files(cert_path: &Path, key_path: &Path) -> ResultTlsCertificateTlsError {
+        let cert_pem: Vec<u8> = tokio::fs::read(cert_path)
+            .await
+            .map_err(|err| TlsError::Io(err.to_string()))?;
+        let key_pem: Vec<u8> = tokio::fs::read(key_path)
+            .await
+            .map_err(|err| TlsError::Io(err.to_string()))?;
+        Self::from_pem(&cert_pem, &key_pem)
+    }
+}
+
+/// Implementation of methods for `TlsClientAuth` structure.
+impl TlsClientAuth {
+    /// Creates the client certificate verification for a CA bundle.
+    ///
+    /// # Arguments
+    ///
+    /// - `VecCertificateDer` - The CA certificates that client certificates must chain to.
+    /// - `bool` - Whether connections without a client certificate are rejected.
+    ///   Certificates that are presented are always verified.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultTlsClientAuthTlsError` - The verification, or a `TlsError` if the bundle is invalid.
+    pub fn new(ca_certs: VecCertificateDer, required: bool) -> ResultTlsClientAuthTlsError {
+        let mut roots: RootCertStore = RootCertStore::empty();
+        for ca_cert in ca_certs {
+            roots
+                .add(ca_cert)
+                .map_err(|err| TlsError::InvalidClientCa(err.to_string()))?;
+        }
+        let mut builder =
+            WebPkiClientVerifier::builder_with_provider(Arc::new(roots), default_crypto_provider());
+        if !required {
+            builder = builder.allow_unauthenticated();
+        }
+        let verifier: ArcClientCertVerifier = builder
+            .build()
+            .map_err(|err| TlsError::InvalidClientCa(err.to_string()))?;
+        Ok(Self { verifier, required })
+    }
+
+    /// Creates the client certificate verification for a PEM encoded CA bundle.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The PEM encoded CA certificates.
+    /// - `bool` - Whether connections without a client certificate are rejected.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultTlsClientAuthTlsError` - The verification, or a `TlsError` if the bundle is invalid.
+    pub fn from_pem(ca_pem: &[u8], required: bool) -> ResultTlsClientAuthTlsError {
+        let ca_certs: VecCertificateDer = CertificateDer::pem_slice_iter(ca_pem)
+            .collect::<Result<VecCertificateDer, _>>()
+            .map_err(|err| TlsError::InvalidClientCa(err.to_string()))?;
+        Self::new(ca_certs, required)
+    }
+
+    /// Retrieves the verifier that checks client certificates.
+    ///
+    /// # Returns
+    ///
+    /// - `ArcClientCertVerifier` - The shared verifier.
+    pub(crate) fn get_verifier(&self) -> ArcClientCertVerifier {
+        self.verifier.clone()
+    }
+
+    /// Checks whether connections without a client certificate are rejected.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if a client certificate is required, false otherwise.
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+/// Implementation of methods for `TlsCertResolver` structure.
+impl TlsCertResolver {
+    /// Creates a resolver without any certificate.
+    ///
+    /// # Returns
+    ///
+    /// - `TlsCertResolver` - The new resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Acquires a read lock on the certificate store.
+    ///
+    /// # Returns
+    ///
+    /// - `StdRwLockReadGuard<TlsCertStore>` - The read guard.
+    fn read(&self) -> StdRwLockReadGuard<'_, TlsCertStore> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Acquires a write lock on the certificate store.
+    ///
+    /// # Returns
+    ///
+    /// - `StdRwLockWriteGuard<TlsCertStore>` - The write guard.
+    fn write(&self) -> StdRwLockWriteGuard<'_, TlsCertStore> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sets the certificate used when no server name matches.
+    ///
+    /// # Arguments
+    ///
+    /// - `TlsCertificate` - The certificate.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub fn set_default(&self, certificate: TlsCertificate) -> &Self {
+        self.write().set_default(Some(certificate));
+        self
+    }
+
+    /// Adds or replaces the certificate of a server name.
+    ///
+    /// The name is matched case-insensitively. A name such as `*.example.com`
+    /// covers every direct subdomain that has no certificate of its own.
+    ///
+    /// # Arguments
+    ///
+    /// - `N` - The server name implementing `ToString`.
+    /// - `TlsCertificate` - The certificate.
+    ///
+    /// # Returns
+    ///
+    /// - `&Self` - Reference to self for method chaining.
+    pub fn insert<N: ToString>(&self, server_name: N, certificate: TlsCertificate) -> &Self {
+        self.write()
+            .get_mut_names()
+            .insert(server_name.to_string().to_ascii_lowercase(), certificate);
+        self
+    }
+
+    /// Removes the certificate of a server name.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The server name.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if a certificate was removed, false otherwise.
+    pub fn remove(&self, server_name: &str) -> bool {
+        self.write()
+            .get_mut_names()
+            .remove(&server_name.to_ascii_lowercase())
+            .is_some()
+    }
+
+    /// Stores a certificate for a server name, or as the default certificate.
+    ///
+    /// # Arguments
+    ///
+    /// - `Option<&str>` - The server name, or None for the default certificate.
+    /// - `TlsCertificate` - The certificate.
+    fn store(&self, server_name: Option<&str>, certificate: TlsCertificate) {
+        match server_name {
+            Some(server_name) => self.insert(server_name, certificate),
+            None => self.set_default(certificate),
+        };
+    }
+
+    /// Finds the certificate for a server name.
+    ///
+    /// An exact name is preferred over a wildcard name, which is preferred over
+    /// the default certificate.
+    ///
+    /// # Arguments
+    ///
+    /// - `Option<&str>` - The server name sent by the client, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionArcCertifiedKey` - The certificate to present, or None to abort the handshake.
+    fn find(&self, server_name: Option<&str>) -> OptionArcCertifiedKey {
+        let store: StdRwLockReadGuard<'_, TlsCertStore> = self.read();
+        let server_name: String = server_name.unwrap_or_default().to_ascii_lowercase();
+        let wildcard: OptionString = server_name
+            .split_once(TLS_NAME_SEPARATOR)
+            .map(|(_, parent)| format!("{}{}", TLS_WILDCARD_PREFIX, parent));
+        store
+            .get_names()
+            .get(&server_name)
+            .or_else(|| wildcard.and_then(|wildcard| store.get_names().get(&wildcard)))
+            .or(store.get_default().as_ref())
+            .map(|certificate: &TlsCertificate| certificate.0.clone())
+    }
+
+    /// Reloads a certificate whenever its PEM files change.
+    ///
+    /// The files are checked once per interval and loaded right away. A change is
+    /// only applied once both files parse and match, so a half-written pair keeps
+    /// the previous certificate in place until the next check.
+    ///
+    /// # Arguments
+    ///
+    /// - `OptionString` - The server name, or None to replace the default certificate.
+    /// - `C` - The path of the certificate chain file.
+    /// - `K` - The path of the private key file.
+    /// - `Duration` - How often the files are checked.
+    ///
+    /// # Returns
+    ///
+    /// - `AbortHandle` - The handle that stops watching.
+    pub fn watch_pem_files<C, K>(
+        &self,
+        server_name: OptionString,
+        cert_path: C,
+        key_path: K,
+        interval: Duration,
+    ) -> AbortHandle
+    where
+        C: Into<PathBuf>,
+        K: Into<PathBuf>,
+    {
+        let resolver: TlsCertResolver = self.clone();
+        let cert_path: PathBuf = cert_path.into();
+        let key_path: PathBuf = key_path.into();
+        tokio::spawn(async move {
+            let mut loaded: OptionSystemTime = None;
+            loop {
+                let modified: OptionSystemTime = latest_modified(&cert_path, &key_path).await;
+                if modified.is_some() && modified != loaded {
+                    if let Ok(certificate) =
+                        TlsCertificate::from_pem_files(&cert_path, &key_path).await
+                    {
+                        resolver.store(server_name.as_deref(), certificate);
+                        loaded = modified;
+                    }
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+        .abort_handle()
+    }
+}
+
+/// Implementation of `ResolvesServerCert` trait for `TlsCertResolver`.
+impl ResolvesServerCert for TlsCertResolver {
+    /// Picks the certificate for the server name sent through SNI.
+    ///
+    /// # Arguments
+    ///
+    /// - `ClientHello` - The hello message of the client.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionArcCertifiedKey` - The certificate to present, or None to abort the handshake.
+    fn resolve(&self, client_hello: ClientHello<'_>) -> OptionArcCertifiedKey {
+        self.find(client_hello.server_name())
+    }
+}
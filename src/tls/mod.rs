@@ -0,0 +1,17 @@
+pub(crate) mod r#const;
+pub(crate) mod r#enum;
+#[cfg(feature = "tls")]
+pub(crate) mod r#fn;
+#[cfg(feature = "tls")]
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#type;
+
+pub use r#enum::*;
+pub use r#struct::*;
+pub use r#type::*;
+
+#[cfg(feature = "tls")]
+pub(crate) use r#const::*;
+#[cfg(feature = "tls")]
+pub(crate) use r#fn::*;
@@ -0,0 +1,62 @@
+use crate::*;
+
+/// The parameters negotiated during the TLS handshake of a connection.
+#[derive(Clone, Default, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// The negotiated protocol version, such as `TLSv1_3`.
+    #[get(pub)]
+    pub(super) version: String,
+    /// The negotiated cipher suite, such as `TLS13_AES_128_GCM_SHA256`.
+    #[get(pub)]
+    pub(super) cipher: String,
+    /// The server name the client asked for through SNI, if any.
+    #[get(pub)]
+    pub(super) sni: OptionString,
+    /// The application protocol selected through ALPN, if any.
+    #[get(pub)]
+    pub(super) alpn: OptionString,
+    /// The DER encoded certificate chain the client presented, leaf certificate first.
+    ///
+    /// The chain has been verified against the client CA bundle of the server.
+    #[get(pub)]
+    pub(super) peer_certificates: VecVecU8,
+    /// The subject of the client certificate, such as `CN=service,O=example`.
+    #[get(pub)]
+    pub(super) peer_subject: OptionString,
+    /// The subject alternative names of the client certificate.
+    #[get(pub)]
+    pub(super) peer_sans: VecTlsSubjectAltName,
+}
+
+/// A certificate chain together with its private key.
+#[cfg(feature = "tls")]
+#[derive(Clone, CustomDebug, DisplayDebug)]
+pub struct TlsCertificate(pub(super) ArcCertifiedKey);
+
+/// Selects the certificate of each TLS handshake from the server name sent through SNI.
+///
+/// Certificates can be added, replaced and removed while the server is running.
+/// Every change is atomic and only affects handshakes that start afterwards.
+#[cfg(feature = "tls")]
+#[derive(Clone, Default, CustomDebug, DisplayDebug)]
+pub struct TlsCertResolver(pub(super) ArcStdRwLockTlsCertStore);
+
+/// The certificates known to a `TlsCertResolver`.
+#[cfg(feature = "tls")]
+#[derive(Clone, Default, CustomDebug, Data, DisplayDebug)]
+pub(crate) struct TlsCertStore {
+    /// The certificate used when no server name matches.
+    pub(super) default: OptionTlsCertificate,
+    /// The certificates by lowercase server name, which may start with a `*.` wildcard label.
+    pub(super) names: HashMapStringTlsCertificate,
+}
+
+/// The client certificate verification of a TLS server.
+#[cfg(feature = "tls")]
+#[derive(Clone, CustomDebug, DisplayDebug)]
+pub struct TlsClientAuth {
+    /// The verifier that checks client certificates against the CA bundle.
+    pub(super) verifier: ArcClientCertVerifier,
+    /// Whether connections without a client certificate are rejected.
+    pub(super) required: bool,
+}
@@ -0,0 +1,76 @@
+use crate::*;
+
+/// A type alias for the optional TLS parameters of a connection.
+pub type OptionTlsInfo = Option<TlsInfo>;
+
+/// A type alias for a list of DER encoded certificates.
+pub type VecVecU8 = Vec<Vec<u8>>;
+
+/// A type alias for a list of subject alternative names.
+pub type VecTlsSubjectAltName = Vec<TlsSubjectAltName>;
+
+/// A type alias for a certificate chain, leaf certificate first.
+#[cfg(feature = "tls")]
+pub type VecCertificateDer = Vec<CertificateDer<'static>>;
+
+/// A type alias for the optional TLS acceptor of a server.
+#[cfg(feature = "tls")]
+pub(crate) type OptionTlsAcceptor = Option<TlsAcceptor>;
+
+/// A type alias for a `Result` that returns nothing or a `TlsError`.
+#[cfg(feature = "tls")]
+pub type ResultTlsError = Result<(), TlsError>;
+
+/// A type alias for a `Result` that returns a `TlsCertificate` or a `TlsError`.
+#[cfg(feature = "tls")]
+pub type ResultTlsCertificateTlsError = Result<TlsCertificate, TlsError>;
+
+/// A type alias for an optional `TlsCertResolver`.
+#[cfg(feature = "tls")]
+pub type OptionTlsCertResolver = Option<TlsCertResolver>;
+
+/// A type alias for an optional `TlsCertificate`.
+#[cfg(feature = "tls")]
+pub(crate) type OptionTlsCertificate = Option<TlsCertificate>;
+
+/// A type alias for certificates by server name.
+#[cfg(feature = "tls")]
+pub(crate) type HashMapStringTlsCertificate = HashMap<String, TlsCertificate>;
+
+/// A type alias for a shared certificate chain with its signing key.
+#[cfg(feature = "tls")]
+pub(crate) type ArcCertifiedKey = Arc<CertifiedKey>;
+
+/// A type alias for an optional shared certificate chain with its signing key.
+#[cfg(feature = "tls")]
+pub(crate) type OptionArcCertifiedKey = Option<ArcCertifiedKey>;
+
+/// A type alias for the synchronously locked certificate store of a resolver.
+///
+/// The lock is synchronous because rustls resolves certificates outside of an async context.
+#[cfg(feature = "tls")]
+pub(crate) type ArcStdRwLockTlsCertStore = Arc<StdRwLock<TlsCertStore>>;
+
+/// A type alias for an optional file modification time.
+#[cfg(feature = "tls")]
+pub(crate) type OptionSystemTime = Option<SystemTime>;
+
+/// A type alias for a shared cryptographic provider.
+#[cfg(feature = "tls")]
+pub(crate) type ArcCryptoProvider = Arc<CryptoProvider>;
+
+/// A type alias for a shared client certificate verifier.
+#[cfg(feature = "tls")]
+pub(crate) type ArcClientCertVerifier = Arc<dyn ClientCertVerifier>;
+
+/// A type alias for an optional `TlsClientAuth`.
+#[cfg(feature = "tls")]
+pub(crate) type OptionTlsClientAuth = Option<TlsClientAuth>;
+
+/// A type alias for a `Result` that returns a `TlsClientAuth` or a `TlsError`.
+#[cfg(feature = "tls")]
+pub type ResultTlsClientAuthTlsError = Result<TlsClientAuth, TlsError>;
+
+/// A type alias for an optional IP address.
+#[cfg(feature = "tls")]
+pub(crate) type OptionIpAddr = Option<IpAddr>;
@@ -0,0 +1,5 @@
+/// The prefix of the textual address of a Unix domain socket connection.
+pub(crate) const UNIX_SOCKET_SCHEME: &str = "unix:";
+
+/// Whether a socket file left behind by a stopped server is removed before binding by default.
+pub(crate) const DEFAULT_UNIX_SOCKET_REMOVE_STALE: bool = true;
@@ -0,0 +1,54 @@
+use crate::*;
+
+/// Removes a socket file that no server is listening on anymore.
+///
+/// Only socket files that refuse connections are removed, so a running server
+/// keeps its socket and other files are never touched.
+///
+/// # Arguments
+///
+/// - `&Path` - The path of the socket file.
+///
+/// # Returns
+///
+/// - `io::Result<()>` - Ok if the path is free to bind or still in use, or the removal error.
+pub(crate) async fn remove_stale_unix_socket(path: &Path) -> io::Result<()> {
+    let Ok(metadata) = tokio::fs::symlink_metadata(path).await else {
+        return Ok(());
+    };
+    if !metadata.file_type().is_socket() {
+        return Ok(());
+    }
+    match UnixStream::connect(path).await {
+        Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => {
+            tokio::fs::remove_file(path).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Binds a listener to a Unix domain socket.
+///
+/// # Arguments
+///
+/// - `&Path` - The path of the socket file.
+/// - `OptionU32` - The file mode to apply to the socket file, such as `0o660`.
+/// - `bool` - Whether to remove a stale socket file before binding.
+///
+/// # Returns
+///
+/// - `io::Result<UnixListener>` - The listener, or the error that prevented binding.
+pub(crate) async fn bind_unix_listener(
+    path: &Path,
+    mode: OptionU32,
+    remove_stale: bool,
+) -> io::Result<UnixListener> {
+    if remove_stale {
+        remove_stale_unix_socket(path).await?;
+    }
+    let listener: UnixListener = UnixListener::bind(path)?;
+    if let Some(mode) = mode {
+        tokio::fs::set_permissions(path, Permissions::from_mode(mode)).await?;
+    }
+    Ok(listener)
+}
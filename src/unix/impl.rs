@@ -0,0 +1,77 @@
+use crate::*;
+
+/// Implementation of methods for `UnixPeer` structure.
+impl UnixPeer {
+    /// Reads the peer credentials of an accepted connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `&UnixStream` - The accepted connection.
+    /// - `&Path` - The path of the listening socket.
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<UnixPeer>` - The peer, or the error that prevented reading its credentials.
+    pub(crate) fn from_stream(stream: &UnixStream, socket_path: &Path) -> io::Result<Self> {
+        let credentials: UCred = stream.peer_cred()?;
+        Ok(Self {
+            socket_path: socket_path.to_path_buf(),
+            uid: credentials.uid(),
+            gid: credentials.gid(),
+            pid: credentials.pid(),
+        })
+    }
+
+    /// Formats the address of the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The socket path prefixed with `unix:`, such as `unix:/run/app.sock`.
+    pub fn to_address_string(&self) -> String {
+        format!("{}{}", UNIX_SOCKET_SCHEME, self.socket_path.display())
+    }
+}
+
+/// Implementation of methods for `UnixSocketFile` structure.
+impl UnixSocketFile {
+    /// Records the socket file a listener is bound to.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path` - The path of the socket file.
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<UnixSocketFile>` - The socket file, or the error that prevented reading it.
+    pub(crate) async fn new(path: &Path) -> io::Result<Self> {
+        let metadata: Metadata = tokio::fs::symlink_metadata(path).await?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            id: (metadata.dev(), metadata.ino()),
+            remove: true,
+        })
+    }
+
+    /// Keeps the socket file when this value is dropped, as another process serves it now.
+    pub(crate) fn keep(&mut self) {
+        self.remove = false;
+    }
+}
+
+/// Implementation of `Drop` trait for `UnixSocketFile`.
+impl Drop for UnixSocketFile {
+    /// Removes the socket file if it is still the one the server listened on.
+    ///
+    /// The filesystem is accessed synchronously, as a value cannot be dropped asynchronously.
+    fn drop(&mut self) {
+        if !self.remove {
+            return;
+        }
+        let unchanged: bool = std::fs::symlink_metadata(&self.path)
+            .map(|metadata: Metadata| (metadata.dev(), metadata.ino()) == self.id)
+            .unwrap_or_default();
+        if unchanged {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
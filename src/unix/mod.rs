@@ -0,0 +1,11 @@
+pub(crate) mod r#const;
+pub(crate) mod r#fn;
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#type;
+
+pub use r#struct::*;
+pub use r#type::*;
+
+pub(crate) use r#const::*;
+pub(crate) use r#fn::*;
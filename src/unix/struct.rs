@@ -0,0 +1,35 @@
+use crate::*;
+
+/// The peer of a connection accepted on a Unix domain socket.
+///
+/// The credentials are read with `SO_PEERCRED` on Linux and the platform
+/// equivalent elsewhere when the connection is accepted.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct UnixPeer {
+    /// The path of the socket the connection arrived on.
+    #[get(pub)]
+    pub(super) socket_path: PathBuf,
+    /// The user id of the peer process.
+    #[get(pub)]
+    pub(super) uid: u32,
+    /// The group id of the peer process.
+    #[get(pub)]
+    pub(super) gid: u32,
+    /// The process id of the peer, if the platform reports it.
+    #[get(pub)]
+    pub(super) pid: OptionI32,
+}
+
+/// The socket file the server listens on, removed when the server stops.
+///
+/// The file is only removed if it is still the one the server listened on, so a
+/// socket another process bound to the same path in the meantime is kept.
+#[derive(CustomDebug)]
+pub(crate) struct UnixSocketFile {
+    /// The path of the socket file.
+    pub(super) path: PathBuf,
+    /// The device and inode numbers of the socket file.
+    pub(super) id: (u64, u64),
+    /// Whether the socket file is removed when this value is dropped.
+    pub(super) remove: bool,
+}
@@ -0,0 +1,10 @@
+use crate::*;
+
+/// A type alias for the optional Unix domain socket peer of a connection.
+pub type OptionUnixPeer = Option<UnixPeer>;
+
+/// A type alias for an optional filesystem path.
+pub type OptionPathBuf = Option<PathBuf>;
+
+/// A type alias for the optional socket file the server listens on.
+pub(crate) type OptionUnixSocketFile = Option<UnixSocketFile>;
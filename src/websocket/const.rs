@@ -0,0 +1,103 @@
+use crate::*;
+
+/// The bit of the first frame byte marking the final fragment of a message.
+pub(crate) const WS_FIN_BIT: u8 = 0b1000_0000;
+
+/// The bits of the first frame byte reserved for extensions.
+pub(crate) const WS_RSV_BITS: u8 = 0b0111_0000;
+
+/// The reserved bit marking the first frame of a compressed message.
+pub(crate) const WS_RSV1_BIT: u8 = 0b0100_0000;
+
+/// The bits of the first frame byte holding the opcode.
+pub(crate) const WS_OPCODE_BITS: u8 = 0b0000_1111;
+
+/// The bit of the second frame byte marking a masked payload.
+pub(crate) const WS_MASK_BIT: u8 = 0b1000_0000;
+
+/// The bits of the second frame byte holding the payload length.
+pub(crate) const WS_PAYLOAD_LEN_BITS: u8 = 0b0111_1111;
+
+/// The payload length marker announcing a 16-bit extended length.
+pub(crate) const WS_PAYLOAD_LEN_16: u8 = 126;
+
+/// The payload length marker announcing a 64-bit extended length.
+pub(crate) const WS_PAYLOAD_LEN_64: u8 = 127;
+
+/// The length of the masking key of a client frame.
+pub(crate) const WS_MASK_KEY_LEN: usize = 4;
+
+/// The length of the status code at the start of a close frame payload.
+pub(crate) const WS_CLOSE_CODE_LEN: usize = 2;
+
+/// The name of the WebSocket compression extension defined in RFC 7692.
+pub(crate) const PERMESSAGE_DEFLATE: &str = "permessage-deflate";
+
+/// The extension parameter asking the server to reset its compressor after every message.
+pub(crate) const SERVER_NO_CONTEXT_TAKEOVER: &str = "server_no_context_takeover";
+
+/// The extension parameter asking the client to reset its compressor after every message.
+pub(crate) const CLIENT_NO_CONTEXT_TAKEOVER: &str = "client_no_context_takeover";
+
+/// The extension parameter limiting the window of the server compressor.
+pub(crate) const SERVER_MAX_WINDOW_BITS: &str = "server_max_window_bits";
+
+/// The extension parameter limiting the window of the client compressor.
+pub(crate) const CLIENT_MAX_WINDOW_BITS: &str = "client_max_window_bits";
+
+/// The smallest window size supported for compression.
+pub(crate) const MIN_WINDOW_BITS: u8 = 9;
+
+/// The largest window size defined for compression.
+pub(crate) const MAX_WINDOW_BITS: u8 = 15;
+
+/// The bytes a sync flush appends to a deflate stream, removed from compressed messages.
+pub(crate) const DEFLATE_TRAILER: [u8; 4] = [0x00, 0x00, 0xFF, 0xFF];
+
+/// The default number of broadcast messages queued per connection.
+pub(crate) const DEFAULT_WS_QUEUE_CAPACITY: usize = 64;
+
+/// The origin pattern allowing every origin.
+pub(crate) const ANY_ORIGIN: &str = "*";
+
+/// The largest payload of a control frame.
+pub(crate) const WS_MAX_CONTROL_PAYLOAD_LEN: usize = 125;
+
+/// The default size limit of a reassembled WebSocket message.
+pub(crate) const DEFAULT_WS_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// The largest payload length a frame may announce, as the most significant bit must be zero.
+pub(crate) const WS_MAX_FRAME_PAYLOAD_LEN: u64 = i64::MAX as u64;
+
+/// The default time to wait for the peer's close frame after sending one.
+pub(crate) const DEFAULT_WS_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Close code for a normal closure.
+pub const WS_CLOSE_NORMAL: u16 = 1000;
+
+/// Close code for an endpoint going away, such as a server shutting down.
+pub const WS_CLOSE_GOING_AWAY: u16 = 1001;
+
+/// Close code for a protocol error.
+pub const WS_CLOSE_PROTOCOL_ERROR: u16 = 1002;
+
+/// Close code for a data type the endpoint cannot accept.
+pub const WS_CLOSE_UNSUPPORTED_DATA: u16 = 1003;
+
+/// Close code reported when a close frame carried no status code.
+pub const WS_CLOSE_NO_STATUS: u16 = 1005;
+
+/// Close code reported when the connection ended without a close frame.
+pub const WS_CLOSE_ABNORMAL: u16 = 1006;
+
+/// Close code for message data that does not match the message type.
+pub const WS_CLOSE_INVALID_PAYLOAD: u16 = 1007;
+
+/// Close code for a message that violates the endpoint's policy.
+pub const WS_CLOSE_POLICY_VIOLATION: u16 = 1008;
+
+/// Close code for a message too big to process.
+pub const WS_CLOSE_MESSAGE_TOO_BIG: u16 = 1009;
+
+/// Close code for an unexpected condition on the server.
+pub const WS_CLOSE_INTERNAL_ERROR: u16 = 1011;
@@ -0,0 +1,222 @@
+use crate::*;
+
+/// Decodes a single WebSocket frame from the start of a buffer.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The received bytes.
+///
+/// # Returns
+///
+/// - `OptionWebSocketFrameDataUsize` - The frame and the number of bytes it occupied,
+///   or None if the buffer does not hold a complete frame yet.
+pub(crate) fn decode_ws_frame(data: &[u8]) -> OptionWebSocketFrameDataUsize {
+    if data.len() < 2 {
+        return None;
+    }
+    let fin: bool = data[0] & WS_FIN_BIT != 0;
+    let rsv: u8 = data[0] & WS_RSV_BITS;
+    let opcode: WebSocketOpcode = WebSocketOpcode::from_u8(data[0] & WS_OPCODE_BITS);
+    let masked: bool = data[1] & WS_MASK_BIT != 0;
+    let mut index: usize = 2;
+    let payload_len: usize = match data[1] & WS_PAYLOAD_LEN_BITS {
+        WS_PAYLOAD_LEN_16 => {
+            let bytes: [u8; 2] = data.get(index..index + 2)?.try_into().ok()?;
+            index += 2;
+            u16::from_be_bytes(bytes) as usize
+        }
+        WS_PAYLOAD_LEN_64 => {
+            let bytes: [u8; 8] = data.get(index..index + 8)?.try_into().ok()?;
+            index += 8;
+            usize::try_from(u64::from_be_bytes(bytes)).unwrap_or(usize::MAX)
+        }
+        len => len as usize,
+    };
+    let mask_key: Option<[u8; WS_MASK_KEY_LEN]> = if masked {
+        let key: [u8; WS_MASK_KEY_LEN] =
+            data.get(index..index + WS_MASK_KEY_LEN)?.try_into().ok()?;
+        index += WS_MASK_KEY_LEN;
+        Some(key)
+    } else {
+        None
+    };
+    let end: usize = index.checked_add(payload_len)?;
+    let mut payload: Vec<u8> = data.get(index..end)?.to_vec();
+    if let Some(mask_key) = mask_key {
+        for (position, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask_key[position % WS_MASK_KEY_LEN];
+        }
+    }
+    let frame: WebSocketFrameData = WebSocketFrameData {
+        fin,
+        rsv,
+        opcode,
+        masked,
+        payload,
+    };
+    Some((frame, end))
+}
+
+/// Reads the payload length announced by a frame header.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The received bytes, starting at a frame boundary.
+///
+/// # Returns
+///
+/// - `Option<u64>` - The announced payload length, or None if the header is incomplete.
+pub(crate) fn decode_ws_payload_len(data: &[u8]) -> Option<u64> {
+    match data.get(1)? & WS_PAYLOAD_LEN_BITS {
+        WS_PAYLOAD_LEN_16 => Some(u16::from_be_bytes(data.get(2..4)?.try_into().ok()?) as u64),
+        WS_PAYLOAD_LEN_64 => Some(u64::from_be_bytes(data.get(2..10)?.try_into().ok()?)),
+        len => Some(len as u64),
+    }
+}
+
+/// Checks whether a status code may be sent in a close frame.
+///
+/// Codes reserved for local use by RFC 6455, such as 1005 and 1006, and unassigned
+/// codes below 3000 are rejected.
+///
+/// # Arguments
+///
+/// - `u16` - The close status code.
+///
+/// # Returns
+///
+/// - `bool` - true if the code is valid on the wire, false otherwise.
+pub(crate) fn is_valid_ws_close_code(code: u16) -> bool {
+    matches!(code, 1000..=1003 | 1007..=1014 | 3000..=4999)
+}
+
+/// Encodes a single unmasked WebSocket frame as sent by a server.
+///
+/// # Arguments
+///
+/// - `bool` - Whether the frame is the final fragment of a message.
+/// - `u8` - The reserved extension bits of the frame.
+/// - `WebSocketOpcode` - The opcode of the frame.
+/// - `&[u8]` - The payload of the frame.
+///
+/// # Returns
+///
+/// - `Vec<u8>` - The encoded frame.
+pub(crate) fn encode_ws_frame(
+    fin: bool,
+    rsv: u8,
+    opcode: WebSocketOpcode,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut frame: Vec<u8> = Vec::with_capacity(payload.len() + 10);
+    let fin_bit: u8 = if fin { WS_FIN_BIT } else { 0 };
+    frame.push(fin_bit | (rsv & WS_RSV_BITS) | (opcode.to_u8() & WS_OPCODE_BITS));
+    if payload.len() < WS_PAYLOAD_LEN_16 as usize {
+        frame.push(payload.len() as u8);
+    } else if payload.len() <= u16::MAX as usize {
+        frame.push(WS_PAYLOAD_LEN_16);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    } else {
+        frame.push(WS_PAYLOAD_LEN_64);
+        frame.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Negotiates the `permessage-deflate` extension from the offers of a client.
+///
+/// The first offer the server can satisfy is accepted, as described in RFC 7692.
+///
+/// # Arguments
+///
+/// - `&str` - The value of the `Sec-WebSocket-Extensions` request header.
+/// - `&WebSocketDeflate` - The settings of the server.
+///
+/// # Returns
+///
+/// - `OptionWebSocketDeflateAgreement` - The agreed parameters, or None if no offer is acceptable.
+pub(crate) fn negotiate_ws_deflate(
+    offers: &str,
+    config: &WebSocketDeflate,
+) -> OptionWebSocketDeflateAgreement {
+    offers
+        .split(COMMA)
+        .find_map(|offer: &str| WebSocketDeflateAgreement::from_offer(offer, config))
+}
+
+/// Finds the setting of the first route pattern matching a path.
+///
+/// # Arguments
+///
+/// - `&[(RoutePattern, T)]` - The per-route settings.
+/// - `&str` - The request path.
+/// - `bool` - Whether static segments are compared case-sensitively.
+/// - `bool` - Whether empty path segments are significant.
+///
+/// # Returns
+///
+/// - `Option<&T>` - The matching setting, if any.
+pub(crate) fn find_route_setting<'a, T>(
+    settings: &'a [(RoutePattern, T)],
+    path: &str,
+    case_sensitive: bool,
+    empty_segments: bool,
+) -> Option<&'a T> {
+    settings
+        .iter()
+        .find(|(pattern, _)| {
+            pattern
+                .match_path(path, case_sensitive, empty_segments)
+                .is_some()
+        })
+        .map(|(_, setting)| setting)
+}
+
+/// Stores the setting of a route pattern, replacing an earlier one for the same pattern.
+///
+/// # Arguments
+///
+/// - `&mut Vec<(RoutePattern, T)>` - The per-route settings.
+/// - `RoutePattern` - The route pattern.
+/// - `T` - The setting.
+pub(crate) fn upsert_route_setting<T>(
+    settings: &mut Vec<(RoutePattern, T)>,
+    route_pattern: RoutePattern,
+    setting: T,
+) {
+    match settings
+        .iter_mut()
+        .find(|(tmp_pattern, _)| tmp_pattern == &route_pattern)
+    {
+        Some((_, tmp_setting)) => *tmp_setting = setting,
+        None => settings.push((route_pattern, setting)),
+    }
+}
+
+/// Chooses the opcode of a data message from its payload.
+///
+/// # Arguments
+///
+/// - `&[u8]` - The payload of the message.
+///
+/// # Returns
+///
+/// - `WebSocketOpcode` - `Text` if the payload is valid UTF-8, `Binary` otherwise.
+pub(crate) fn ws_message_opcode(payload: &[u8]) -> WebSocketOpcode {
+    if std::str::from_utf8(payload).is_ok() {
+        WebSocketOpcode::Text
+    } else {
+        WebSocketOpcode::Binary
+    }
+}
+
+/// Allocates the id of a new broadcast member.
+///
+/// # Returns
+///
+/// - `u64` - An id unique within the process.
+pub(crate) fn next_ws_member_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
@@ -0,0 +1,1171 @@
+use crate::*;
+
+impl Default for WebSocketReader {
+    /// Creates a reader with an empty buffer and the default message size limit.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketReader` - The reader.
+    fn default() -> Self {
+        Self {
+            buffer: Vec::new(),
+            message_opcode: None,
+            message_compressed: false,
+            message: Vec::new(),
+            inflater: None,
+            max_message_size: DEFAULT_WS_MAX_MESSAGE_SIZE,
+            violation: None,
+        }
+    }
+}
+
+impl WebSocketReader {
+    /// Records a protocol violation that fails the connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `u16` - The close code sent to the peer.
+    /// - `R` - The close reason implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `RequestError` - The error returned to the caller of the read.
+    fn violation<R: ToString>(&mut self, code: u16, reason: R) -> RequestError {
+        let close: WebSocketClose = WebSocketClose::new(code, reason);
+        let error: RequestError = RequestError::InvalidWebSocketFrame(close.get_reason().clone());
+        self.violation = Some(close);
+        error
+    }
+
+    /// Takes the close frame recorded by the last protocol violation.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionWebSocketClose` - The close frame failing the connection, if any.
+    pub(crate) fn take_violation(&mut self) -> OptionWebSocketClose {
+        self.violation.take()
+    }
+
+    /// Checks the header of the frame at the front of the buffer against the framing
+    /// rules of RFC 6455, before its payload is buffered.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), RequestError>` - An error if the header violates the protocol or
+    ///   announces a payload that is too large.
+    fn check_frame_header(&mut self) -> Result<(), RequestError> {
+        let (first, second): (u8, u8) = match self.get_buffer().get(..2) {
+            Some(header) => (header[0], header[1]),
+            None => return Ok(()),
+        };
+        let opcode: WebSocketOpcode = WebSocketOpcode::from_u8(first & WS_OPCODE_BITS);
+        let rsv: u8 = first & WS_RSV_BITS;
+        if second & WS_MASK_BIT == 0 {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "unmasked client frame"));
+        }
+        if opcode.is_reserved() {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "reserved opcode"));
+        }
+        if rsv & !WS_RSV1_BIT != 0 {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "reserved bits set"));
+        }
+        if opcode.is_control() && (rsv != 0 || first & WS_FIN_BIT == 0) {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "invalid control frame"));
+        }
+        let Some(len) = decode_ws_payload_len(self.get_buffer()) else {
+            return Ok(());
+        };
+        if len > WS_MAX_FRAME_PAYLOAD_LEN {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "invalid payload length"));
+        }
+        if opcode.is_control() && len > WS_MAX_CONTROL_PAYLOAD_LEN as u64 {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "control frame too large"));
+        }
+        let limit: u64 = self.max_message_size.saturating_sub(self.message.len()) as u64;
+        if opcode.is_data() && len > limit {
+            return Err(self.violation(WS_CLOSE_MESSAGE_TOO_BIG, "message too big"));
+        }
+        Ok(())
+    }
+
+    /// Checks the payload of a decoded close frame.
+    ///
+    /// The header of every frame is checked by `check_frame_header` before decoding.
+    ///
+    /// # Arguments
+    ///
+    /// - `&WebSocketFrameData` - The frame received from the client.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), RequestError>` - An error if the close payload is invalid.
+    fn validate_frame(&mut self, frame: &WebSocketFrameData) -> Result<(), RequestError> {
+        if !frame.get_opcode().is_close() || frame.get_payload().is_empty() {
+            return Ok(());
+        }
+        let payload: &[u8] = frame.get_payload();
+        if payload.len() < WS_CLOSE_CODE_LEN {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "invalid close payload"));
+        }
+        if !is_valid_ws_close_code(u16::from_be_bytes([payload[0], payload[1]])) {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "invalid close code"));
+        }
+        if std::str::from_utf8(&payload[WS_CLOSE_CODE_LEN..]).is_err() {
+            return Err(self.violation(WS_CLOSE_INVALID_PAYLOAD, "invalid close reason"));
+        }
+        Ok(())
+    }
+
+    /// Reads the next frame from the stream.
+    ///
+    /// Bytes following the frame are kept for the next call, so frames arriving
+    /// in the same packet are never lost. Frames violating RFC 6455 or the message
+    /// size limit are rejected and recorded with `take_violation`.
+    ///
+    /// # Arguments
+    ///
+    /// - `&ConnectionStream` - The stream to read from.
+    /// - `usize` - The read buffer size.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultWebSocketFrameDataRequestError` - The decoded frame or a read error.
+    pub(crate) async fn read_frame(
+        &mut self,
+        stream: &ConnectionStream,
+        buffer_size: usize,
+    ) -> ResultWebSocketFrameDataRequestError {
+        loop {
+            self.check_frame_header()?;
+            if let Some((frame, consumed)) = decode_ws_frame(self.get_buffer()) {
+                self.get_mut_buffer().drain(..consumed);
+                self.validate_frame(&frame)?;
+                return Ok(frame);
+            }
+            match stream.read_into(self.get_mut_buffer(), buffer_size).await {
+                Ok(0) => return Err(RequestError::ClientDisconnected),
+                Ok(_) => {}
+                Err(err)
+                    if err.kind() == io::ErrorKind::ConnectionReset
+                        || err.kind() == io::ErrorKind::ConnectionAborted =>
+                {
+                    return Err(RequestError::ClientDisconnected);
+                }
+                Err(err) => return Err(RequestError::InvalidWebSocketRequest(err.to_string())),
+            }
+        }
+    }
+
+    /// Feeds a data frame into the message reassembly.
+    ///
+    /// # Arguments
+    ///
+    /// - `WebSocketFrameData` - A text, binary or continuation frame.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultOptionRequestBodyRequestError` - The complete message once its final frame
+    ///   arrived, None while fragments are pending, or an error for an unexpected frame.
+    pub(crate) fn assemble(
+        &mut self,
+        frame: WebSocketFrameData,
+    ) -> ResultOptionRequestBodyRequestError {
+        let opcode: WebSocketOpcode = *frame.get_opcode();
+        let fin: bool = *frame.get_fin();
+        let compressed: bool = frame.get_rsv() & WS_RSV1_BIT != 0;
+        if compressed && (opcode.is_continuation() || self.get_inflater().is_none()) {
+            return Err(self.violation(WS_CLOSE_PROTOCOL_ERROR, "unexpected compressed frame"));
+        }
+        match (
+            opcode.is_continuation(),
+            self.get_message_opcode().is_some(),
+        ) {
+            (true, false) => {
+                return Err(self.violation(
+                    WS_CLOSE_PROTOCOL_ERROR,
+                    "continuation frame without a message",
+                ));
+            }
+            (false, true) => {
+                return Err(self.violation(
+                    WS_CLOSE_PROTOCOL_ERROR,
+                    "data frame inside a fragmented message",
+                ));
+            }
+            (false, false) if fin => {
+                return self
+                    .finish_message(opcode, compressed, frame.payload)
+                    .map(Some);
+            }
+            (false, false) => {
+                *self.get_mut_message_opcode() = Some(opcode);
+                *self.get_mut_message_compressed() = compressed;
+            }
+            (true, true) => {}
+        }
+        self.get_mut_message()
+            .extend_from_slice(frame.get_payload());
+        if self.message.len() > self.max_message_size {
+            return Err(self.violation(WS_CLOSE_MESSAGE_TOO_BIG, "message too big"));
+        }
+        if !fin {
+            return Ok(None);
+        }
+        let opcode: WebSocketOpcode = self.message_opcode.take().unwrap_or(opcode);
+        let message: RequestBody = std::mem::take(self.get_mut_message());
+        let compressed: bool = std::mem::take(self.get_mut_message_compressed());
+        self.finish_message(opcode, compressed, message).map(Some)
+    }
+
+    /// Decompresses and validates a complete message.
+    ///
+    /// # Arguments
+    ///
+    /// - `WebSocketOpcode` - The opcode of the first frame of the message.
+    /// - `bool` - Whether the message is compressed.
+    /// - `RequestBody` - The payload of the message.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultRequestBodyRequestError` - The message, or an error if it is too big or a
+    ///   text message is not valid UTF-8.
+    fn finish_message(
+        &mut self,
+        opcode: WebSocketOpcode,
+        compressed: bool,
+        message: RequestBody,
+    ) -> ResultRequestBodyRequestError {
+        let message: RequestBody = if compressed {
+            self.inflate(message)?
+        } else {
+            message
+        };
+        if message.len() > self.max_message_size {
+            return Err(self.violation(WS_CLOSE_MESSAGE_TOO_BIG, "message too big"));
+        }
+        if opcode.is_text() && std::str::from_utf8(&message).is_err() {
+            return Err(self.violation(WS_CLOSE_INVALID_PAYLOAD, "invalid UTF-8 text"));
+        }
+        Ok(message)
+    }
+
+    /// Decompresses a complete message with the negotiated inflater.
+    ///
+    /// # Arguments
+    ///
+    /// - `RequestBody` - The compressed message payload.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultRequestBodyRequestError` - The decompressed payload or an error.
+    fn inflate(&mut self, message: RequestBody) -> ResultRequestBodyRequestError {
+        let max_len: usize = self.max_message_size;
+        let result: ResultVecU8String = match self.get_mut_inflater() {
+            Some(inflater) => inflater.inflate(message, max_len),
+            None => Err("unexpected compressed frame".to_owned()),
+        };
+        result.map_err(|err: String| self.violation(WS_CLOSE_PROTOCOL_ERROR, err))
+    }
+}
+
+impl WebSocketClose {
+    /// Creates a new close frame description.
+    ///
+    /// # Arguments
+    ///
+    /// - `u16` - The close status code.
+    /// - `R` - The close reason implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketClose` - The close frame description.
+    pub fn new<R: ToString>(code: u16, reason: R) -> Self {
+        Self {
+            code,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Parses the payload of a received close frame.
+    ///
+    /// An empty payload is reported with `WS_CLOSE_NO_STATUS`.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The close frame payload.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketClose` - The parsed close frame.
+    pub(crate) fn from_payload(payload: &[u8]) -> Self {
+        match payload.get(..WS_CLOSE_CODE_LEN) {
+            Some(code) => Self::new(
+                u16::from_be_bytes([code[0], code[1]]),
+                String::from_utf8_lossy(&payload[WS_CLOSE_CODE_LEN..]),
+            ),
+            None => Self::new(WS_CLOSE_NO_STATUS, EMPTY_STR),
+        }
+    }
+
+    /// Encodes the close frame payload.
+    ///
+    /// The reason is truncated on a character boundary so the payload fits in a control frame.
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<u8>` - The close frame payload.
+    pub(crate) fn to_payload(&self) -> Vec<u8> {
+        let mut reason_len: usize = self
+            .get_reason()
+            .len()
+            .min(WS_MAX_CONTROL_PAYLOAD_LEN - WS_CLOSE_CODE_LEN);
+        while !self.get_reason().is_char_boundary(reason_len) {
+            reason_len -= 1;
+        }
+        let mut payload: Vec<u8> = self.get_code().to_be_bytes().to_vec();
+        payload.extend_from_slice(&self.get_reason().as_bytes()[..reason_len]);
+        payload
+    }
+}
+
+impl WebSocketHeartbeat {
+    /// Creates heartbeat settings with every check disabled.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketHeartbeat` - The heartbeat settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long the connection may stay quiet before the server sends a ping.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The ping interval.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketHeartbeat` - The modified settings.
+    pub fn with_ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = Some(interval);
+        self
+    }
+
+    /// Sets how long the server waits for the pong answering its ping.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The pong deadline.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketHeartbeat` - The modified settings.
+    pub fn with_pong_timeout(mut self, timeout: Duration) -> Self {
+        self.pong_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long the connection may go without receiving any frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `Duration` - The maximum idle time.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketHeartbeat` - The modified settings.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Checks whether any heartbeat check is enabled.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if at least one setting is present.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.ping_interval.is_some() || self.pong_timeout.is_some() || self.idle_timeout.is_some()
+    }
+
+    /// Computes when the pong answering the last ping must have arrived.
+    ///
+    /// # Arguments
+    ///
+    /// - `OptionInstant` - When the unanswered ping was sent, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionInstant` - The pong deadline, or None if no pong is awaited.
+    pub(crate) fn pong_deadline(&self, ping_sent: OptionInstant) -> OptionInstant {
+        Some(ping_sent? + self.pong_timeout?)
+    }
+
+    /// Computes when the next ping should be sent.
+    ///
+    /// # Arguments
+    ///
+    /// - `Instant` - When the last frame was received.
+    /// - `OptionInstant` - When the unanswered ping was sent, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionInstant` - The time of the next ping, or None while a pong is awaited
+    ///   or pings are disabled.
+    pub(crate) fn ping_due(
+        &self,
+        last_received: Instant,
+        ping_sent: OptionInstant,
+    ) -> OptionInstant {
+        if ping_sent.is_some() && self.pong_timeout.is_some() {
+            return None;
+        }
+        let last_activity: Instant =
+            ping_sent.map_or(last_received, |sent: Instant| sent.max(last_received));
+        Some(last_activity + self.ping_interval?)
+    }
+
+    /// Computes the next moment a heartbeat check is due.
+    ///
+    /// # Arguments
+    ///
+    /// - `Instant` - When the last frame was received.
+    /// - `OptionInstant` - When the unanswered ping was sent, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionInstant` - The next deadline, or None if no check is enabled.
+    pub(crate) fn next_deadline(
+        &self,
+        last_received: Instant,
+        ping_sent: OptionInstant,
+    ) -> OptionInstant {
+        let idle: OptionInstant = self
+            .idle_timeout
+            .map(|timeout: Duration| last_received + timeout);
+        let heartbeat: OptionInstant = self
+            .pong_deadline(ping_sent)
+            .or_else(|| self.ping_due(last_received, ping_sent));
+        match (idle, heartbeat) {
+            (Some(idle), Some(heartbeat)) => Some(idle.min(heartbeat)),
+            (idle, heartbeat) => idle.or(heartbeat),
+        }
+    }
+}
+
+impl Default for WebSocketDeflate {
+    fn default() -> Self {
+        Self {
+            server_no_context_takeover: false,
+            client_no_context_takeover: false,
+            server_max_window_bits: MAX_WINDOW_BITS,
+            client_max_window_bits: MAX_WINDOW_BITS,
+        }
+    }
+}
+
+impl WebSocketDeflate {
+    /// Creates `permessage-deflate` settings with context takeover and full windows.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketDeflate` - The compression settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the server resets its compressor after every message.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether server context takeover is disabled.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketDeflate` - The modified settings.
+    pub fn with_server_no_context_takeover(mut self, enable: bool) -> Self {
+        self.server_no_context_takeover = enable;
+        self
+    }
+
+    /// Sets whether the client is asked to reset its compressor after every message.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether client context takeover is disabled.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketDeflate` - The modified settings.
+    pub fn with_client_no_context_takeover(mut self, enable: bool) -> Self {
+        self.client_no_context_takeover = enable;
+        self
+    }
+
+    /// Sets the largest window the server compresses with.
+    ///
+    /// # Arguments
+    ///
+    /// - `u8` - The window bits, clamped to the range 9 to 15.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketDeflate` - The modified settings.
+    pub fn with_server_max_window_bits(mut self, bits: u8) -> Self {
+        self.server_max_window_bits = bits.clamp(MIN_WINDOW_BITS, MAX_WINDOW_BITS);
+        self
+    }
+
+    /// Sets the largest window the client is asked to compress with.
+    ///
+    /// The limit only applies to clients announcing support for `client_max_window_bits`.
+    ///
+    /// # Arguments
+    ///
+    /// - `u8` - The window bits, clamped to the range 9 to 15.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketDeflate` - The modified settings.
+    pub fn with_client_max_window_bits(mut self, bits: u8) -> Self {
+        self.client_max_window_bits = bits.clamp(MIN_WINDOW_BITS, MAX_WINDOW_BITS);
+        self
+    }
+}
+
+impl WebSocketDeflateAgreement {
+    /// Evaluates a single `permessage-deflate` offer.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - One comma-separated element of the `Sec-WebSocket-Extensions` header.
+    /// - `&WebSocketDeflate` - The settings of the server.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionWebSocketDeflateAgreement` - The agreed parameters, or None if the offer
+    ///   is not `permessage-deflate`, is malformed or cannot be satisfied.
+    pub(crate) fn from_offer(
+        offer: &str,
+        config: &WebSocketDeflate,
+    ) -> OptionWebSocketDeflateAgreement {
+        let mut params = offer.split(SEMICOLON).map(str::trim);
+        if !params.next()?.eq_ignore_ascii_case(PERMESSAGE_DEFLATE) {
+            return None;
+        }
+        let mut agreement: Self = Self {
+            server_no_context_takeover: config.server_no_context_takeover,
+            client_no_context_takeover: config.client_no_context_takeover,
+            server_max_window_bits: config.server_max_window_bits,
+            client_max_window_bits: MAX_WINDOW_BITS,
+            announce_server_window: config.server_max_window_bits < MAX_WINDOW_BITS,
+            announce_client_window: false,
+        };
+        let mut seen: Vec<String> = Vec::new();
+        for param in params {
+            let (name, value) = match param.split_once(EQUAL) {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (param, None),
+            };
+            let name: String = name.to_ascii_lowercase();
+            if seen.contains(&name) {
+                return None;
+            }
+            let window_bits: Option<u8> = match value {
+                Some(value) => {
+                    let bits: u8 = value.parse().ok()?;
+                    if !(8..=MAX_WINDOW_BITS).contains(&bits) {
+                        return None;
+                    }
+                    Some(bits)
+                }
+                None => None,
+            };
+            match (name.as_str(), value) {
+                (SERVER_NO_CONTEXT_TAKEOVER, None) => agreement.server_no_context_takeover = true,
+                (CLIENT_NO_CONTEXT_TAKEOVER, None) => agreement.client_no_context_takeover = true,
+                (SERVER_MAX_WINDOW_BITS, Some(_)) => {
+                    let bits: u8 = window_bits?;
+                    if bits < MIN_WINDOW_BITS {
+                        return None;
+                    }
+                    agreement.server_max_window_bits = agreement.server_max_window_bits.min(bits);
+                    agreement.announce_server_window = true;
+                }
+                (CLIENT_MAX_WINDOW_BITS, _) => {
+                    let limit: u8 = window_bits.unwrap_or(MAX_WINDOW_BITS);
+                    if limit < MIN_WINDOW_BITS {
+                        return None;
+                    }
+                    agreement.client_max_window_bits =
+                        config.client_max_window_bits.clamp(MIN_WINDOW_BITS, limit);
+                    agreement.announce_client_window =
+                        agreement.client_max_window_bits < MAX_WINDOW_BITS || value.is_some();
+                }
+                _ => return None,
+            }
+            seen.push(name);
+        }
+        Some(agreement)
+    }
+
+    /// Renders the agreement as the `Sec-WebSocket-Extensions` response header value.
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The header value.
+    pub(crate) fn to_header(&self) -> String {
+        let mut params: Vec<String> = vec![PERMESSAGE_DEFLATE.to_owned()];
+        if self.server_no_context_takeover {
+            params.push(SERVER_NO_CONTEXT_TAKEOVER.to_owned());
+        }
+        if self.client_no_context_takeover {
+            params.push(CLIENT_NO_CONTEXT_TAKEOVER.to_owned());
+        }
+        if self.announce_server_window {
+            params.push(format!(
+                "{}{}{}",
+                SERVER_MAX_WINDOW_BITS, EQUAL, self.server_max_window_bits
+            ));
+        }
+        if self.announce_client_window {
+            params.push(format!(
+                "{}{}{}",
+                CLIENT_MAX_WINDOW_BITS, EQUAL, self.client_max_window_bits
+            ));
+        }
+        params.join(&format!("{} ", SEMICOLON))
+    }
+
+    /// Creates the decompressor for messages sent by the client.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketInflater` - The decompressor.
+    pub(crate) fn inflater(&self) -> WebSocketInflater {
+        WebSocketInflater {
+            decompress: DeflateDecompress::new_with_window_bits(false, self.client_max_window_bits),
+            no_context_takeover: self.client_no_context_takeover,
+        }
+    }
+
+    /// Creates the compressor for messages sent by the server.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketDeflater` - The compressor.
+    pub(crate) fn deflater(&self) -> WebSocketDeflater {
+        WebSocketDeflater {
+            compress: DeflateCompress::new_with_window_bits(
+                DeflateLevel::default(),
+                false,
+                self.server_max_window_bits,
+            ),
+            no_context_takeover: self.server_no_context_takeover,
+        }
+    }
+}
+
+impl WebSocketInflater {
+    /// Decompresses a complete message.
+    ///
+    /// Decompression stops as soon as the output exceeds the size limit, so the caller
+    /// can reject the message without inflating all of it.
+    ///
+    /// # Arguments
+    ///
+    /// - `Vec<u8>` - The compressed message payload.
+    /// - `usize` - The size limit of the decompressed message.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultVecU8String` - The decompressed payload or an error message.
+    pub(crate) fn inflate(&mut self, mut message: Vec<u8>, max_len: usize) -> ResultVecU8String {
+        message.extend_from_slice(&DEFLATE_TRAILER);
+        let start: u64 = self.decompress.total_in();
+        let mut output: Vec<u8> = Vec::with_capacity(message.len() * 2);
+        loop {
+            if output.len() == output.capacity() {
+                output.reserve(output.capacity().max(DEFAULT_BUFFER_SIZE));
+            }
+            let consumed: usize = (self.decompress.total_in() - start) as usize;
+            let produced: usize = output.len();
+            self.decompress
+                .decompress_vec(&message[consumed..], &mut output, FlushDecompress::Sync)
+                .map_err(|err| err.to_string())?;
+            let total: usize = (self.decompress.total_in() - start) as usize;
+            if output.len() > max_len {
+                return Ok(output);
+            }
+            if total == message.len() && output.len() < output.capacity() {
+                break;
+            }
+            if total == consumed && output.len() == produced {
+                return Err("truncated compressed message".to_owned());
+            }
+        }
+        if self.no_context_takeover {
+            self.decompress.reset(false);
+        }
+        Ok(output)
+    }
+}
+
+impl WebSocketDeflater {
+    /// Compresses a complete message.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The message payload.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultVecU8String` - The compressed payload or an error message.
+    pub(crate) fn deflate(&mut self, message: &[u8]) -> ResultVecU8String {
+        self.deflate_fragment(message, true)
+    }
+
+    /// Compresses one fragment of a message.
+    ///
+    /// Every fragment is flushed on its own, and only the output of the final one has
+    /// the empty block trailer removed, so the fragments concatenate to a valid message.
+    ///
+    /// # Arguments
+    ///
+    /// - `&[u8]` - The fragment payload.
+    /// - `bool` - Whether this is the final fragment of the message.
+    ///
+    /// # Returns
+    ///
+    /// - `ResultVecU8String` - The compressed fragment or an error message.
+    pub(crate) fn deflate_fragment(&mut self, message: &[u8], fin: bool) -> ResultVecU8String {
+        let start: u64 = self.compress.total_in();
+        let mut output: Vec<u8> = Vec::with_capacity(message.len() / 2 + DEFLATE_TRAILER.len());
+        loop {
+            if output.len() == output.capacity() {
+                output.reserve(output.capacity().max(DEFAULT_BUFFER_SIZE));
+            }
+            let consumed: usize = (self.compress.total_in() - start) as usize;
+            self.compress
+                .compress_vec(&message[consumed..], &mut output, FlushCompress::Sync)
+                .map_err(|err| err.to_string())?;
+            let total: usize = (self.compress.total_in() - start) as usize;
+            if total == message.len() && output.len() < output.capacity() {
+                break;
+            }
+        }
+        if !fin {
+            return Ok(output);
+        }
+        if output.ends_with(&DEFLATE_TRAILER) {
+            output.truncate(output.len() - DEFLATE_TRAILER.len());
+        }
+        if output.is_empty() {
+            output.push(0);
+        }
+        if self.no_context_takeover {
+            self.compress.reset();
+        }
+        Ok(output)
+    }
+}
+
+impl WebSocketPolicy {
+    /// Creates a policy accepting every origin and negotiating no subprotocol.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketPolicy` - The upgrade policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the supported subprotocols.
+    ///
+    /// The first protocol offered by the client that is supported is selected.
+    ///
+    /// # Arguments
+    ///
+    /// - `I` - The subprotocol names.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketPolicy` - The modified policy.
+    pub fn with_protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: ToString,
+    {
+        self.protocols = protocols
+            .into_iter()
+            .map(|protocol: P| protocol.to_string())
+            .collect();
+        self
+    }
+
+    /// Sets whether the client must offer one of the supported subprotocols.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether a subprotocol is required.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketPolicy` - The modified policy.
+    pub fn with_protocol_required(mut self, required: bool) -> Self {
+        self.protocol_required = required;
+        self
+    }
+
+    /// Sets the allowed values of the `Origin` header.
+    ///
+    /// Origins are compared case-insensitively, and `*` allows every origin. Requests
+    /// without an `Origin` header, which browsers always send, are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// - `I` - The allowed origins, such as `https://example.com`.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketPolicy` - The modified policy.
+    pub fn with_allowed_origins<I, O>(mut self, origins: I) -> Self
+    where
+        I: IntoIterator<Item = O>,
+        O: ToString,
+    {
+        self.allowed_origins = origins
+            .into_iter()
+            .map(|origin: O| origin.to_string())
+            .collect();
+        self
+    }
+
+    /// Checks whether an origin is allowed.
+    ///
+    /// # Arguments
+    ///
+    /// - `Option<&str>` - The `Origin` header of the request, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the origin is allowed, false otherwise.
+    pub(crate) fn is_origin_allowed(&self, origin: Option<&str>) -> bool {
+        let Some(origin) = origin else {
+            return true;
+        };
+        self.allowed_origins.is_empty()
+            || self.allowed_origins.iter().any(|allowed: &String| {
+                allowed == ANY_ORIGIN || allowed.eq_ignore_ascii_case(origin.trim())
+            })
+    }
+
+    /// Merges a route-specific policy over the server-level one.
+    ///
+    /// Subprotocols and allowed origins left empty on the route are inherited from the
+    /// server, and a subprotocol is required if the route or the inherited settings
+    /// require one.
+    ///
+    /// # Arguments
+    ///
+    /// - `&WebSocketPolicy` - The server-level policy.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketPolicy` - The effective policy of the route.
+    pub(crate) fn merge(&self, server: &WebSocketPolicy) -> WebSocketPolicy {
+        let inherit_protocols: bool = self.protocols.is_empty();
+        WebSocketPolicy {
+            protocols: if inherit_protocols {
+                server.protocols.clone()
+            } else {
+                self.protocols.clone()
+            },
+            protocol_required: self.protocol_required
+                || (inherit_protocols && server.protocol_required),
+            allowed_origins: if self.allowed_origins.is_empty() {
+                server.allowed_origins.clone()
+            } else {
+                self.allowed_origins.clone()
+            },
+        }
+    }
+
+    /// Selects the subprotocol of the connection.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The comma-separated `Sec-WebSocket-Protocol` values offered by the client.
+    ///
+    /// # Returns
+    ///
+    /// - `OptionString` - The first offered protocol that is supported, if any.
+    pub(crate) fn select_protocol(&self, offered: &str) -> OptionString {
+        offered
+            .split(COMMA)
+            .map(str::trim)
+            .find(|protocol: &&str| {
+                self.protocols
+                    .iter()
+                    .any(|supported: &String| supported == protocol)
+            })
+            .map(str::to_owned)
+    }
+}
+
+impl WebSocketMember {
+    /// Creates a membership with a new id and a bounded broadcast queue.
+    ///
+    /// # Arguments
+    ///
+    /// - `usize` - The capacity of the queue.
+    ///
+    /// # Returns
+    ///
+    /// - `(WebSocketMember, ReceiverWebSocketQueuedMessage)` - The membership and the receiving half of its queue.
+    pub(crate) fn new(capacity: usize) -> (Self, ReceiverWebSocketQueuedMessage) {
+        let (sender, receiver): (SenderWebSocketQueuedMessage, ReceiverWebSocketQueuedMessage) =
+            mpsc::channel(capacity);
+        let member: WebSocketMember = WebSocketMember {
+            id: next_ws_member_id(),
+            sender,
+        };
+        (member, receiver)
+    }
+}
+
+impl WebSocketHub {
+    /// Creates an empty hub.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketHub` - The new hub.
+    pub(crate) fn new() -> Self {
+        Self(arc_rwlock(HashMap::new()))
+    }
+
+    /// Adds a member to a room.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The room name.
+    /// - `&WebSocketMember` - The joining member.
+    pub(crate) async fn join(&self, room: &str, member: &WebSocketMember) {
+        self.0
+            .write()
+            .await
+            .entry(room.to_owned())
+            .or_default()
+            .insert(member.id, member.sender.clone());
+    }
+
+    /// Removes a member from a room, dropping the room once it is empty.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The room name.
+    /// - `u64` - The id of the leaving member.
+    pub(crate) async fn leave(&self, room: &str, id: u64) {
+        let mut rooms: RwLockWriteGuard<HashMapStringRoomMembers> = self.0.write().await;
+        if let Some(members) = rooms.get_mut(room) {
+            members.remove(&id);
+            if members.is_empty() {
+                rooms.remove(room);
+            }
+        }
+    }
+
+    /// Sends a text message to every member of a room.
+    ///
+    /// The message is queued without waiting, so a member whose queue is full misses
+    /// the message instead of delaying the others.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The room name implementing `ToString`.
+    /// - `T` - The text implementing `ToString`.
+    /// - `Option<&Context>` - A connection that should not receive the message, usually the sender.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The number of members the message was queued for.
+    pub async fn broadcast_text<R, T>(&self, room: R, text: T, except: Option<&Context>) -> usize
+    where
+        R: ToString,
+        T: ToString,
+    {
+        self.broadcast_message(
+            &room.to_string(),
+            WebSocketOpcode::Text,
+            text.to_string().into_bytes(),
+            except,
+        )
+        .await
+    }
+
+    /// Sends a binary message to every member of a room.
+    ///
+    /// The message is queued without waiting, so a member whose queue is full misses
+    /// the message instead of delaying the others.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The room name implementing `ToString`.
+    /// - `B` - The binary payload.
+    /// - `Option<&Context>` - A connection that should not receive the message, usually the sender.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The number of members the message was queued for.
+    pub async fn broadcast_binary<R, B>(&self, room: R, data: B, except: Option<&Context>) -> usize
+    where
+        R: ToString,
+        B: Into<Vec<u8>>,
+    {
+        self.broadcast_message(
+            &room.to_string(),
+            WebSocketOpcode::Binary,
+            data.into(),
+            except,
+        )
+        .await
+    }
+
+    /// Queues a data message for every member of a room.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str` - The room name.
+    /// - `WebSocketOpcode` - The opcode of the message, `Text` or `Binary`.
+    /// - `Vec<u8>` - The payload of the message.
+    /// - `Option<&Context>` - A connection that should not receive the message.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The number of members the message was queued for.
+    async fn broadcast_message(
+        &self,
+        room: &str,
+        opcode: WebSocketOpcode,
+        payload: Vec<u8>,
+        except: Option<&Context>,
+    ) -> usize {
+        let message: WebSocketQueuedMessage = WebSocketQueuedMessage {
+            opcode,
+            payload: Arc::new(payload),
+        };
+        let except: Option<u64> = match except {
+            Some(ctx) => ctx.get_ws_member_id().await,
+            None => None,
+        };
+        let rooms: RwLockReadGuard<HashMapStringRoomMembers> = self.0.read().await;
+        let Some(members) = rooms.get(room) else {
+            return 0;
+        };
+        members
+            .iter()
+            .filter(|(id, _)| Some(**id) != except)
+            .filter(|(_, sender)| sender.try_send(message.clone()).is_ok())
+            .count()
+    }
+
+    /// Counts the members of a room.
+    ///
+    /// # Arguments
+    ///
+    /// - `R` - The room name implementing `ToString`.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The number of connections in the room.
+    pub async fn get_room_size<R: ToString>(&self, room: R) -> usize {
+        self.0
+            .read()
+            .await
+            .get(&room.to_string())
+            .map_or(0, |members| members.len())
+    }
+}
+
+impl WebSocketMessageWriter {
+    /// Creates a writer for a fragmented message.
+    ///
+    /// # Arguments
+    ///
+    /// - `ConnectionStream` - The stream of the connection.
+    /// - `OwnedMutexGuardWebSocketWriter` - The locked write side of the connection.
+    /// - `WebSocketOpcode` - The opcode of the message.
+    ///
+    /// # Returns
+    ///
+    /// - `WebSocketMessageWriter` - The message writer.
+    pub(crate) fn new(
+        stream: ConnectionStream,
+        writer: OwnedMutexGuardWebSocketWriter,
+        opcode: WebSocketOpcode,
+    ) -> Self {
+        Self {
+            stream,
+            writer,
+            opcode,
+            started: false,
+        }
+    }
+
+    /// Writes one frame of the message.
+    ///
+    /// # Arguments
+    ///
+    /// - `bool` - Whether this is the final frame of the message.
+    /// - `&[u8]` - The fragment payload.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    async fn write_fragment(&mut self, fin: bool, payload: &[u8]) -> ResponseResult {
+        let opcode: WebSocketOpcode = if self.started {
+            WebSocketOpcode::Continuation
+        } else {
+            self.opcode
+        };
+        let frame: Vec<u8> = match self.writer.get_mut_deflater() {
+            Some(deflater) => {
+                let compressed: Vec<u8> = deflater
+                    .deflate_fragment(payload, fin)
+                    .map_err(ResponseError::Response)?;
+                let rsv: u8 = if self.started { 0 } else { WS_RSV1_BIT };
+                encode_ws_frame(fin, rsv, opcode, &compressed)
+            }
+            None => encode_ws_frame(fin, 0, opcode, payload),
+        };
+        self.started = true;
+        self.stream.write_all(&frame).await
+    }
+
+    /// Sends the next fragment of the message as a non-final frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `B` - The fragment payload.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn send<B: AsRef<[u8]>>(&mut self, fragment: B) -> ResponseResult {
+        self.write_fragment(false, fragment.as_ref()).await
+    }
+
+    /// Sends the last fragment of the message as the final frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `B` - The fragment payload, which may be empty.
+    ///
+    /// # Returns
+    ///
+    /// - `ResponseResult` - The outcome of the send operation.
+    pub async fn finish<B: AsRef<[u8]>>(mut self, fragment: B) -> ResponseResult {
+        self.write_fragment(true, fragment.as_ref()).await
+    }
+}
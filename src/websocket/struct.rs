@@ -0,0 +1,219 @@
+use crate::*;
+
+/// A single decoded WebSocket frame.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug)]
+pub(crate) struct WebSocketFrameData {
+    /// Whether this frame is the final fragment of a message.
+    pub(super) fin: bool,
+    /// The reserved extension bits of the frame.
+    pub(super) rsv: u8,
+    /// The opcode of the frame.
+    pub(super) opcode: WebSocketOpcode,
+    /// Whether the payload was masked by the sender.
+    pub(super) masked: bool,
+    /// The unmasked payload of the frame.
+    #[debug(skip)]
+    pub(super) payload: Vec<u8>,
+}
+
+/// The read side of a WebSocket connection.
+///
+/// Keeps bytes that were received but not yet decoded, and the fragments of a
+/// message whose final frame has not arrived yet.
+#[derive(CustomDebug, Getter, GetterMut, DisplayDebug)]
+pub(crate) struct WebSocketReader {
+    /// Received bytes that do not form a complete frame yet.
+    #[debug(skip)]
+    pub(super) buffer: Vec<u8>,
+    /// The opcode of the message currently being reassembled.
+    pub(super) message_opcode: Option<WebSocketOpcode>,
+    /// Whether the message currently being reassembled is compressed.
+    pub(super) message_compressed: bool,
+    /// The payload collected so far for the current message.
+    #[debug(skip)]
+    pub(super) message: RequestBody,
+    /// The decompressor of the negotiated `permessage-deflate` extension.
+    pub(super) inflater: OptionWebSocketInflater,
+    /// The size limit of a reassembled message.
+    pub(super) max_message_size: usize,
+    /// The close frame failing the connection after a protocol violation, if any.
+    pub(super) violation: OptionWebSocketClose,
+}
+
+/// The write side of a WebSocket connection.
+///
+/// Holding its lock guarantees that frames written by concurrent tasks are not interleaved.
+#[derive(Default, CustomDebug, Getter, GetterMut, DisplayDebug)]
+pub(crate) struct WebSocketWriter {
+    /// The compressor of the negotiated `permessage-deflate` extension.
+    pub(super) deflater: OptionWebSocketDeflater,
+}
+
+/// The `permessage-deflate` settings offered by the server.
+///
+/// Window bits are limited to the range 9 to 15 supported by zlib.
+#[derive(Clone, Copy, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct WebSocketDeflate {
+    /// Whether the server resets its compressor after every message.
+    #[get(pub)]
+    pub(super) server_no_context_takeover: bool,
+    /// Whether the client is asked to reset its compressor after every message.
+    #[get(pub)]
+    pub(super) client_no_context_takeover: bool,
+    /// The largest LZ77 window the server compresses with.
+    #[get(pub)]
+    pub(super) server_max_window_bits: u8,
+    /// The largest LZ77 window the client is asked to compress with.
+    #[get(pub)]
+    pub(super) client_max_window_bits: u8,
+}
+
+/// The `permessage-deflate` parameters agreed on with a client.
+#[derive(Clone, Copy, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub(crate) struct WebSocketDeflateAgreement {
+    /// Whether the server resets its compressor after every message.
+    pub(super) server_no_context_takeover: bool,
+    /// Whether the client resets its compressor after every message.
+    pub(super) client_no_context_takeover: bool,
+    /// The window the server compresses with.
+    pub(super) server_max_window_bits: u8,
+    /// The window the client compresses with.
+    pub(super) client_max_window_bits: u8,
+    /// Whether the server window is announced in the response.
+    pub(super) announce_server_window: bool,
+    /// Whether the client window is announced in the response.
+    pub(super) announce_client_window: bool,
+}
+
+/// The decompressor of incoming compressed messages.
+#[derive(CustomDebug, DisplayDebug)]
+pub(crate) struct WebSocketInflater {
+    /// The raw deflate decompressor.
+    #[debug(skip)]
+    pub(super) decompress: DeflateDecompress,
+    /// Whether the decompressor is reset after every message.
+    pub(super) no_context_takeover: bool,
+}
+
+/// The compressor of outgoing messages.
+#[derive(CustomDebug, DisplayDebug)]
+pub(crate) struct WebSocketDeflater {
+    /// The raw deflate compressor.
+    #[debug(skip)]
+    pub(super) compress: DeflateCompress,
+    /// Whether the compressor is reset after every message.
+    pub(super) no_context_takeover: bool,
+}
+
+/// The status code and reason of a WebSocket close handshake.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct WebSocketClose {
+    /// The close status code, such as `WS_CLOSE_NORMAL`.
+    #[get(pub)]
+    pub(super) code: u16,
+    /// The reason given for the closure, possibly empty.
+    #[get(pub)]
+    pub(super) reason: String,
+}
+
+/// The keep-alive settings of WebSocket connections.
+///
+/// Every setting is disabled by default.
+#[derive(Clone, Copy, Default, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct WebSocketHeartbeat {
+    /// How long the connection may stay quiet before the server sends a ping.
+    #[get(pub)]
+    pub(super) ping_interval: OptionDuration,
+    /// How long the server waits for the pong answering its ping.
+    #[get(pub)]
+    pub(super) pong_timeout: OptionDuration,
+    /// How long the connection may go without receiving any frame.
+    #[get(pub)]
+    pub(super) idle_timeout: OptionDuration,
+}
+
+/// The checks applied to a WebSocket upgrade request.
+///
+/// The default policy accepts every origin and negotiates no subprotocol.
+#[derive(Clone, Default, CustomDebug, Getter, DisplayDebug, PartialEq, Eq)]
+pub struct WebSocketPolicy {
+    /// The supported subprotocols.
+    #[get(pub)]
+    pub(super) protocols: Vec<String>,
+    /// Whether the client must offer one of the supported subprotocols.
+    #[get(pub)]
+    pub(super) protocol_required: bool,
+    /// The allowed values of the `Origin` header, empty to allow every origin.
+    #[get(pub)]
+    pub(super) allowed_origins: Vec<String>,
+}
+
+/// Sends one WebSocket message as a sequence of fragments.
+///
+/// The writer holds the write side of the connection until it is dropped, so other
+/// messages cannot be interleaved with the fragments. A message that is dropped
+/// without calling `finish` is left incomplete and the peer will fail the connection.
+#[derive(CustomDebug, DisplayDebug)]
+pub struct WebSocketMessageWriter {
+    /// The stream of the connection.
+    #[debug(skip)]
+    pub(super) stream: ConnectionStream,
+    /// The locked write side of the connection.
+    #[debug(skip)]
+    pub(super) writer: OwnedMutexGuardWebSocketWriter,
+    /// The opcode of the message.
+    pub(super) opcode: WebSocketOpcode,
+    /// Whether the first fragment has been sent.
+    pub(super) started: bool,
+}
+
+/// A message waiting in a connection's broadcast queue.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug)]
+pub(crate) struct WebSocketQueuedMessage {
+    /// The opcode of the message.
+    pub(super) opcode: WebSocketOpcode,
+    /// The payload of the message, shared by every recipient.
+    #[debug(skip)]
+    pub(super) payload: Arc<Vec<u8>>,
+}
+
+/// A connection's membership in the broadcast hub.
+#[derive(Clone, CustomDebug, Getter, DisplayDebug)]
+pub(crate) struct WebSocketMember {
+    /// The id identifying the connection in every room.
+    pub(super) id: u64,
+    /// The bounded queue drained by the connection's writer task.
+    pub(super) sender: SenderWebSocketQueuedMessage,
+}
+
+/// A registry of WebSocket rooms used to send one message to many connections.
+///
+/// Every server owns a hub, connections enter and leave rooms with `Context::ws_join`
+/// and `Context::ws_leave`, and are removed from all rooms when they disconnect.
+#[derive(Clone, CustomDebug, DisplayDebug)]
+pub struct WebSocketHub(pub(super) ArcRwLock<HashMapStringRoomMembers>);
+
+/// The per-connection WebSocket state shared by all clones of a context.
+#[derive(Clone, Default, CustomDebug, Data, DisplayDebug)]
+pub(crate) struct WebSocketConnection {
+    /// The read side of the connection.
+    pub(super) reader: ArcMutexWebSocketReader,
+    /// The write side of the connection.
+    pub(super) writer: ArcMutexWebSocketWriter,
+    /// Whether a close frame has been sent to the peer.
+    pub(super) close_sent: bool,
+    /// Whether a close frame has been received from the peer.
+    pub(super) close_received: bool,
+    /// The close frame of the side that initiated the closure.
+    pub(super) close: OptionWebSocketClose,
+    /// When the last frame was received from the peer.
+    pub(super) last_received: OptionInstant,
+    /// When the unanswered heartbeat ping was sent, if any.
+    pub(super) ping_sent: OptionInstant,
+    /// The subprotocol selected during the handshake, if any.
+    pub(super) protocol: OptionString,
+    /// The broadcast membership, created when the connection first joins a room.
+    pub(super) member: OptionWebSocketMember,
+    /// The rooms the connection has joined.
+    pub(super) rooms: HashSetString,
+}
@@ -0,0 +1,70 @@
+use crate::*;
+
+/// A decoded WebSocket frame together with the number of bytes it occupied.
+pub(crate) type OptionWebSocketFrameDataUsize = Option<(WebSocketFrameData, usize)>;
+
+/// The result of reading a single WebSocket frame from the stream.
+pub(crate) type ResultWebSocketFrameDataRequestError = Result<WebSocketFrameData, RequestError>;
+
+/// The result of feeding a data frame into the message reassembly.
+pub(crate) type ResultOptionRequestBodyRequestError = Result<Option<RequestBody>, RequestError>;
+
+/// The result of reading a complete WebSocket message.
+pub type ResultRequestBodyRequestError = Result<RequestBody, RequestError>;
+
+/// An optional WebSocket close frame.
+pub type OptionWebSocketClose = Option<WebSocketClose>;
+
+/// An optional `permessage-deflate` configuration.
+pub type OptionWebSocketDeflate = Option<WebSocketDeflate>;
+
+/// An optional `permessage-deflate` agreement.
+pub(crate) type OptionWebSocketDeflateAgreement = Option<WebSocketDeflateAgreement>;
+
+/// An optional message decompressor.
+pub(crate) type OptionWebSocketInflater = Option<WebSocketInflater>;
+
+/// An optional message compressor.
+pub(crate) type OptionWebSocketDeflater = Option<WebSocketDeflater>;
+
+/// The result of compressing or decompressing a message.
+pub(crate) type ResultVecU8String = Result<Vec<u8>, String>;
+
+/// An optional point in time.
+pub(crate) type OptionInstant = Option<Instant>;
+
+/// Per-route heartbeat settings.
+pub(crate) type VecRoutePatternWebSocketHeartbeat = Vec<(RoutePattern, WebSocketHeartbeat)>;
+
+/// Per-route upgrade policies.
+pub(crate) type VecRoutePatternWebSocketPolicy = Vec<(RoutePattern, WebSocketPolicy)>;
+
+/// The sending half of a connection's broadcast queue.
+pub(crate) type SenderWebSocketQueuedMessage = Sender<WebSocketQueuedMessage>;
+
+/// The receiving half of a connection's broadcast queue.
+pub(crate) type ReceiverWebSocketQueuedMessage = Receiver<WebSocketQueuedMessage>;
+
+/// An optional broadcast membership.
+pub(crate) type OptionWebSocketMember = Option<WebSocketMember>;
+
+/// The queues of the members of a room, keyed by member id.
+pub(crate) type HashMapU64SenderWebSocketQueuedMessage = HashMap<u64, SenderWebSocketQueuedMessage>;
+
+/// The members of every room, keyed by room name.
+pub(crate) type HashMapStringRoomMembers = HashMap<String, HashMapU64SenderWebSocketQueuedMessage>;
+
+/// A set of room names.
+pub(crate) type HashSetString = HashSet<String>;
+
+/// A shared, lockable WebSocket reader.
+pub(crate) type ArcMutexWebSocketReader = ArcMutex<WebSocketReader>;
+
+/// A shared, lockable WebSocket writer.
+pub(crate) type ArcMutexWebSocketWriter = ArcMutex<WebSocketWriter>;
+
+/// An owned lock on the write side of a WebSocket connection.
+pub(crate) type OwnedMutexGuardWebSocketWriter = OwnedMutexGuard<WebSocketWriter>;
+
+/// The result of starting a fragmented WebSocket message.
+pub type ResultWebSocketMessageWriterResponseError = Result<WebSocketMessageWriter, ResponseError>;